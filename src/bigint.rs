@@ -0,0 +1,111 @@
+//! Zigzag encoding of arbitrary-precision integers.
+//!
+//! An arbitrary-precision integer is given by its sign and the little-endian
+//! bytes of its magnitude, the form most bignum libraries convert to and
+//! from. Its encoding is the little-endian bytes of an unsigned integer,
+//! computed as for the primitive types: `2 * x` for non-negative `x`, and
+//! `2 * |x| - 1` for negative `x`. The encoding of a value which fits in a
+//! primitive type is therefore the same as that type's.
+//!
+//! With the `num-bigint` feature enabled, `num_bigint::BigInt` implements
+//! [`ZigZagEncode`](crate::ZigZagEncode) to `BigUint` directly. These
+//! functions are for other bignum libraries, and for formats which carry the
+//! bytes themselves.
+//!
+//! # Examples
+//!
+//! ```
+//! use zigzag::bigint::{zigzag_decode_le, zigzag_encode_le};
+//!
+//! // -(2^64), which doesn't fit in an `i64`.
+//! let magnitude = [0, 0, 0, 0, 0, 0, 0, 0, 1];
+//! let encoded = zigzag_encode_le(true, &magnitude);
+//! assert_eq!(encoded, [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+//! assert_eq!(zigzag_decode_le(&encoded), (true, magnitude.to_vec()));
+//! ```
+
+use alloc::vec::Vec;
+
+/// Removes the zero bytes from the most significant end of `bytes`.
+fn trim(mut bytes: Vec<u8>) -> Vec<u8> {
+    let len = bytes.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    bytes.truncate(len);
+    bytes
+}
+
+/// Zigzag encodes the integer with the given sign and little-endian
+/// magnitude, returning the little-endian bytes of the result.
+///
+/// The result has no zero bytes at its most significant end, so zero
+/// encodes to no bytes at all. A negative sign with a magnitude of zero
+/// encodes like zero.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::bigint::zigzag_encode_le;
+///
+/// assert_eq!(zigzag_encode_le(false, &[0x80]), [0x00, 0x01]);
+/// assert_eq!(zigzag_encode_le(true, &[0x80]), [0xff]);
+/// assert_eq!(zigzag_encode_le(true, &[0x00]), []);
+/// ```
+pub fn zigzag_encode_le(negative: bool, magnitude: &[u8]) -> Vec<u8> {
+    let mut out = trim(magnitude.to_vec());
+    // `2 * |x| - 1` is `2 * (|x| - 1) + 1`, so subtract one first.
+    let negative = negative && !out.is_empty();
+    if negative {
+        for byte in out.iter_mut() {
+            let (value, borrow) = byte.overflowing_sub(1);
+            *byte = value;
+            if !borrow {
+                break;
+            }
+        }
+    }
+    let mut carry = u8::from(negative);
+    for byte in out.iter_mut() {
+        let next = *byte >> 7;
+        *byte = *byte << 1 | carry;
+        carry = next;
+    }
+    out.push(carry);
+    trim(out)
+}
+
+/// Zigzag decodes the little-endian bytes of an encoded integer, returning
+/// whether it's negative and the little-endian bytes of its magnitude.
+///
+/// The magnitude has no zero bytes at its most significant end, so that of
+/// zero is no bytes at all.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::bigint::zigzag_decode_le;
+///
+/// assert_eq!(zigzag_decode_le(&[0x00, 0x01]), (false, vec![0x80]));
+/// assert_eq!(zigzag_decode_le(&[0xff]), (true, vec![0x80]));
+/// assert_eq!(zigzag_decode_le(&[]), (false, vec![]));
+/// ```
+pub fn zigzag_decode_le(encoded: &[u8]) -> (bool, Vec<u8>) {
+    let negative = encoded.first().is_some_and(|&b| b & 1 == 1);
+    let mut out = encoded.to_vec();
+    let mut carry = 0;
+    for byte in out.iter_mut().rev() {
+        let next = *byte & 1;
+        *byte = *byte >> 1 | carry << 7;
+        carry = next;
+    }
+    // `|x|` is `(encoded - 1) / 2 + 1` for negative `x`.
+    if negative {
+        out.push(0);
+        for byte in out.iter_mut() {
+            let (value, overflow) = byte.overflowing_add(1);
+            *byte = value;
+            if !overflow {
+                break;
+            }
+        }
+    }
+    (negative, trim(out))
+}
@@ -0,0 +1,175 @@
+//! Encodes and decodes streams of zigzag-encoded integers.
+//!
+//! `zigzag encode` reads signed integers from standard input and writes their
+//! encodings to standard output, one after the other. `zigzag decode` does
+//! the reverse. By default, integers are read and written as text, separated
+//! by whitespace on input and one per line on output; with `--raw`, they are
+//! little-endian `i64`s instead.
+//!
+//! ```text
+//! $ echo "0 -1 150" | zigzag encode --format varint | xxd -p
+//! 0001ac02
+//! $ echo "0 -1 150" | zigzag encode | zigzag decode
+//! 0
+//! -1
+//! 150
+//! ```
+
+use std::convert::TryInto;
+use std::io::{self, BufWriter, Read, Write};
+use std::process::ExitCode;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use zigzag::endian::ZigZagBytes;
+use zigzag::DecodeError;
+
+/// The wire format of the encoded stream.
+#[derive(Clone, Copy)]
+enum Format {
+    /// LEB128 varints, as in Protocol Buffers.
+    Varint,
+    /// Prefix varints, whose first byte gives their length.
+    Prefix,
+    /// Fixed-width little-endian 64-bit integers.
+    Fixed,
+}
+
+impl Format {
+    fn from_name(name: &str) -> Format {
+        match name {
+            "varint" => Format::Varint,
+            "prefix" => Format::Prefix,
+            _ => Format::Fixed,
+        }
+    }
+
+    fn encode(self, x: i64, out: &mut Vec<u8>) {
+        match self {
+            Format::Varint => {
+                zigzag::varint::write_zigzag_varint(x, out);
+            }
+            Format::Prefix => {
+                zigzag::prefix_varint::write_zigzag_varint(x, out);
+            }
+            Format::Fixed => out.extend_from_slice(&x.to_zigzag_le_bytes()),
+        }
+    }
+
+    /// Decodes the value at the start of `input`, returning it with the
+    /// number of bytes it occupied.
+    fn decode(self, input: &[u8]) -> Result<(i64, usize), DecodeError> {
+        match self {
+            Format::Varint => Ok(zigzag::varint::read_zigzag_varint(input)?),
+            Format::Prefix => Ok(zigzag::prefix_varint::read_zigzag_varint(input)?),
+            Format::Fixed => {
+                let bytes = input.get(..8).ok_or_else(|| DecodeError::Truncated {
+                    needed: 8 - input.len(),
+                })?;
+                let x = i64::from_zigzag_le_bytes(bytes.try_into().unwrap());
+                Ok((x, 8))
+            }
+        }
+    }
+}
+
+fn command() -> Command {
+    let format = Arg::new("format")
+        .long("format")
+        .short('f')
+        .help("The wire format of the encoded stream")
+        .value_parser(["varint", "prefix", "fixed"])
+        .default_value("varint");
+    let raw = Arg::new("raw")
+        .long("raw")
+        .help("Read or write the integers as raw little-endian i64s instead of text")
+        .action(ArgAction::SetTrue);
+    Command::new("zigzag")
+        .about("Encodes and decodes streams of zigzag-encoded integers")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("encode")
+                .about("Encodes the integers read from standard input")
+                .arg(format.clone())
+                .arg(raw.clone()),
+        )
+        .subcommand(
+            Command::new("decode")
+                .about("Decodes the stream read from standard input")
+                .arg(format)
+                .arg(raw),
+        )
+}
+
+fn encode(input: &[u8], format: Format, raw: bool) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    if raw {
+        let chunks = input.chunks_exact(8);
+        if !chunks.remainder().is_empty() {
+            return Err(format!(
+                "input length {} isn't a multiple of 8 bytes",
+                input.len()
+            ));
+        }
+        for chunk in chunks {
+            format.encode(i64::from_le_bytes(chunk.try_into().unwrap()), &mut out);
+        }
+    } else {
+        let text = std::str::from_utf8(input).map_err(|err| err.to_string())?;
+        for word in text.split_ascii_whitespace() {
+            let x = word
+                .parse()
+                .map_err(|err| format!("invalid integer `{}`: {}", word, err))?;
+            format.encode(x, &mut out);
+        }
+    }
+    Ok(out)
+}
+
+fn decode(mut input: &[u8], format: Format, raw: bool) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let mut offset = 0;
+    while !input.is_empty() {
+        let (x, len) = format
+            .decode(input)
+            .map_err(|err| format!("at byte {}: {}", offset, err))?;
+        if raw {
+            out.extend_from_slice(&x.to_le_bytes());
+        } else {
+            writeln!(out, "{}", x).unwrap();
+        }
+        input = &input[len..];
+        offset += len;
+    }
+    Ok(out)
+}
+
+fn run(matches: &ArgMatches) -> Result<(), String> {
+    let (name, args) = matches.subcommand().unwrap();
+    let format = Format::from_name(args.get_one::<String>("format").unwrap());
+    let raw = args.get_flag("raw");
+
+    let mut input = Vec::new();
+    io::stdin()
+        .read_to_end(&mut input)
+        .map_err(|err| err.to_string())?;
+    let output = match name {
+        "encode" => encode(&input, format, raw)?,
+        _ => decode(&input, format, raw)?,
+    };
+
+    let mut stdout = BufWriter::new(io::stdout().lock());
+    stdout
+        .write_all(&output)
+        .and_then(|()| stdout.flush())
+        .map_err(|err| err.to_string())
+}
+
+fn main() -> ExitCode {
+    match run(&command().get_matches()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("zigzag: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,263 @@
+//! Zigzag encoding at bit widths that don't match a primitive integer type.
+//!
+//! Values are held in the next wider primitive type. Since the zigzag
+//! encoding of a value doesn't depend on the width it is computed at, the
+//! only extra work over the primitive encoding is checking that the value is
+//! in range for the narrower width.
+//!
+//! [`encode_bits`] and [`decode_bits`], along with their checked variants,
+//! work at any width from 1 to 64 bits, given as a const generic parameter.
+//! To pack such fields back to back in a stream of bytes, with widths chosen
+//! at runtime, use the [`bitstream`](crate::bitstream) module instead.
+
+use core::convert::TryFrom;
+
+use crate::{OutOfRange, ZigZagDecode, ZigZagEncode};
+
+const I24_MIN: i32 = -(1 << 23);
+const I24_MAX: i32 = (1 << 23) - 1;
+const U24_MAX: u32 = (1 << 24) - 1;
+
+/// Zigzag encodes a 24-bit sample, such as one from a 24-bit audio stream,
+/// held in an `i32`.
+///
+/// The result fits in the low 24 bits of the `u32`, so it can be packed into
+/// three bytes.
+///
+/// # Errors
+///
+/// Returns [`OutOfRange`] unless `-(1 << 23) <= x < (1 << 23)`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::bits::encode_i24_sample;
+///
+/// assert_eq!(encode_i24_sample(-1), Ok(1));
+/// assert_eq!(encode_i24_sample(-(1 << 23)), Ok(0xff_ffff));
+/// assert!(encode_i24_sample(1 << 23).is_err());
+/// ```
+#[inline]
+pub fn encode_i24_sample(x: i32) -> Result<u32, OutOfRange> {
+    if (I24_MIN..=I24_MAX).contains(&x) {
+        Ok(x.zigzag_encode())
+    } else {
+        Err(OutOfRange)
+    }
+}
+
+/// Decodes a 24-bit zigzag-encoded sample back into an `i32`.
+///
+/// This is the inverse of [`encode_i24_sample`].
+///
+/// # Errors
+///
+/// Returns [`OutOfRange`] if `x` doesn't fit in 24 bits.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::bits::decode_i24_sample;
+///
+/// assert_eq!(decode_i24_sample(1), Ok(-1));
+/// assert_eq!(decode_i24_sample(0xff_ffff), Ok(-(1 << 23)));
+/// assert!(decode_i24_sample(1 << 24).is_err());
+/// ```
+#[inline]
+pub fn decode_i24_sample(x: u32) -> Result<i32, OutOfRange> {
+    if x <= U24_MAX {
+        Ok(x.zigzag_decode())
+    } else {
+        Err(OutOfRange)
+    }
+}
+
+/// Returns the mask of the low `BITS` bits, failing to compile unless
+/// `1 <= BITS <= 64`.
+#[inline]
+const fn low_bits_mask<const BITS: u32>() -> u64 {
+    const { assert!(BITS >= 1 && BITS <= 64, "BITS must be between 1 and 64") };
+    u64::MAX >> (u64::BITS - BITS)
+}
+
+/// Zigzag encodes the low `BITS` bits of `x`, read as a `BITS`-bit signed
+/// integer.
+///
+/// Any bits of `x` above the field are ignored, so a value outside the range
+/// of the field wraps around, just as it would when stored in it; the result
+/// always fits in the low `BITS` bits of a `u64`. Use [`try_encode_bits`] to
+/// reject such values instead. Widths outside `1..=64` fail to compile.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::bits::encode_bits;
+///
+/// assert_eq!(encode_bits::<6>(-1), 1);
+/// assert_eq!(encode_bits::<6>(-32), 63);
+/// assert_eq!(encode_bits::<6>(31), 62);
+///
+/// // 32 wraps around to -32 in a 6-bit field.
+/// assert_eq!(encode_bits::<6>(32), 63);
+/// assert_eq!(encode_bits::<64>(i64::MIN), u64::MAX);
+/// ```
+#[inline]
+pub const fn encode_bits<const BITS: u32>(x: i64) -> u64 {
+    let mask = low_bits_mask::<BITS>();
+    let shift = i64::BITS - BITS;
+    let field = (x << shift) >> shift;
+    ((field >> (i64::BITS - 1)) as u64 ^ (field << 1) as u64) & mask
+}
+
+/// Decodes the low `BITS` bits of `x` as a zigzag-encoded `BITS`-bit signed
+/// integer.
+///
+/// Any bits of `x` above the field are ignored, so this is the inverse of
+/// [`encode_bits`]. Use [`try_decode_bits`] to reject such bits instead.
+/// Widths outside `1..=64` fail to compile.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::bits::decode_bits;
+///
+/// assert_eq!(decode_bits::<6>(1), -1);
+/// assert_eq!(decode_bits::<6>(63), -32);
+/// assert_eq!(decode_bits::<6>(0xc0 | 63), -32);
+/// ```
+#[inline]
+pub const fn decode_bits<const BITS: u32>(x: u64) -> i64 {
+    let field = x & low_bits_mask::<BITS>();
+    (field >> 1) as i64 ^ -((field & 1) as i64)
+}
+
+/// Zigzag encodes `x` as a `BITS`-bit signed integer.
+///
+/// # Errors
+///
+/// Returns [`OutOfRange`] unless `-(1 << (BITS - 1)) <= x < (1 << (BITS -
+/// 1))`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::bits::try_encode_bits;
+///
+/// assert_eq!(try_encode_bits::<24>(-(1 << 23)), Ok(0xff_ffff));
+/// assert!(try_encode_bits::<24>(1 << 23).is_err());
+/// assert!(try_encode_bits::<1>(-1).is_ok());
+/// assert!(try_encode_bits::<1>(1).is_err());
+/// ```
+#[inline]
+pub const fn try_encode_bits<const BITS: u32>(x: i64) -> Result<u64, OutOfRange> {
+    let encoded = encode_bits::<BITS>(x);
+    if decode_bits::<BITS>(encoded) == x {
+        Ok(encoded)
+    } else {
+        Err(OutOfRange)
+    }
+}
+
+/// Decodes `x` as a zigzag-encoded `BITS`-bit signed integer.
+///
+/// # Errors
+///
+/// Returns [`OutOfRange`] if `x` doesn't fit in `BITS` bits.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::bits::try_decode_bits;
+///
+/// assert_eq!(try_decode_bits::<6>(63), Ok(-32));
+/// assert!(try_decode_bits::<6>(64).is_err());
+/// ```
+#[inline]
+pub const fn try_decode_bits<const BITS: u32>(x: u64) -> Result<i64, OutOfRange> {
+    if x & !low_bits_mask::<BITS>() == 0 {
+        Ok(decode_bits::<BITS>(x))
+    } else {
+        Err(OutOfRange)
+    }
+}
+
+/// Returns the mask of a field `bits` wide, or `OutOfRange` if `fields`
+/// fields of that width don't fit in a `u128`.
+fn field_mask(fields: usize, bits: u32) -> Result<u128, OutOfRange> {
+    let total = u32::try_from(fields)
+        .ok()
+        .and_then(|fields| fields.checked_mul(bits))
+        .ok_or(OutOfRange)?;
+    if total > u128::BITS || bits > u128::BITS {
+        return Err(OutOfRange);
+    }
+    Ok(u128::MAX.checked_shr(u128::BITS - bits).unwrap_or(0))
+}
+
+/// Zigzag encodes every value of `values` and packs the results into a
+/// `u128`, `bits_each` bits per field, with the first value in the least
+/// significant bits.
+///
+/// # Errors
+///
+/// Returns [`OutOfRange`] if `K` fields of `bits_each` bits don't fit in 128
+/// bits, or if the encoding of any value doesn't fit in `bits_each` bits.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::bits::pack_zigzag_fields;
+///
+/// assert_eq!(pack_zigzag_fields([-1, 1, 2], 4), Ok(0x421));
+/// assert!(pack_zigzag_fields([8, 0, 0], 4).is_err());
+/// assert!(pack_zigzag_fields([0; 3], 64).is_err());
+/// ```
+pub fn pack_zigzag_fields<const K: usize>(
+    values: [i64; K],
+    bits_each: u32,
+) -> Result<u128, OutOfRange> {
+    let mask = field_mask(K, bits_each)?;
+    let mut packed = 0u128;
+    for (i, &value) in values.iter().enumerate() {
+        let encoded: u64 = value.zigzag_encode();
+        let encoded = u128::from(encoded);
+        if encoded & !mask != 0 {
+            return Err(OutOfRange);
+        }
+        packed |= encoded << (bits_each * i as u32);
+    }
+    Ok(packed)
+}
+
+/// Unpacks `K` fields of `bits_each` bits from `packed` and zigzag decodes
+/// them.
+///
+/// This is the inverse of [`pack_zigzag_fields`]. Any bits above the last
+/// field are ignored.
+///
+/// # Errors
+///
+/// Returns [`OutOfRange`] if `K` fields of `bits_each` bits don't fit in 128
+/// bits, or if a field wider than 64 bits holds a value that doesn't fit in
+/// a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::bits::unpack_zigzag_fields;
+///
+/// assert_eq!(unpack_zigzag_fields::<3>(0x421, 4), Ok([-1, 1, 2]));
+/// ```
+pub fn unpack_zigzag_fields<const K: usize>(
+    packed: u128,
+    bits_each: u32,
+) -> Result<[i64; K], OutOfRange> {
+    let mask = field_mask(K, bits_each)?;
+    let mut values = [0; K];
+    for (i, value) in values.iter_mut().enumerate() {
+        let field = (packed >> (bits_each * i as u32)) & mask;
+        let encoded = u64::try_from(field).map_err(|_| OutOfRange)?;
+        *value = encoded.zigzag_decode();
+    }
+    Ok(values)
+}
@@ -0,0 +1,209 @@
+//! Reading and writing values which aren't a whole number of bytes long.
+//!
+//! [`BitWriter`] packs fields of any width from 0 to 64 bits back to back,
+//! with no padding between them, and [`BitReader`] reads them back. Bits are
+//! packed most significant first, so a stream of bits reads in order when
+//! its bytes are written out in binary.
+//!
+//! Signed fields are zigzag encoded, as by [`bits`](crate::bits), so small
+//! negative values fit in as few bits as small positive ones.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use zigzag::bitstream::{BitReader, BitWriter};
+//!
+//! let mut writer = BitWriter::new();
+//! writer.write_zigzag(-3, 5)?;
+//! writer.write_zigzag(1000, 11)?;
+//! writer.write_bit(true);
+//! assert_eq!(writer.bit_len(), 17);
+//! assert!(writer.write_zigzag(16, 5).is_err());
+//!
+//! let bytes = writer.into_bytes();
+//! assert_eq!(bytes, [0b00101_111, 0b11010000, 0b1_0000000]);
+//!
+//! let mut reader = BitReader::new(&bytes);
+//! assert_eq!(reader.read_zigzag(5), Ok(-3));
+//! assert_eq!(reader.read_zigzag(11), Ok(1000));
+//! assert_eq!(reader.read_bit(), Ok(true));
+//! assert_eq!(reader.remaining_bits(), 7);
+//! # }
+//! # Ok::<(), zigzag::OutOfRange>(())
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{DecodeError, ZigZagDecode};
+#[cfg(feature = "alloc")]
+use crate::{OutOfRange, ZigZagEncode};
+
+/// Packs fields of up to 64 bits each into bytes.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    /// The number of low bits of the last byte which are still unused.
+    free: u32,
+}
+
+#[cfg(feature = "alloc")]
+impl BitWriter {
+    /// Creates an empty writer.
+    #[inline]
+    pub const fn new() -> Self {
+        BitWriter {
+            bytes: Vec::new(),
+            free: 0,
+        }
+    }
+
+    /// Returns the number of bits written so far.
+    #[inline]
+    pub fn bit_len(&self) -> usize {
+        self.bytes.len() * 8 - self.free as usize
+    }
+
+    /// Appends the low `bits` bits of `value`, ignoring any above them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is greater than 64.
+    pub fn write_bits(&mut self, value: u64, bits: u32) {
+        assert!(bits <= 64, "fields are at most 64 bits wide");
+        let mut remaining = bits;
+        while remaining > 0 {
+            if self.free == 0 {
+                self.bytes.push(0);
+                self.free = 8;
+            }
+            let n = remaining.min(self.free);
+            let chunk = (value >> (remaining - n)) as u8 & (0xff >> (8 - n));
+            *self.bytes.last_mut().unwrap() |= chunk << (self.free - n);
+            self.free -= n;
+            remaining -= n;
+        }
+    }
+
+    /// Appends a single bit.
+    #[inline]
+    pub fn write_bit(&mut self, bit: bool) {
+        self.write_bits(u64::from(bit), 1);
+    }
+
+    /// Zigzag encodes `value` and appends it as a field `bits` wide.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OutOfRange`], without writing anything, unless the encoding
+    /// fits in `bits` bits, that is, unless `-(1 << (bits - 1)) <= value <
+    /// (1 << (bits - 1))`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is greater than 64.
+    pub fn write_zigzag(&mut self, value: i64, bits: u32) -> Result<(), OutOfRange> {
+        assert!(bits <= 64, "fields are at most 64 bits wide");
+        let encoded = value.zigzag_encode();
+        if bits < 64 && encoded >> bits != 0 {
+            return Err(OutOfRange);
+        }
+        self.write_bits(encoded, bits);
+        Ok(())
+    }
+
+    /// Returns the bytes written, with the unused bits of the last one left
+    /// as zeros.
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Reads fields of up to 64 bits each from bytes packed by [`BitWriter`].
+#[derive(Clone, Debug)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    /// The number of bits read so far.
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    /// Creates a reader starting at the first bit of `bytes`.
+    #[inline]
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        BitReader { bytes, pos: 0 }
+    }
+
+    /// Returns the number of bits read so far.
+    #[inline]
+    pub const fn bit_position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the number of bits left to read, including any padding at
+    /// the end of the last byte.
+    #[inline]
+    pub const fn remaining_bits(&self) -> usize {
+        self.bytes.len() * 8 - self.pos
+    }
+
+    /// Reads a field `bits` wide.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DecodeError::Truncated`], with the number of bytes missing,
+    /// if fewer than `bits` bits are left, in which case the reader is left
+    /// unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is greater than 64.
+    pub fn read_bits(&mut self, bits: u32) -> Result<u64, DecodeError> {
+        assert!(bits <= 64, "fields are at most 64 bits wide");
+        let end = self.pos + bits as usize;
+        if end > self.bytes.len() * 8 {
+            return Err(DecodeError::Truncated {
+                needed: end.div_ceil(8) - self.bytes.len(),
+            });
+        }
+        let mut value = 0;
+        let mut remaining = bits;
+        while remaining > 0 {
+            let free = 8 - (self.pos % 8) as u32;
+            let n = remaining.min(free);
+            let byte = self.bytes[self.pos / 8] >> (free - n) & (0xff >> (8 - n));
+            value = value << n | u64::from(byte);
+            self.pos += n as usize;
+            remaining -= n;
+        }
+        Ok(value)
+    }
+
+    /// Reads a single bit.
+    ///
+    /// # Errors
+    ///
+    /// See [`read_bits`](Self::read_bits).
+    #[inline]
+    pub fn read_bit(&mut self) -> Result<bool, DecodeError> {
+        self.read_bits(1).map(|bit| bit == 1)
+    }
+
+    /// Reads a field `bits` wide and zigzag decodes it.
+    ///
+    /// # Errors
+    ///
+    /// See [`read_bits`](Self::read_bits).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is greater than 64.
+    #[inline]
+    pub fn read_zigzag(&mut self, bits: u32) -> Result<i64, DecodeError> {
+        self.read_bits(bits).map(ZigZagDecode::zigzag_decode)
+    }
+}
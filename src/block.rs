@@ -0,0 +1,157 @@
+//! Blocks of zigzag-encoded varints with a sparse index for random access.
+//!
+//! Locating the `k`th value of a plain varint stream means decoding every
+//! value before it. A block additionally records the byte offset of every
+//! [`INDEX_INTERVAL`]th value, so a lookup only has to skip over at most
+//! `INDEX_INTERVAL - 1` varints from the nearest indexed offset.
+
+use alloc::vec::{self, Vec};
+use core::slice;
+
+use crate::varint::{read_zigzag_varint, write_uvarint, zigzag_decode_all, CONTINUATION_BIT};
+use crate::{DecodeError, ZigZagEncode};
+
+/// The number of values between consecutive entries of a block's index.
+pub const INDEX_INTERVAL: usize = 64;
+
+/// A block of zigzag-encoded varints, along with its offset index.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EncodedBlock {
+    /// The encoded values, one varint after the other.
+    pub bytes: Vec<u8>,
+    /// The byte offset of every [`INDEX_INTERVAL`]th value in `bytes`,
+    /// starting with the first.
+    pub index: Vec<usize>,
+}
+
+impl EncodedBlock {
+    /// Returns a reader for random access into the block.
+    #[inline]
+    pub fn reader(&self) -> ZigZagBlockReader<'_> {
+        ZigZagBlockReader::new(&self.bytes, &self.index)
+    }
+
+    /// Decodes every value in the block.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the block's bytes don't consist entirely of valid
+    /// varints.
+    #[inline]
+    pub fn decode(&self) -> Result<ZigZagBlock, DecodeError> {
+        Ok(ZigZagBlock(zigzag_decode_all(&self.bytes)?))
+    }
+}
+
+/// The decoded values of a block.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::block::write_zigzag_block;
+///
+/// let block = write_zigzag_block(vec![3i64, -1, 4]).decode()?;
+/// let mut sum = 0;
+/// for value in &block {
+///     sum += value;
+/// }
+/// assert_eq!(sum, 6);
+/// assert_eq!(block.into_iter().collect::<Vec<_>>(), [3, -1, 4]);
+/// # Ok::<(), zigzag::DecodeError>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ZigZagBlock(pub Vec<i64>);
+
+impl IntoIterator for ZigZagBlock {
+    type Item = i64;
+    type IntoIter = vec::IntoIter<i64>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ZigZagBlock {
+    type Item = &'a i64;
+    type IntoIter = slice::Iter<'a, i64>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut ZigZagBlock {
+    type Item = &'a mut i64;
+    type IntoIter = slice::IterMut<'a, i64>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
+
+/// Encodes `values` into a block, building its offset index along the way.
+///
+/// Wrappers such as `Saturating<i64>` can be written by mapping them to their
+/// value first.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::block::write_zigzag_block;
+///
+/// let block = write_zigzag_block((0i64..1000).map(|x| x * 7 - 3500));
+/// let reader = block.reader();
+/// assert_eq!(reader.get(0), Ok(-3500));
+/// assert_eq!(reader.get(100), Ok(-2800));
+/// assert_eq!(reader.get(999), Ok(3493));
+/// assert!(reader.get(1000).is_err());
+/// ```
+pub fn write_zigzag_block<I: IntoIterator<Item = i64>>(values: I) -> EncodedBlock {
+    let mut block = EncodedBlock::default();
+    for (i, value) in values.into_iter().enumerate() {
+        if i % INDEX_INTERVAL == 0 {
+            block.index.push(block.bytes.len());
+        }
+        write_uvarint(value.zigzag_encode(), &mut block.bytes);
+    }
+    block
+}
+
+/// A random-access reader over the bytes and index of an [`EncodedBlock`].
+#[derive(Clone, Copy, Debug)]
+pub struct ZigZagBlockReader<'a> {
+    bytes: &'a [u8],
+    index: &'a [usize],
+}
+
+impl<'a> ZigZagBlockReader<'a> {
+    /// Creates a reader over a block's bytes and its offset index.
+    #[inline]
+    pub fn new(bytes: &'a [u8], index: &'a [usize]) -> Self {
+        ZigZagBlockReader { bytes, index }
+    }
+
+    /// Decodes the value at position `index` in the block.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DecodeError::Truncated`] if the block holds fewer than
+    /// `index + 1` values, or any error encountered while reading the value.
+    pub fn get(&self, index: usize) -> Result<i64, DecodeError> {
+        const TRUNCATED: DecodeError = DecodeError::Truncated { needed: 1 };
+
+        let start = *self.index.get(index / INDEX_INTERVAL).ok_or(TRUNCATED)?;
+        let mut rest = self.bytes.get(start..).ok_or(TRUNCATED)?;
+        for _ in 0..index % INDEX_INTERVAL {
+            let end = rest
+                .iter()
+                .position(|&byte| byte & CONTINUATION_BIT == 0)
+                .ok_or(TRUNCATED)?;
+            rest = &rest[end + 1..];
+        }
+        Ok(read_zigzag_varint(rest)?.0)
+    }
+}
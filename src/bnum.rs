@@ -0,0 +1,56 @@
+//! Implementations of [`ZigZagEncode`] and [`ZigZagDecode`] for the
+//! fixed-width big integers of the [`bnum`](::bnum) crate.
+//!
+//! Every signed type `BIntD*<N>` encodes to the unsigned type `BUintD*<N>`
+//! with the same digit type and number of digits, using the same formula as
+//! the primitive integers at the type's bit width. This includes the
+//! 256-bit `I256` and `U256` aliases in [`bnum::types`](::bnum::types), so
+//! trait-based code works unchanged from 8 to 256 bits and beyond.
+
+use ::bnum::{BInt, BIntD16, BIntD32, BIntD8, BUint, BUintD16, BUintD32, BUintD8};
+
+use crate::{private, ZigZag, ZigZagDecode, ZigZagEncode, ZigZagUnsigned, ZigZagZero};
+
+macro_rules! impl_bnum {
+    ($signed:ident, $unsigned:ident) => {
+        impl<const N: usize> private::Sealed for $signed<N> {}
+        impl<const N: usize> private::Sealed for $unsigned<N> {}
+
+        impl<const N: usize> ZigZagEncode for $signed<N> {
+            type Output = $unsigned<N>;
+
+            #[inline]
+            fn zigzag_encode(self) -> $unsigned<N> {
+                (self >> (Self::BITS - 1)).cast_unsigned() ^ (self << 1u32).cast_unsigned()
+            }
+        }
+
+        impl<const N: usize> ZigZagDecode for $unsigned<N> {
+            type Output = $signed<N>;
+
+            #[inline]
+            fn zigzag_decode(self) -> $signed<N> {
+                (self >> 1u32).cast_signed() ^ (self & Self::ONE).cast_signed().wrapping_neg()
+            }
+        }
+
+        impl<const N: usize> ZigZag for $signed<N> {
+            type Unsigned = $unsigned<N>;
+            const BITS: u32 = Self::BITS;
+            const ENCODED_NEG_ONE: $unsigned<N> = $unsigned::ONE;
+        }
+
+        impl<const N: usize> ZigZagZero for $signed<N> {
+            const ENCODED_ZERO: $unsigned<N> = $unsigned::ZERO;
+        }
+
+        impl<const N: usize> ZigZagUnsigned for $unsigned<N> {
+            type Signed = $signed<N>;
+        }
+    };
+}
+
+impl_bnum!(BInt, BUint);
+impl_bnum!(BIntD32, BUintD32);
+impl_bnum!(BIntD16, BUintD16);
+impl_bnum!(BIntD8, BUintD8);
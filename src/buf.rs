@@ -0,0 +1,134 @@
+//! Zigzag-encoded varints in the buffers of the `bytes` crate.
+//!
+//! The [`ZigZagBufExt`] and [`ZigZagBufMutExt`] extension traits read and
+//! write varints directly in any [`Buf`] or [`BufMut`], such as `Bytes` and
+//! `BytesMut`, without copying them into an intermediate slice. A [`Buf`]
+//! may hold its bytes in several chunks, as a [`Chain`](bytes::buf::Chain)
+//! does, and a varint may be split across them.
+//!
+//! # Examples
+//!
+//! ```
+//! use bytes::{Buf, BytesMut};
+//! use zigzag::buf::{ZigZagBufExt, ZigZagBufMutExt};
+//!
+//! let mut out = BytesMut::new();
+//! out.put_zigzag_varint_i64(-1);
+//! out.put_zigzag_varint_i32(150);
+//! assert_eq!(out[..], [0x01, 0xac, 0x02]);
+//!
+//! // The second varint is split between the two halves of the chain.
+//! let mut input = out.split_to(2).chain(out);
+//! assert_eq!(input.get_zigzag_varint_i64(), Ok(-1));
+//! assert_eq!(input.get_zigzag_varint_i32(), Ok(150));
+//! assert!(!input.has_remaining());
+//! ```
+
+use core::convert::TryFrom;
+
+use bytes::{Buf, BufMut};
+
+use crate::varint::{encode_uvarint, read_zigzag_varint, VarintDecoderState, MAX_VARINT_LEN};
+use crate::{DecodeError, VarintError, ZigZagEncode};
+
+/// Extends every [`Buf`] with methods which read zigzag-encoded varints as
+/// signed integers.
+///
+/// # Errors
+///
+/// Every method returns [`DecodeError::Truncated`] if the buffer ends before
+/// the varint does, and [`DecodeError::Overflow`] if the varint encodes a
+/// value which doesn't fit in the type being read.
+///
+/// A varint held in a single chunk is only consumed once it is decoded, so a
+/// truncated varint at the end of a contiguous buffer, such as `Bytes`, is
+/// left in place to be read again once more data has arrived. A varint split
+/// across chunks is consumed as it is decoded, up to the error if there is
+/// one.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Bytes;
+/// use zigzag::buf::ZigZagBufExt;
+/// use zigzag::DecodeError;
+///
+/// let mut input = Bytes::from_static(&[0xac]);
+/// assert_eq!(input.get_zigzag_varint_i64(), Err(DecodeError::Truncated { needed: 1 }));
+/// assert_eq!(input.len(), 1);
+///
+/// let mut input = Bytes::from_static(&[0x80, 0x80, 0x04]);
+/// assert_eq!(input.get_zigzag_varint_i16(), Err(DecodeError::Overflow));
+/// ```
+pub trait ZigZagBufExt: Buf {
+    /// Reads a zigzag-encoded varint as an `i64`.
+    fn get_zigzag_varint_i64(&mut self) -> Result<i64, DecodeError> {
+        let chunk = self.chunk();
+        match read_zigzag_varint(chunk) {
+            Ok((value, len)) => {
+                self.advance(len);
+                return Ok(value);
+            }
+            Err(VarintError::Truncated) if chunk.len() < self.remaining() => {}
+            Err(err) => return Err(err.into()),
+        }
+        // The varint continues into the next chunk.
+        let mut decoder = VarintDecoderState::new();
+        while self.has_remaining() {
+            if let Some(result) = decoder.feed(self.get_u8()) {
+                return result.map_err(Into::into);
+            }
+        }
+        Err(DecodeError::Truncated { needed: 1 })
+    }
+
+    /// Reads a zigzag-encoded varint as an `i32`.
+    #[inline]
+    fn get_zigzag_varint_i32(&mut self) -> Result<i32, DecodeError> {
+        i32::try_from(self.get_zigzag_varint_i64()?).map_err(|_| DecodeError::Overflow)
+    }
+
+    /// Reads a zigzag-encoded varint as an `i16`.
+    #[inline]
+    fn get_zigzag_varint_i16(&mut self) -> Result<i16, DecodeError> {
+        i16::try_from(self.get_zigzag_varint_i64()?).map_err(|_| DecodeError::Overflow)
+    }
+}
+
+impl<B: Buf + ?Sized> ZigZagBufExt for B {}
+
+/// Extends every [`BufMut`] with methods which write signed integers as
+/// zigzag-encoded varints.
+///
+/// Each method puts exactly the bytes
+/// [`write_zigzag_varint`](crate::varint::write_zigzag_varint) would append,
+/// and returns how many there were.
+///
+/// # Panics
+///
+/// Like [`BufMut::put_slice`], every method panics if the buffer doesn't
+/// have room for the varint.
+pub trait ZigZagBufMutExt: BufMut {
+    /// Writes `x` as a zigzag-encoded varint.
+    #[inline]
+    fn put_zigzag_varint_i64(&mut self, x: i64) -> usize {
+        let mut buf = [0; MAX_VARINT_LEN];
+        let len = encode_uvarint(x.zigzag_encode(), &mut buf);
+        self.put_slice(&buf[..len]);
+        len
+    }
+
+    /// Writes `x` as a zigzag-encoded varint.
+    #[inline]
+    fn put_zigzag_varint_i32(&mut self, x: i32) -> usize {
+        self.put_zigzag_varint_i64(i64::from(x))
+    }
+
+    /// Writes `x` as a zigzag-encoded varint.
+    #[inline]
+    fn put_zigzag_varint_i16(&mut self, x: i16) -> usize {
+        self.put_zigzag_varint_i64(i64::from(x))
+    }
+}
+
+impl<B: BufMut + ?Sized> ZigZagBufMutExt for B {}
@@ -0,0 +1,73 @@
+//! `const fn` versions of zigzag encoding and decoding.
+//!
+//! Trait methods can't be called in const contexts, so [`ZigZagEncode`] and
+//! [`ZigZagDecode`] can't be used to compute constants or statics. This
+//! module provides a free function for each primitive integer type instead,
+//! along with functions which transform whole arrays, so lookup tables of
+//! encoded values can be built at compile time. Every function returns
+//! exactly what the corresponding trait method does.
+//!
+//! # Examples
+//!
+//! ```
+//! use zigzag::const_fn::{decode_u32, encode_array_i32, encode_i32};
+//!
+//! const MINUS_ONE: u32 = encode_i32(-1);
+//! static TABLE: [u32; 4] = encode_array_i32([0, -1, 1, i32::MIN]);
+//!
+//! assert_eq!(MINUS_ONE, 1);
+//! assert_eq!(TABLE, [0, 1, 2, u32::MAX]);
+//! assert_eq!(decode_u32(TABLE[3]), i32::MIN);
+//! ```
+//!
+//! [`ZigZagEncode`]: crate::ZigZagEncode
+//! [`ZigZagDecode`]: crate::ZigZagDecode
+
+macro_rules! impl_const_fn {
+    ($($signed:ident, $unsigned:ident, $encode:ident, $decode:ident, $encode_array:ident, $decode_array:ident;)*) => {
+        $(
+            #[doc = concat!("Zigzag encodes an `", stringify!($signed), "`.")]
+            #[inline]
+            pub const fn $encode(x: $signed) -> $unsigned {
+                (x >> ($signed::BITS - 1)) as $unsigned ^ (x << 1) as $unsigned
+            }
+
+            #[doc = concat!("Zigzag decodes a `", stringify!($unsigned), "`.")]
+            #[inline]
+            pub const fn $decode(x: $unsigned) -> $signed {
+                (x >> 1) as $signed ^ -((x & 1) as $signed)
+            }
+
+            #[doc = concat!("Zigzag encodes every `", stringify!($signed), "` of an array.")]
+            pub const fn $encode_array<const N: usize>(values: [$signed; N]) -> [$unsigned; N] {
+                let mut encoded = [0; N];
+                let mut i = 0;
+                while i < N {
+                    encoded[i] = $encode(values[i]);
+                    i += 1;
+                }
+                encoded
+            }
+
+            #[doc = concat!("Zigzag decodes every `", stringify!($unsigned), "` of an array.")]
+            pub const fn $decode_array<const N: usize>(values: [$unsigned; N]) -> [$signed; N] {
+                let mut decoded = [0; N];
+                let mut i = 0;
+                while i < N {
+                    decoded[i] = $decode(values[i]);
+                    i += 1;
+                }
+                decoded
+            }
+        )*
+    };
+}
+
+impl_const_fn! {
+    i8, u8, encode_i8, decode_u8, encode_array_i8, decode_array_u8;
+    i16, u16, encode_i16, decode_u16, encode_array_i16, decode_array_u16;
+    i32, u32, encode_i32, decode_u32, encode_array_i32, decode_array_u32;
+    i64, u64, encode_i64, decode_u64, encode_array_i64, decode_array_u64;
+    i128, u128, encode_i128, decode_u128, encode_array_i128, decode_array_u128;
+    isize, usize, encode_isize, decode_usize, encode_array_isize, decode_array_usize;
+}
@@ -0,0 +1,49 @@
+//! Signed integers written as the decimal digits of their zigzag encoding.
+//!
+//! The encoding of a signed value is never negative, so its decimal form has
+//! no minus sign. This suits text formats where `-` is awkward or reserved,
+//! such as URL path segments, while keeping small values of either sign
+//! short.
+
+use alloc::string::{String, ToString};
+use core::num::ParseIntError;
+
+use crate::{ZigZagDecode, ZigZagEncode};
+
+/// Returns the decimal digits of the zigzag encoding of `x`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::decimal::encode_zigzag_decimal;
+///
+/// assert_eq!(encode_zigzag_decimal(-1), "1");
+/// assert_eq!(encode_zigzag_decimal(1), "2");
+/// assert_eq!(encode_zigzag_decimal(i64::MIN), u64::MAX.to_string());
+/// ```
+pub fn encode_zigzag_decimal(x: i64) -> String {
+    let encoded: u64 = x.zigzag_encode();
+    encoded.to_string()
+}
+
+/// Parses the decimal digits of a zigzag encoding and decodes it.
+///
+/// This is the inverse of [`encode_zigzag_decimal`].
+///
+/// # Errors
+///
+/// Returns an error if `s` isn't the decimal form of a `u64`. In
+/// particular, a minus sign is rejected.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::decimal::decode_zigzag_decimal;
+///
+/// assert_eq!(decode_zigzag_decimal("1"), Ok(-1));
+/// assert_eq!(decode_zigzag_decimal("300"), Ok(150));
+/// assert!(decode_zigzag_decimal("-1").is_err());
+/// ```
+pub fn decode_zigzag_decimal(s: &str) -> Result<i64, ParseIntError> {
+    s.parse::<u64>().map(ZigZagDecode::zigzag_decode)
+}
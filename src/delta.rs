@@ -0,0 +1,458 @@
+//! Delta encoding layered on zigzag varints, for slowly changing series.
+//!
+//! A delta-encoded stream starts with the first value itself, followed by the
+//! difference between every value and the one before it. Each is stored as a
+//! zigzag-encoded varint, so a series whose values stay close together
+//! encodes to about one byte per value regardless of its magnitude.
+//!
+//! Differences are computed with wrapping arithmetic, so every series of
+//! `i64`s round-trips, even where the difference between two values
+//! overflows.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+#[cfg(feature = "alloc")]
+use crate::varint::write_zigzag_varint;
+use crate::varint::{read_zigzag_varint, MAX_VARINT_LEN};
+#[cfg(feature = "alloc")]
+use crate::OutOfRange;
+use crate::VarintError;
+
+/// Appends the delta encoding of `values` to `out`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::delta::delta_zigzag_encode;
+///
+/// let mut out = Vec::new();
+/// delta_zigzag_encode(&[1000, 1001, 1003, 1002], &mut out);
+/// assert_eq!(out, [0xd0, 0x0f, 0x02, 0x04, 0x01]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn delta_zigzag_encode(values: &[i64], out: &mut Vec<u8>) {
+    let mut prev = 0i64;
+    for &value in values {
+        write_zigzag_varint(value.wrapping_sub(prev), out);
+        prev = value;
+    }
+}
+
+/// Decodes a delta-encoded stream into its absolute values.
+///
+/// # Errors
+///
+/// Returns an error if `input` doesn't consist entirely of valid varints.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::delta::{delta_zigzag_decode, delta_zigzag_encode};
+///
+/// let values = [1000, 1001, 1003, 1002, i64::MIN, i64::MAX];
+/// let mut out = Vec::new();
+/// delta_zigzag_encode(&values, &mut out);
+/// assert_eq!(delta_zigzag_decode(&out), Ok(values.to_vec()));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn delta_zigzag_decode(input: &[u8]) -> Result<Vec<i64>, VarintError> {
+    delta_zigzag_decode_iter(input).collect()
+}
+
+/// Appends a copy of the delta-encoded stream `input` to `out`, shifted so
+/// that its first value is `new_base`.
+///
+/// Only the leading absolute value changes; the deltas after it are copied
+/// byte for byte, so every value moves by the same amount. This lets one
+/// block be re-based to chain onto the end of another. An empty stream has
+/// no values to shift and appends nothing.
+///
+/// # Errors
+///
+/// Returns an error if `input` doesn't consist entirely of valid varints, in
+/// which case `out` is left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::delta::{delta_zigzag_decode, delta_zigzag_encode, rebase_delta_block};
+///
+/// let mut block = Vec::new();
+/// delta_zigzag_encode(&[10, 12, 11], &mut block);
+///
+/// let mut out = Vec::new();
+/// rebase_delta_block(&block, 100, &mut out)?;
+/// assert_eq!(delta_zigzag_decode(&out)?, [100, 102, 101]);
+/// # Ok::<(), zigzag::VarintError>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn rebase_delta_block(
+    input: &[u8],
+    new_base: i64,
+    out: &mut Vec<u8>,
+) -> Result<(), VarintError> {
+    if input.is_empty() {
+        return Ok(());
+    }
+    let (_, first_len) = read_zigzag_varint(input)?;
+    let deltas = &input[first_len..];
+    let mut rest = deltas;
+    while !rest.is_empty() {
+        let (_, len) = read_zigzag_varint(rest)?;
+        rest = &rest[len..];
+    }
+    write_zigzag_varint(new_base, out);
+    out.extend_from_slice(deltas);
+    Ok(())
+}
+
+/// Returns an iterator which lazily decodes a delta-encoded stream into its
+/// absolute values.
+///
+/// The iterator yields an error and then ends if it reaches a malformed
+/// varint.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use zigzag::delta::{delta_zigzag_decode_iter, delta_zigzag_encode};
+/// use zigzag::VarintError;
+///
+/// let mut out = Vec::new();
+/// delta_zigzag_encode(&[10, 20, 30], &mut out);
+/// let mut iter = delta_zigzag_decode_iter(&out);
+/// assert_eq!(iter.next(), Some(Ok(10)));
+/// assert_eq!(iter.next(), Some(Ok(20)));
+/// assert_eq!(iter.next(), Some(Ok(30)));
+/// assert_eq!(iter.next(), None);
+///
+/// // A truncated delta ends the series with an error.
+/// let mut iter = delta_zigzag_decode_iter(&[0x14, 0x80]);
+/// assert_eq!(iter.next(), Some(Ok(10)));
+/// assert_eq!(iter.next(), Some(Err(VarintError::Truncated)));
+/// assert_eq!(iter.next(), None);
+/// # }
+/// ```
+#[inline]
+pub fn delta_zigzag_decode_iter(input: &[u8]) -> DeltaZigZagIter<'_> {
+    DeltaZigZagIter { input, prev: 0 }
+}
+
+/// An iterator which lazily decodes a delta-encoded stream.
+///
+/// This `struct` is created by [`delta_zigzag_decode_iter`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct DeltaZigZagIter<'a> {
+    input: &'a [u8],
+    prev: i64,
+}
+
+impl Iterator for DeltaZigZagIter<'_> {
+    type Item = Result<i64, VarintError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_empty() {
+            return None;
+        }
+        match read_zigzag_varint(self.input) {
+            Ok((delta, len)) => {
+                self.input = &self.input[len..];
+                self.prev = self.prev.wrapping_add(delta);
+                Some(Ok(self.prev))
+            }
+            Err(err) => {
+                self.input = &[];
+                Some(Err(err))
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.input.len();
+        (len.div_ceil(MAX_VARINT_LEN), Some(len))
+    }
+}
+
+impl FusedIterator for DeltaZigZagIter<'_> {}
+
+/// A stateful delta encoder, for series which arrive a few values at a time.
+///
+/// The encoder remembers the last value it encoded, so encoding a series in
+/// any number of pieces produces the same bytes as encoding it at once with
+/// [`delta_zigzag_encode`]. The first value of a new encoder is written as
+/// its difference from zero—that is, as its absolute value—which
+/// [`DeltaZigZagDecoder`] and [`delta_zigzag_decode`] both expect.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::delta::{delta_zigzag_encode, DeltaZigZagEncoder};
+///
+/// let mut encoder = DeltaZigZagEncoder::new();
+/// let mut out = Vec::new();
+/// encoder.encode_slice(&[1000, 1001], &mut out);
+/// encoder.encode(1003, &mut out);
+/// encoder.encode_slice(&[1002], &mut out);
+/// assert_eq!(encoder.prev(), 1002);
+///
+/// let mut expected = Vec::new();
+/// delta_zigzag_encode(&[1000, 1001, 1003, 1002], &mut expected);
+/// assert_eq!(out, expected);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DeltaZigZagEncoder {
+    prev: i64,
+}
+
+#[cfg(feature = "alloc")]
+impl DeltaZigZagEncoder {
+    /// Creates an encoder for a new series.
+    #[inline]
+    pub const fn new() -> Self {
+        DeltaZigZagEncoder { prev: 0 }
+    }
+
+    /// Returns the last value encoded, or zero if there was none.
+    #[inline]
+    pub const fn prev(&self) -> i64 {
+        self.prev
+    }
+
+    /// Appends the difference between `value` and the previous value to
+    /// `out`, and returns the number of bytes appended.
+    #[inline]
+    pub fn encode(&mut self, value: i64, out: &mut Vec<u8>) -> usize {
+        let len = write_zigzag_varint(value.wrapping_sub(self.prev), out);
+        self.prev = value;
+        len
+    }
+
+    /// Appends the differences of every value of `values` to `out`.
+    pub fn encode_slice(&mut self, values: &[i64], out: &mut Vec<u8>) {
+        for &value in values {
+            self.encode(value, out);
+        }
+    }
+}
+
+/// A stateful delta decoder, for streams which arrive a few bytes at a time.
+///
+/// This is the counterpart of [`DeltaZigZagEncoder`]: the decoder remembers
+/// the last value it decoded, so a stream can be decoded in pieces, as long
+/// as each piece holds whole varints.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use zigzag::delta::{DeltaZigZagDecoder, DeltaZigZagEncoder};
+///
+/// let mut encoder = DeltaZigZagEncoder::new();
+/// let mut first = Vec::new();
+/// encoder.encode_slice(&[1000, 1001], &mut first);
+/// let mut second = Vec::new();
+/// encoder.encode_slice(&[1003, 1002], &mut second);
+///
+/// let mut decoder = DeltaZigZagDecoder::new();
+/// let mut values = Vec::new();
+/// decoder.decode_slice(&first, &mut values)?;
+/// assert_eq!(decoder.decode(&second)?, (1003, 1));
+/// decoder.decode_slice(&second[1..], &mut values)?;
+/// assert_eq!(values, [1000, 1001, 1002]);
+/// # }
+/// # Ok::<(), zigzag::VarintError>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DeltaZigZagDecoder {
+    prev: i64,
+}
+
+impl DeltaZigZagDecoder {
+    /// Creates a decoder for a new series.
+    #[inline]
+    pub const fn new() -> Self {
+        DeltaZigZagDecoder { prev: 0 }
+    }
+
+    /// Returns the last value decoded, or zero if there was none.
+    #[inline]
+    pub const fn prev(&self) -> i64 {
+        self.prev
+    }
+
+    /// Reads a difference from the start of `input` and returns the value it
+    /// leads to, along with the number of bytes it occupied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` doesn't start with a valid varint, in
+    /// which case the decoder is left unchanged.
+    #[inline]
+    pub fn decode(&mut self, input: &[u8]) -> Result<(i64, usize), VarintError> {
+        let (delta, len) = read_zigzag_varint(input)?;
+        self.prev = self.prev.wrapping_add(delta);
+        Ok((self.prev, len))
+    }
+
+    /// Decodes every difference in `input` and appends the values they lead
+    /// to to `out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` doesn't consist entirely of valid varints,
+    /// in which case neither the decoder nor `out` is changed.
+    #[cfg(feature = "alloc")]
+    pub fn decode_slice(
+        &mut self,
+        mut input: &[u8],
+        out: &mut Vec<i64>,
+    ) -> Result<(), VarintError> {
+        let original_len = out.len();
+        let mut decoder = *self;
+        while !input.is_empty() {
+            match decoder.decode(input) {
+                Ok((value, len)) => {
+                    out.push(value);
+                    input = &input[len..];
+                }
+                Err(err) => {
+                    out.truncate(original_len);
+                    return Err(err);
+                }
+            }
+        }
+        *self = decoder;
+        Ok(())
+    }
+}
+
+/// An encoder which stores a series of increments, checking that their
+/// running total never overflows.
+///
+/// The increments are written as zigzag-encoded varints, which makes the
+/// output the delta encoding of the running totals, as written by
+/// [`delta_zigzag_encode`]. Unlike the delta encoder, which wraps around,
+/// this rejects any increment that would overflow the total, so
+/// [`PrefixSumDecoder`] can recover every total exactly.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::delta::{PrefixSumDecoder, PrefixSumEncoder};
+///
+/// let mut encoder = PrefixSumEncoder::new();
+/// let mut out = Vec::new();
+/// for &increment in &[10, -3, 5] {
+///     encoder.push(increment, &mut out)?;
+/// }
+/// assert_eq!(encoder.total(), 12);
+/// assert!(encoder.push(i64::MAX, &mut out).is_err());
+///
+/// let totals: Result<Vec<i64>, _> = PrefixSumDecoder::new(&out).collect();
+/// assert_eq!(totals, Ok(vec![10, 7, 12]));
+/// # Ok::<(), zigzag::OutOfRange>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PrefixSumEncoder {
+    total: i64,
+}
+
+#[cfg(feature = "alloc")]
+impl PrefixSumEncoder {
+    /// Creates an encoder with a running total of zero.
+    #[inline]
+    pub const fn new() -> Self {
+        PrefixSumEncoder { total: 0 }
+    }
+
+    /// Returns the sum of every increment pushed so far.
+    #[inline]
+    pub const fn total(&self) -> i64 {
+        self.total
+    }
+
+    /// Adds `increment` to the running total and appends it to `out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OutOfRange`] if the running total would overflow, in which
+    /// case neither the total nor `out` is changed.
+    #[inline]
+    pub fn push(&mut self, increment: i64, out: &mut Vec<u8>) -> Result<(), OutOfRange> {
+        self.total = self.total.checked_add(increment).ok_or(OutOfRange)?;
+        write_zigzag_varint(increment, out);
+        Ok(())
+    }
+}
+
+/// An iterator which decodes a series of increments written by
+/// [`PrefixSumEncoder`], yielding their running totals.
+///
+/// A varint that fails to decode, or an increment that would overflow the
+/// total, ends the series with an error. The latter is reported as
+/// [`VarintError::Overflow`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PrefixSumDecoder<'a> {
+    input: &'a [u8],
+    total: i64,
+}
+
+impl<'a> PrefixSumDecoder<'a> {
+    /// Creates a decoder over the increments in `input`.
+    #[inline]
+    pub fn new(input: &'a [u8]) -> Self {
+        PrefixSumDecoder { input, total: 0 }
+    }
+
+    /// Returns the running total of the increments decoded so far.
+    #[inline]
+    pub fn total(&self) -> i64 {
+        self.total
+    }
+}
+
+impl Iterator for PrefixSumDecoder<'_> {
+    type Item = Result<i64, VarintError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_empty() {
+            return None;
+        }
+        let result = read_zigzag_varint(self.input).and_then(|(increment, len)| {
+            let total = self
+                .total
+                .checked_add(increment)
+                .ok_or(VarintError::Overflow)?;
+            Ok((total, len))
+        });
+        match result {
+            Ok((total, len)) => {
+                self.input = &self.input[len..];
+                self.total = total;
+                Some(Ok(total))
+            }
+            Err(err) => {
+                self.input = &[];
+                Some(Err(err))
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.input.len();
+        (len.div_ceil(MAX_VARINT_LEN), Some(len))
+    }
+}
+
+impl FusedIterator for PrefixSumDecoder<'_> {}
@@ -0,0 +1,336 @@
+//! Newtypes for unsigned integers which are known to be zigzag encoded.
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use crate::offset::{OrderedDecode, OrderedEncode};
+use crate::{ZigZag, ZigZagDecode, ZigZagEncode};
+
+/// A `u32` which holds a zigzag-encoded `i32`.
+///
+/// Comparing two raw zigzag-encoded values orders them by magnitude rather
+/// than by value, which is rarely what's intended. `EncodedU32` instead
+/// compares, and displays, by the decoded signed value.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::encoded::EncodedU32;
+///
+/// let minus_one = EncodedU32(1);
+/// let one = EncodedU32(2);
+/// let minus_two = EncodedU32(3);
+///
+/// assert!(minus_one < one);
+/// assert!(minus_two < minus_one);
+/// assert_eq!(minus_two.decode(), -2);
+/// assert_eq!(minus_two.to_string(), "-2");
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EncodedU32(pub u32);
+
+impl EncodedU32 {
+    /// Decodes the wrapped value into its signed counterpart.
+    #[inline]
+    pub fn decode(self) -> i32 {
+        self.0.zigzag_decode()
+    }
+}
+
+impl PartialOrd for EncodedU32 {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EncodedU32 {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.decode().cmp(&other.decode())
+    }
+}
+
+impl fmt::Display for EncodedU32 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.decode(), f)
+    }
+}
+
+/// Implements `Debug` showing both the encoded bits and the decoded value,
+/// such as `Zigzag32 { encoded: 1, decoded: -1 }`.
+macro_rules! impl_debug {
+    ($name:ident, $decode:ident) => {
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("encoded", &self.0)
+                    .field("decoded", &self.$decode())
+                    .finish()
+            }
+        }
+    };
+}
+
+impl_debug!(EncodedU32, decode);
+
+macro_rules! impl_transparent {
+    ($name:ident, $signed:ty, $unsigned:ty) => {
+        #[doc = concat!(
+            "A zigzag-encoded `", stringify!($signed), "`, laid out exactly like a `",
+            stringify!($unsigned), "`.\n\n",
+            "Because the type is `#[repr(transparent)]`, it can be used in place of a `",
+            stringify!($unsigned), "` field of a `#[repr(C)]` struct shared over FFI, while ",
+            "recording in the type that the field is zigzag encoded.\n\n",
+            "`Display` shows the decoded signed value, while `Binary`, `LowerHex` and ",
+            "`UpperHex` show the encoded bits. `Debug` shows both."
+        )]
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        pub struct $name(pub $unsigned);
+
+        impl $name {
+            /// Zigzag encodes a signed value.
+            #[inline]
+            pub fn from_signed(x: $signed) -> Self {
+                $name(x.zigzag_encode())
+            }
+
+            /// Decodes the wrapped value into its signed counterpart.
+            #[inline]
+            pub fn to_signed(self) -> $signed {
+                self.0.zigzag_decode()
+            }
+        }
+
+        impl_debug!($name, to_signed);
+
+        /// Formats the decoded signed value.
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.to_signed(), f)
+            }
+        }
+
+        /// Formats the encoded bits.
+        impl fmt::Binary for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Binary::fmt(&self.0, f)
+            }
+        }
+
+        /// Formats the encoded bits.
+        impl fmt::LowerHex for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::LowerHex::fmt(&self.0, f)
+            }
+        }
+
+        /// Formats the encoded bits.
+        impl fmt::UpperHex for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::UpperHex::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+impl_transparent!(Zigzag8, i8, u8);
+impl_transparent!(Zigzag16, i16, u16);
+impl_transparent!(Zigzag32, i32, u32);
+impl_transparent!(Zigzag64, i64, u64);
+impl_transparent!(Zigzag128, i128, u128);
+
+/// A zigzag-encoded `i32`, ordered by its decoded value.
+///
+/// Zigzag-encoded bits sort by magnitude—`0, -1, 1, -2, 2, ...`—so the
+/// [`Ord`] implementation decodes both sides before comparing them. Values
+/// that need to sort correctly as raw bits should use
+/// [`OrderPreservingEncoded`] instead. Keeping the two as distinct types
+/// prevents mixing up values produced by the two encodings, which would
+/// otherwise both be plain `u32`s.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::encoded::ZigzagEncoded;
+///
+/// let mut values: Vec<_> = [2, -3, 0, -1].iter().map(|&x| ZigzagEncoded::from_signed(x)).collect();
+/// values.sort();
+/// let sorted: Vec<i32> = values.iter().map(|x| x.to_signed()).collect();
+/// assert_eq!(sorted, [-3, -1, 0, 2]);
+///
+/// // The raw bits sort differently.
+/// let mut raw: Vec<u32> = values.iter().map(|x| x.0).collect();
+/// raw.sort();
+/// assert_eq!(raw, [0, 1, 4, 5]);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ZigzagEncoded(pub u32);
+
+impl ZigzagEncoded {
+    /// Zigzag encodes a signed value.
+    #[inline]
+    pub fn from_signed(x: i32) -> Self {
+        ZigzagEncoded(x.zigzag_encode())
+    }
+
+    /// Decodes the wrapped value into its signed counterpart.
+    #[inline]
+    pub fn to_signed(self) -> i32 {
+        self.0.zigzag_decode()
+    }
+}
+
+impl_debug!(ZigzagEncoded, to_signed);
+
+impl PartialOrd for ZigzagEncoded {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ZigzagEncoded {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_signed().cmp(&other.to_signed())
+    }
+}
+
+/// An `i32` encoded so that its raw bits sort in the same order as the
+/// signed values.
+///
+/// The encoding is the offset binary of the [`offset`](crate::offset)
+/// module, which flips the sign bit, mapping `i32::MIN..=i32::MAX` onto
+/// `0..=u32::MAX` in order. Its [`Ord`] implementation compares the raw bits
+/// directly, which is also how the values sort once serialized as big-endian
+/// bytes, such as in the keys of a sorted key-value store.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::encoded::OrderPreservingEncoded;
+///
+/// let signed = [2, -3, 0, -1, i32::MIN, i32::MAX];
+/// let mut values: Vec<_> = signed.iter().map(|&x| OrderPreservingEncoded::from_signed(x)).collect();
+/// values.sort();
+///
+/// let mut expected = signed;
+/// expected.sort();
+/// let sorted: Vec<i32> = values.iter().map(|x| x.to_signed()).collect();
+/// assert_eq!(sorted, expected);
+/// assert_eq!(OrderPreservingEncoded::from_signed(-1), OrderPreservingEncoded(0x7fff_ffff));
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrderPreservingEncoded(pub u32);
+
+impl OrderPreservingEncoded {
+    /// Encodes a signed value by flipping its sign bit.
+    #[inline]
+    pub fn from_signed(x: i32) -> Self {
+        OrderPreservingEncoded(x.ordered_encode())
+    }
+
+    /// Decodes the wrapped value into its signed counterpart.
+    #[inline]
+    pub fn to_signed(self) -> i32 {
+        self.0.ordered_decode()
+    }
+}
+
+impl_debug!(OrderPreservingEncoded, to_signed);
+
+/// A map key which stores a signed integer in its zigzag-encoded form.
+///
+/// Since zigzag encoding is a bijection, hashing and comparing the encoded
+/// bits behaves exactly like hashing and comparing the signed value itself,
+/// so a `ZigZagKey<T>` can stand in for a `T` key without changing which
+/// entries collide.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use zigzag::encoded::ZigZagKey;
+///
+/// let mut map = HashMap::new();
+/// map.insert(ZigZagKey::from(-1i32), "minus one");
+/// map.insert(ZigZagKey::from(1i32), "one");
+///
+/// assert_eq!(map[&ZigZagKey::from(-1i32)], "minus one");
+/// assert_eq!(ZigZagKey::from(-1i32).get(), -1);
+/// assert_eq!(ZigZagKey::from(-1i32).encoded(), 1);
+/// ```
+pub struct ZigZagKey<T: ZigZag>(T::Unsigned);
+
+impl<T: ZigZag> ZigZagKey<T> {
+    /// Creates a key from an already zigzag-encoded value.
+    #[inline]
+    pub fn from_encoded(encoded: T::Unsigned) -> Self {
+        ZigZagKey(encoded)
+    }
+
+    /// Returns the encoded value stored in the key.
+    #[inline]
+    pub fn encoded(self) -> T::Unsigned {
+        self.0
+    }
+
+    /// Decodes the key back into its signed value.
+    #[inline]
+    pub fn get(self) -> T {
+        self.0.zigzag_decode()
+    }
+}
+
+impl<T: ZigZag> From<T> for ZigZagKey<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        ZigZagKey(value.zigzag_encode())
+    }
+}
+
+impl<T: ZigZag> Clone for ZigZagKey<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ZigZag> Copy for ZigZagKey<T> {}
+
+impl<T: ZigZag> PartialEq for ZigZagKey<T>
+where
+    T::Unsigned: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: ZigZag> Eq for ZigZagKey<T> where T::Unsigned: Eq {}
+
+impl<T: ZigZag> Hash for ZigZagKey<T>
+where
+    T::Unsigned: Hash,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<T: ZigZag + fmt::Debug> fmt::Debug for ZigZagKey<T>
+where
+    T::Unsigned: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZigZagKey")
+            .field("encoded", &self.0)
+            .field("decoded", &self.get())
+            .finish()
+    }
+}
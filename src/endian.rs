@@ -0,0 +1,166 @@
+//! Fixed-width byte serialization of zigzag-encoded values with the byte
+//! order selected at the type level.
+//!
+//! This mirrors the `ByteOrder` trait of the `byteorder` crate, but only
+//! covers what's needed to serialize encoded values. The [`ZigZagBytes`]
+//! trait instead mirrors the `to_le_bytes` family of methods of the integer
+//! types themselves, at every width.
+
+use crate::{ZigZagDecode, ZigZagEncode};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A byte order that encoded values can be serialized in.
+///
+/// This trait is sealed and implemented only by [`LittleEndian`] and
+/// [`BigEndian`].
+pub trait ByteOrder: private::Sealed {
+    /// Converts `x` to bytes in this byte order.
+    fn u32_to_bytes(x: u32) -> [u8; 4];
+
+    /// Converts bytes in this byte order back to a `u32`.
+    fn u32_from_bytes(bytes: [u8; 4]) -> u32;
+}
+
+/// Little-endian byte order, with the least significant byte first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LittleEndian {}
+
+/// Big-endian byte order, with the most significant byte first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BigEndian {}
+
+impl private::Sealed for LittleEndian {}
+impl private::Sealed for BigEndian {}
+
+impl ByteOrder for LittleEndian {
+    #[inline]
+    fn u32_to_bytes(x: u32) -> [u8; 4] {
+        x.to_le_bytes()
+    }
+
+    #[inline]
+    fn u32_from_bytes(bytes: [u8; 4]) -> u32 {
+        u32::from_le_bytes(bytes)
+    }
+}
+
+impl ByteOrder for BigEndian {
+    #[inline]
+    fn u32_to_bytes(x: u32) -> [u8; 4] {
+        x.to_be_bytes()
+    }
+
+    #[inline]
+    fn u32_from_bytes(bytes: [u8; 4]) -> u32 {
+        u32::from_be_bytes(bytes)
+    }
+}
+
+/// Zigzag encodes `x` and returns the result as bytes in the byte order `E`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::endian::{zigzag_encode_bytes_endian, BigEndian, LittleEndian};
+///
+/// assert_eq!(zigzag_encode_bytes_endian::<LittleEndian>(-1), [1, 0, 0, 0]);
+/// assert_eq!(zigzag_encode_bytes_endian::<BigEndian>(-1), [0, 0, 0, 1]);
+/// ```
+#[inline]
+pub fn zigzag_encode_bytes_endian<E: ByteOrder>(x: i32) -> [u8; 4] {
+    E::u32_to_bytes(x.zigzag_encode())
+}
+
+/// Zigzag decodes bytes in the byte order `E` into an `i32`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::endian::{zigzag_decode_bytes_endian, BigEndian, LittleEndian};
+///
+/// assert_eq!(zigzag_decode_bytes_endian::<LittleEndian>([3, 0, 0, 0]), -2);
+/// assert_eq!(zigzag_decode_bytes_endian::<BigEndian>([0, 0, 1, 0]), 128);
+/// ```
+#[inline]
+pub fn zigzag_decode_bytes_endian<E: ByteOrder>(bytes: [u8; 4]) -> i32 {
+    E::u32_from_bytes(bytes).zigzag_decode()
+}
+
+/// A trait extending signed integer types with fixed-width byte
+/// serialization of their zigzag encoding.
+///
+/// Each method zigzag encodes or decodes at the width of `Self`, and
+/// otherwise behaves like the method of the same name without `zigzag_` on
+/// the unsigned counterpart of `Self`. Small negative values thus have high
+/// bytes of zero, just like small positive ones.
+///
+/// This trait is sealed.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::endian::ZigZagBytes;
+///
+/// assert_eq!((-2i32).to_zigzag_le_bytes(), [3, 0, 0, 0]);
+/// assert_eq!((-2i32).to_zigzag_be_bytes(), [0, 0, 0, 3]);
+/// assert_eq!(i16::from_zigzag_le_bytes([0xff, 0xff]), i16::MIN);
+/// assert_eq!(i16::from_zigzag_be_bytes([0x01, 0x00]), 128);
+/// ```
+pub trait ZigZagBytes: crate::private::Sealed + Sized {
+    /// The byte array `Self` is serialized as, such as `[u8; 4]` for `i32`.
+    type Bytes;
+
+    /// Zigzag encodes `self` and returns the result as little-endian bytes.
+    fn to_zigzag_le_bytes(self) -> Self::Bytes;
+
+    /// Zigzag encodes `self` and returns the result as big-endian bytes.
+    fn to_zigzag_be_bytes(self) -> Self::Bytes;
+
+    /// Zigzag decodes a value from its little-endian bytes.
+    fn from_zigzag_le_bytes(bytes: Self::Bytes) -> Self;
+
+    /// Zigzag decodes a value from its big-endian bytes.
+    fn from_zigzag_be_bytes(bytes: Self::Bytes) -> Self;
+}
+
+macro_rules! impl_zigzag_bytes {
+    ($($signed:ty, $unsigned:ty;)*) => {
+        $(
+            impl ZigZagBytes for $signed {
+                type Bytes = [u8; core::mem::size_of::<$signed>()];
+
+                #[inline]
+                fn to_zigzag_le_bytes(self) -> Self::Bytes {
+                    ZigZagEncode::zigzag_encode(self).to_le_bytes()
+                }
+
+                #[inline]
+                fn to_zigzag_be_bytes(self) -> Self::Bytes {
+                    ZigZagEncode::zigzag_encode(self).to_be_bytes()
+                }
+
+                #[inline]
+                fn from_zigzag_le_bytes(bytes: Self::Bytes) -> Self {
+                    <$unsigned>::from_le_bytes(bytes).zigzag_decode()
+                }
+
+                #[inline]
+                fn from_zigzag_be_bytes(bytes: Self::Bytes) -> Self {
+                    <$unsigned>::from_be_bytes(bytes).zigzag_decode()
+                }
+            }
+        )*
+    };
+}
+
+impl_zigzag_bytes! {
+    i8, u8;
+    i16, u16;
+    i32, u32;
+    i64, u64;
+    i128, u128;
+    isize, usize;
+}
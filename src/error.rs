@@ -0,0 +1,154 @@
+//! Error types returned by the fallible APIs of this crate.
+
+use core::fmt;
+
+/// An error which can be returned when reading a varint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VarintError {
+    /// The input ended before the final byte of the varint was reached.
+    Truncated,
+    /// The varint encodes a value which does not fit in 64 bits, or in the
+    /// narrower integer type being read.
+    Overflow,
+    /// The varint decodes to a value outside the range the reader accepts.
+    OutOfRange,
+}
+
+impl fmt::Display for VarintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            VarintError::Truncated => "varint is truncated",
+            VarintError::Overflow => "varint overflows the integer type",
+            VarintError::OutOfRange => "varint value out of range",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VarintError {}
+
+/// An error which can be returned when decoding from a buffer or stream.
+///
+/// Unlike [`VarintError`], this tells how much more input a truncated value
+/// needs, and it also covers input left over once decoding is done. Every
+/// `VarintError` converts into a `DecodeError`, so `?` works on either.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DecodeError {
+    /// The input ended before the value being decoded did.
+    Truncated {
+        /// The number of additional bytes needed, or a lower bound on it
+        /// where the length of the value isn't known up front, as for LEB128
+        /// varints.
+        needed: usize,
+    },
+    /// A varint encodes a value which does not fit in 64 bits, or in the
+    /// narrower integer type being read.
+    Overflow,
+    /// A value was decoded successfully, but falls outside the range the
+    /// reader accepts.
+    OutOfRange,
+    /// The input continues past the end of the data it was expected to
+    /// consist of.
+    TrailingGarbage,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Truncated { needed } => {
+                write!(f, "input is truncated: {} more bytes needed", needed)
+            }
+            DecodeError::Overflow => f.write_str("value overflows the integer type"),
+            DecodeError::OutOfRange => f.write_str("value out of range"),
+            DecodeError::TrailingGarbage => f.write_str("input has trailing bytes"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+impl From<VarintError> for DecodeError {
+    #[inline]
+    fn from(err: VarintError) -> Self {
+        match err {
+            VarintError::Truncated => DecodeError::Truncated { needed: 1 },
+            VarintError::Overflow => DecodeError::Overflow,
+            VarintError::OutOfRange => DecodeError::OutOfRange,
+        }
+    }
+}
+
+/// An error which can be returned when a value doesn't fit in the range of
+/// the type or field it is converted to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OutOfRange;
+
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("value out of range")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OutOfRange {}
+
+/// An error which can be returned when an output buffer can't hold
+/// everything that would be written to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BufferTooSmall {
+    /// The number of bytes that would have been written.
+    pub needed: usize,
+    /// The number of bytes that were available.
+    pub available: usize,
+}
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "buffer too small: {} bytes needed, but {} available",
+            self.needed, self.available
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BufferTooSmall {}
+
+/// An error which can be returned when a byte slice's length isn't a
+/// multiple of the size of the values it is read as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LengthError {
+    /// The length of the byte slice.
+    pub len: usize,
+    /// The size of each value, which `len` must be a multiple of.
+    pub value_size: usize,
+}
+
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "length {} is not a multiple of {}",
+            self.len, self.value_size
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthError {}
+
+/// An error which can be returned when a time difference can't be
+/// represented.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimeError;
+
+impl fmt::Display for TimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("time difference out of range")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TimeError {}
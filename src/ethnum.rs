@@ -0,0 +1,45 @@
+//! Implementations of [`ZigZagEncode`] and [`ZigZagDecode`] for the 256-bit
+//! integers of the [`ethnum`](::ethnum) crate.
+//!
+//! An [`I256`] encodes to a [`U256`] using the same formula as the primitive
+//! integers, so a value which fits in `i128` encodes to the same number as
+//! the `i128` does, and trait-based code works unchanged from 8 to 256 bits.
+
+use ::ethnum::{I256, U256};
+
+use crate::{private, ZigZag, ZigZagDecode, ZigZagEncode, ZigZagUnsigned, ZigZagZero};
+
+impl private::Sealed for I256 {}
+impl private::Sealed for U256 {}
+
+impl ZigZagEncode for I256 {
+    type Output = U256;
+
+    #[inline]
+    fn zigzag_encode(self) -> U256 {
+        (self >> (I256::BITS - 1)).as_u256() ^ (self << 1u32).as_u256()
+    }
+}
+
+impl ZigZagDecode for U256 {
+    type Output = I256;
+
+    #[inline]
+    fn zigzag_decode(self) -> I256 {
+        (self >> 1u32).as_i256() ^ (self & U256::ONE).as_i256().wrapping_neg()
+    }
+}
+
+impl ZigZag for I256 {
+    type Unsigned = U256;
+    const BITS: u32 = I256::BITS;
+    const ENCODED_NEG_ONE: U256 = U256::ONE;
+}
+
+impl ZigZagZero for I256 {
+    const ENCODED_ZERO: U256 = U256::ZERO;
+}
+
+impl ZigZagUnsigned for U256 {
+    type Signed = I256;
+}
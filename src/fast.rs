@@ -0,0 +1,212 @@
+//! Vectorized slice encoding and decoding on a stable compiler, built on
+//! [`std::arch`].
+//!
+//! With the `fast` feature enabled, [`zigzag_encode_slice`] and
+//! [`zigzag_decode_slice`] use these kernels for `i16`, `i32` and `i64`. Each
+//! call picks the widest instruction set the running CPU supports—AVX2 or SSE2
+//! on x86, NEON on AArch64—and falls back to the scalar loop everywhere else.
+//! Values left over after the last full vector are also handled by the scalar
+//! loop, so the results are bit-for-bit identical to encoding or decoding one
+//! value at a time.
+//!
+//! [`zigzag_encode_slice`]: crate::slice::zigzag_encode_slice
+//! [`zigzag_decode_slice`]: crate::slice::zigzag_decode_slice
+
+use crate::{ZigZagDecode, ZigZagEncode};
+
+#[inline]
+fn encode_scalar<S: ZigZagEncode<Output = U> + Copy, U>(src: &[S], dst: &mut [U]) {
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = s.zigzag_encode();
+    }
+}
+
+#[inline]
+fn decode_scalar<U: ZigZagDecode<Output = S> + Copy, S>(src: &[U], dst: &mut [S]) {
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = s.zigzag_decode();
+    }
+}
+
+/// Defines a function which dispatches to the best kernel for the running
+/// CPU. The caller has already checked that both slices have the same
+/// length.
+macro_rules! dispatch {
+    ($name:ident, $src:ty, $dst:ty, $scalar:ident) => {
+        pub(crate) fn $name(src: &[$src], dst: &mut [$dst]) {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            {
+                if std::arch::is_x86_feature_detected!("avx2") {
+                    // SAFETY: the CPU supports AVX2.
+                    return unsafe { x86::avx2::$name(src, dst) };
+                }
+                if std::arch::is_x86_feature_detected!("sse2") {
+                    // SAFETY: the CPU supports SSE2.
+                    return unsafe { x86::sse2::$name(src, dst) };
+                }
+            }
+            #[cfg(target_arch = "aarch64")]
+            {
+                if std::arch::is_aarch64_feature_detected!("neon") {
+                    // SAFETY: the CPU supports NEON.
+                    return unsafe { neon::$name(src, dst) };
+                }
+            }
+            $scalar(src, dst)
+        }
+    };
+}
+
+dispatch!(encode_i16, i16, u16, encode_scalar);
+dispatch!(encode_i32, i32, u32, encode_scalar);
+dispatch!(encode_i64, i64, u64, encode_scalar);
+dispatch!(decode_u16, u16, i16, decode_scalar);
+dispatch!(decode_u32, u32, i32, decode_scalar);
+dispatch!(decode_u64, u64, i64, decode_scalar);
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    /// Defines a kernel which transforms one full vector at a time with
+    /// `$body`, and the remainder with `$scalar`.
+    macro_rules! kernel {
+        (
+            $feature:literal, $vec:ident, $load:ident, $store:ident;
+            $($name:ident($src:ty => $dst:ty, $scalar:ident) = |$v:ident| $body:expr;)*
+        ) => {
+            $(
+                #[target_feature(enable = $feature)]
+                pub(crate) unsafe fn $name(src: &[$src], dst: &mut [$dst]) {
+                    const LANES: usize = core::mem::size_of::<$vec>() / core::mem::size_of::<$src>();
+                    let mut src_chunks = src.chunks_exact(LANES);
+                    let mut dst_chunks = dst.chunks_exact_mut(LANES);
+                    for (s, d) in (&mut src_chunks).zip(&mut dst_chunks) {
+                        // SAFETY: both chunks hold exactly one vector, and
+                        // the unaligned load and store don't need more.
+                        let $v = $load(s.as_ptr() as *const $vec);
+                        $store(d.as_mut_ptr() as *mut $vec, $body);
+                    }
+                    crate::fast::$scalar(src_chunks.remainder(), dst_chunks.into_remainder());
+                }
+            )*
+        };
+    }
+
+    pub(crate) mod sse2 {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::*;
+
+        kernel! {
+            "sse2", __m128i, _mm_loadu_si128, _mm_storeu_si128;
+            encode_i16(i16 => u16, encode_scalar) =
+                |v| _mm_xor_si128(_mm_slli_epi16(v, 1), _mm_srai_epi16(v, 15));
+            encode_i32(i32 => u32, encode_scalar) =
+                |v| _mm_xor_si128(_mm_slli_epi32(v, 1), _mm_srai_epi32(v, 31));
+            // There is no 64-bit arithmetic shift, so the sign of each high
+            // half is copied over its low half instead.
+            encode_i64(i64 => u64, encode_scalar) = |v| _mm_xor_si128(
+                _mm_slli_epi64(v, 1),
+                _mm_shuffle_epi32(_mm_srai_epi32(v, 31), 0b11_11_01_01),
+            );
+            decode_u16(u16 => i16, decode_scalar) = |v| _mm_xor_si128(
+                _mm_srli_epi16(v, 1),
+                _mm_sub_epi16(_mm_setzero_si128(), _mm_and_si128(v, _mm_set1_epi16(1))),
+            );
+            decode_u32(u32 => i32, decode_scalar) = |v| _mm_xor_si128(
+                _mm_srli_epi32(v, 1),
+                _mm_sub_epi32(_mm_setzero_si128(), _mm_and_si128(v, _mm_set1_epi32(1))),
+            );
+            decode_u64(u64 => i64, decode_scalar) = |v| _mm_xor_si128(
+                _mm_srli_epi64(v, 1),
+                _mm_sub_epi64(_mm_setzero_si128(), _mm_and_si128(v, _mm_set1_epi64x(1))),
+            );
+        }
+    }
+
+    pub(crate) mod avx2 {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::*;
+
+        kernel! {
+            "avx2", __m256i, _mm256_loadu_si256, _mm256_storeu_si256;
+            encode_i16(i16 => u16, encode_scalar) =
+                |v| _mm256_xor_si256(_mm256_slli_epi16(v, 1), _mm256_srai_epi16(v, 15));
+            encode_i32(i32 => u32, encode_scalar) =
+                |v| _mm256_xor_si256(_mm256_slli_epi32(v, 1), _mm256_srai_epi32(v, 31));
+            encode_i64(i64 => u64, encode_scalar) = |v| _mm256_xor_si256(
+                _mm256_slli_epi64(v, 1),
+                _mm256_shuffle_epi32(_mm256_srai_epi32(v, 31), 0b11_11_01_01),
+            );
+            decode_u16(u16 => i16, decode_scalar) = |v| _mm256_xor_si256(
+                _mm256_srli_epi16(v, 1),
+                _mm256_sub_epi16(_mm256_setzero_si256(), _mm256_and_si256(v, _mm256_set1_epi16(1))),
+            );
+            decode_u32(u32 => i32, decode_scalar) = |v| _mm256_xor_si256(
+                _mm256_srli_epi32(v, 1),
+                _mm256_sub_epi32(_mm256_setzero_si256(), _mm256_and_si256(v, _mm256_set1_epi32(1))),
+            );
+            decode_u64(u64 => i64, decode_scalar) = |v| _mm256_xor_si256(
+                _mm256_srli_epi64(v, 1),
+                _mm256_sub_epi64(_mm256_setzero_si256(), _mm256_and_si256(v, _mm256_set1_epi64x(1))),
+            );
+        }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use std::arch::aarch64::*;
+
+    /// Defines a kernel which transforms one full vector at a time with
+    /// `$body`, and the remainder with `$scalar`.
+    macro_rules! kernel {
+        ($($name:ident($src:ty => $dst:ty, $lanes:literal, $scalar:ident) = |$v:ident| $load:ident, $body:expr, $store:ident;)*) => {
+            $(
+                #[target_feature(enable = "neon")]
+                pub(crate) unsafe fn $name(src: &[$src], dst: &mut [$dst]) {
+                    let mut src_chunks = src.chunks_exact($lanes);
+                    let mut dst_chunks = dst.chunks_exact_mut($lanes);
+                    for (s, d) in (&mut src_chunks).zip(&mut dst_chunks) {
+                        // SAFETY: both chunks hold exactly one vector.
+                        let $v = $load(s.as_ptr());
+                        $store(d.as_mut_ptr(), $body);
+                    }
+                    crate::fast::$scalar(src_chunks.remainder(), dst_chunks.into_remainder());
+                }
+            )*
+        };
+    }
+
+    kernel! {
+        encode_i16(i16 => u16, 8, encode_scalar) = |v| vld1q_s16,
+            vreinterpretq_u16_s16(veorq_s16(vshlq_n_s16(v, 1), vshrq_n_s16(v, 15))),
+            vst1q_u16;
+        encode_i32(i32 => u32, 4, encode_scalar) = |v| vld1q_s32,
+            vreinterpretq_u32_s32(veorq_s32(vshlq_n_s32(v, 1), vshrq_n_s32(v, 31))),
+            vst1q_u32;
+        encode_i64(i64 => u64, 2, encode_scalar) = |v| vld1q_s64,
+            vreinterpretq_u64_s64(veorq_s64(vshlq_n_s64(v, 1), vshrq_n_s64(v, 63))),
+            vst1q_u64;
+        decode_u16(u16 => i16, 8, decode_scalar) = |v| vld1q_u16,
+            veorq_s16(
+                vreinterpretq_s16_u16(vshrq_n_u16(v, 1)),
+                vnegq_s16(vreinterpretq_s16_u16(vandq_u16(v, vdupq_n_u16(1)))),
+            ),
+            vst1q_s16;
+        decode_u32(u32 => i32, 4, decode_scalar) = |v| vld1q_u32,
+            veorq_s32(
+                vreinterpretq_s32_u32(vshrq_n_u32(v, 1)),
+                vnegq_s32(vreinterpretq_s32_u32(vandq_u32(v, vdupq_n_u32(1)))),
+            ),
+            vst1q_s32;
+        decode_u64(u64 => i64, 2, decode_scalar) = |v| vld1q_u64,
+            veorq_s64(
+                vreinterpretq_s64_u64(vshrq_n_u64(v, 1)),
+                vnegq_s64(vreinterpretq_s64_u64(vandq_u64(v, vdupq_n_u64(1)))),
+            ),
+            vst1q_s64;
+    }
+}
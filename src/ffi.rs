@@ -0,0 +1,242 @@
+//! A C interface to the bulk operations.
+//!
+//! Every function is `extern "C"` and unmangled, so this crate can be built
+//! as a static or dynamic library and called from C or C++:
+//!
+//! ```text
+//! cargo rustc --release --features ffi --crate-type staticlib
+//! cargo rustc --release --features ffi --crate-type cdylib
+//! ```
+//!
+//! The matching C declarations are:
+//!
+//! ```c
+//! typedef enum {
+//!     ZIGZAG_OK = 0,
+//!     ZIGZAG_NULL_POINTER = 1,
+//!     ZIGZAG_BUFFER_TOO_SMALL = 2,
+//!     ZIGZAG_TRUNCATED = 3,
+//!     ZIGZAG_OVERFLOW = 4,
+//! } zigzag_status;
+//!
+//! zigzag_status zigzag_encode_i32_buf(const int32_t *src, uint32_t *dst, size_t len);
+//! zigzag_status zigzag_encode_i64_buf(const int64_t *src, uint64_t *dst, size_t len);
+//! zigzag_status zigzag_decode_u32_buf(const uint32_t *src, int32_t *dst, size_t len);
+//! zigzag_status zigzag_decode_u64_buf(const uint64_t *src, int64_t *dst, size_t len);
+//! zigzag_status zigzag_varint_encode_i64_buf(const int64_t *src, size_t len,
+//!                                            uint8_t *dst, size_t dst_len,
+//!                                            size_t *written);
+//! zigzag_status zigzag_varint_decode_i64_buf(const uint8_t *src, size_t len,
+//!                                            int64_t *dst, size_t dst_len,
+//!                                            size_t *decoded);
+//! ```
+//!
+//! A pointer may be null when its length is zero. The fixed-width functions
+//! accept the same buffer as both `src` and `dst`, and transform it in
+//! place; otherwise, buffers must not overlap.
+
+use core::slice;
+
+use crate::slice::ZigZagInPlace;
+use crate::varint::{append_zigzag_batch, read_zigzag_varint};
+use crate::{ZigZagDecode, ZigZagEncode};
+
+/// The result of a call through the C interface.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ZigZagStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A pointer was null even though its length wasn't zero.
+    NullPointer = 1,
+    /// The output buffer is too small for the result.
+    BufferTooSmall = 2,
+    /// The input ended in the middle of a varint.
+    Truncated = 3,
+    /// A varint encodes a value which doesn't fit in 64 bits.
+    Overflow = 4,
+}
+
+/// Borrows `len` values starting at `ptr`, which may be null if `len` is
+/// zero.
+unsafe fn slice_from_raw<'a, T>(ptr: *const T, len: usize) -> Result<&'a [T], ZigZagStatus> {
+    if len == 0 {
+        Ok(&[])
+    } else if ptr.is_null() {
+        Err(ZigZagStatus::NullPointer)
+    } else {
+        // SAFETY: the caller guarantees that `ptr` points to `len` values.
+        Ok(slice::from_raw_parts(ptr, len))
+    }
+}
+
+/// Mutably borrows `len` values starting at `ptr`, which may be null if
+/// `len` is zero.
+unsafe fn slice_from_raw_mut<'a, T>(ptr: *mut T, len: usize) -> Result<&'a mut [T], ZigZagStatus> {
+    if len == 0 {
+        Ok(&mut [])
+    } else if ptr.is_null() {
+        Err(ZigZagStatus::NullPointer)
+    } else {
+        // SAFETY: the caller guarantees that `ptr` points to `len` values
+        // which nothing else accesses during the call.
+        Ok(slice::from_raw_parts_mut(ptr, len))
+    }
+}
+
+macro_rules! impl_buf {
+    ($($encode:ident($signed:ty => $unsigned:ty), $decode:ident;)*) => {
+        $(
+            #[doc = concat!("Zigzag encodes the `len` `", stringify!($signed), "`s of `src` into `dst`.")]
+            ///
+            /// # Safety
+            ///
+            /// Unless `len` is zero, `src` and `dst` must each point to `len`
+            /// valid, aligned values. The two buffers must either be the same
+            /// or not overlap at all.
+            #[no_mangle]
+            pub unsafe extern "C" fn $encode(
+                src: *const $signed,
+                dst: *mut $unsigned,
+                len: usize,
+            ) -> ZigZagStatus {
+                if src as usize == dst as usize {
+                    match slice_from_raw_mut(dst as *mut $signed, len) {
+                        Ok(buf) => {
+                            <$signed>::encode_in_place(buf);
+                        }
+                        Err(status) => return status,
+                    }
+                } else {
+                    match (slice_from_raw(src, len), slice_from_raw_mut(dst, len)) {
+                        (Ok(src), Ok(dst)) => <$signed>::encode_slice(src, dst),
+                        (Err(status), _) | (_, Err(status)) => return status,
+                    }
+                }
+                ZigZagStatus::Ok
+            }
+
+            #[doc = concat!("Zigzag decodes the `len` `", stringify!($unsigned), "`s of `src` into `dst`.")]
+            ///
+            /// # Safety
+            ///
+            /// See the encoding counterpart of this function.
+            #[no_mangle]
+            pub unsafe extern "C" fn $decode(
+                src: *const $unsigned,
+                dst: *mut $signed,
+                len: usize,
+            ) -> ZigZagStatus {
+                if src as usize == dst as usize {
+                    match slice_from_raw_mut(dst as *mut $unsigned, len) {
+                        Ok(buf) => {
+                            <$signed>::decode_in_place(buf);
+                        }
+                        Err(status) => return status,
+                    }
+                } else {
+                    match (slice_from_raw(src, len), slice_from_raw_mut(dst, len)) {
+                        (Ok(src), Ok(dst)) => <$unsigned>::decode_slice(src, dst),
+                        (Err(status), _) | (_, Err(status)) => return status,
+                    }
+                }
+                ZigZagStatus::Ok
+            }
+        )*
+    };
+}
+
+impl_buf! {
+    zigzag_encode_i32_buf(i32 => u32), zigzag_decode_u32_buf;
+    zigzag_encode_i64_buf(i64 => u64), zigzag_decode_u64_buf;
+}
+
+/// Encodes the `len` values of `src` as zigzag-encoded varints, one after
+/// the other, into the `dst_len` bytes of `dst`.
+///
+/// On success, stores the number of bytes written in `*written`. If they
+/// don't fit, returns [`ZigZagStatus::BufferTooSmall`] without writing to
+/// `dst`, and stores the number of bytes needed in `*written` instead.
+///
+/// # Safety
+///
+/// Unless its length is zero, `src` must point to `len` values and `dst` to
+/// `dst_len` bytes, which must not overlap. `written` must point to a
+/// writable `size_t`, or be null, in which case this returns
+/// [`ZigZagStatus::NullPointer`] without doing anything else.
+#[no_mangle]
+pub unsafe extern "C" fn zigzag_varint_encode_i64_buf(
+    src: *const i64,
+    len: usize,
+    dst: *mut u8,
+    dst_len: usize,
+    written: *mut usize,
+) -> ZigZagStatus {
+    if written.is_null() {
+        return ZigZagStatus::NullPointer;
+    }
+    let (src, dst) = match (slice_from_raw(src, len), slice_from_raw_mut(dst, dst_len)) {
+        (Ok(src), Ok(dst)) => (src, dst),
+        (Err(status), _) | (_, Err(status)) => return status,
+    };
+    match append_zigzag_batch(dst, 0, src) {
+        Ok(end) => {
+            *written = end;
+            ZigZagStatus::Ok
+        }
+        Err(err) => {
+            *written = err.needed;
+            ZigZagStatus::BufferTooSmall
+        }
+    }
+}
+
+/// Decodes the zigzag-encoded varints making up the `len` bytes of `src`
+/// into the `dst_len` values of `dst`.
+///
+/// Stores the number of values decoded in `*decoded`, even if an error
+/// occurs partway through. Returns [`ZigZagStatus::BufferTooSmall`] if `src`
+/// holds more than `dst_len` varints.
+///
+/// # Safety
+///
+/// Unless its length is zero, `src` must point to `len` bytes and `dst` to
+/// `dst_len` values, which must not overlap. `decoded` must point to a
+/// writable `size_t`, or be null, in which case this returns
+/// [`ZigZagStatus::NullPointer`] without doing anything else.
+#[no_mangle]
+pub unsafe extern "C" fn zigzag_varint_decode_i64_buf(
+    src: *const u8,
+    len: usize,
+    dst: *mut i64,
+    dst_len: usize,
+    decoded: *mut usize,
+) -> ZigZagStatus {
+    if decoded.is_null() {
+        return ZigZagStatus::NullPointer;
+    }
+    let (mut src, dst) = match (slice_from_raw(src, len), slice_from_raw_mut(dst, dst_len)) {
+        (Ok(src), Ok(dst)) => (src, dst),
+        (Err(status), _) | (_, Err(status)) => return status,
+    };
+    *decoded = 0;
+    for slot in dst {
+        if src.is_empty() {
+            return ZigZagStatus::Ok;
+        }
+        match read_zigzag_varint(src) {
+            Ok((value, n)) => {
+                *slot = value;
+                *decoded += 1;
+                src = &src[n..];
+            }
+            Err(crate::VarintError::Truncated) => return ZigZagStatus::Truncated,
+            Err(_) => return ZigZagStatus::Overflow,
+        }
+    }
+    if src.is_empty() {
+        ZigZagStatus::Ok
+    } else {
+        ZigZagStatus::BufferTooSmall
+    }
+}
@@ -0,0 +1,77 @@
+//! Layouts of zigzag-encoded values within fixed-width words.
+
+use crate::{ZigZagDecode, ZigZagEncode};
+
+/// Zigzag encodes `x` and splits the result into its high and low 32-bit
+/// halves, in that order.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::fixed::encode_i64_to_halves;
+///
+/// assert_eq!(encode_i64_to_halves(-1), (0, 1));
+/// assert_eq!(encode_i64_to_halves(i64::MIN), (u32::MAX, u32::MAX));
+/// assert_eq!(encode_i64_to_halves(1 << 31), (1, 0));
+/// ```
+#[inline]
+pub fn encode_i64_to_halves(x: i64) -> (u32, u32) {
+    let encoded = x.zigzag_encode();
+    ((encoded >> 32) as u32, encoded as u32)
+}
+
+/// Reassembles a zigzag-encoded value from its high and low 32-bit halves
+/// and decodes it.
+///
+/// This is the inverse of [`encode_i64_to_halves`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::fixed::{decode_u64_from_halves, encode_i64_to_halves};
+///
+/// assert_eq!(decode_u64_from_halves(0, 1), -1);
+///
+/// let (hi, lo) = encode_i64_to_halves(i64::MIN);
+/// assert_eq!(decode_u64_from_halves(hi, lo), i64::MIN);
+/// ```
+#[inline]
+pub fn decode_u64_from_halves(hi: u32, lo: u32) -> i64 {
+    ((u64::from(hi) << 32) | u64::from(lo)).zigzag_decode()
+}
+
+/// Zigzag encodes a pair of 16-bit coordinates, such as a screen position,
+/// and packs them into a `u32`, with `x` in the high half and `y` in the low
+/// half.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::fixed::encode_point_i16;
+///
+/// assert_eq!(encode_point_i16(-1, 1), 0x0001_0002);
+/// assert_eq!(encode_point_i16(i16::MIN, 0), 0xffff_0000);
+/// ```
+#[inline]
+pub fn encode_point_i16(x: i16, y: i16) -> u32 {
+    let x: u16 = x.zigzag_encode();
+    let y: u16 = y.zigzag_encode();
+    u32::from(x) << 16 | u32::from(y)
+}
+
+/// Unpacks a pair of coordinates packed by [`encode_point_i16`] and decodes
+/// them.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::fixed::{decode_point_i16, encode_point_i16};
+///
+/// assert_eq!(decode_point_i16(encode_point_i16(-320, 240)), (-320, 240));
+/// ```
+#[inline]
+pub fn decode_point_i16(packed: u32) -> (i16, i16) {
+    let x = (packed >> 16) as u16;
+    let y = packed as u16;
+    (x.zigzag_decode(), y.zigzag_decode())
+}
@@ -0,0 +1,131 @@
+//! Transforms of `f32` and `f64` into unsigned integers that sort or
+//! compress well.
+//!
+//! The total-order transform is the floating-point counterpart of
+//! [offset binary](crate::offset): it flips the sign bit of non-negative
+//! values and every bit of negative ones, so the resulting integers sort
+//! exactly like [`f64::total_cmp`]. That places `-NaN` first, then negative
+//! infinity, the negative values, `-0.0`, `+0.0`, the positive values,
+//! infinity, and finally `NaN`.
+//!
+//! The XOR-delta transform is the one used by Gorilla-style time series
+//! compressors. Each value is replaced by the XOR of its bits with those of
+//! the value before it, the first with those of `0.0`. Neighbouring values
+//! of a slowly changing series share their sign, exponent and leading
+//! mantissa bits, so their XOR has long runs of leading and trailing zeros.
+//!
+//! Both round-trip every bit pattern, including every `NaN` payload.
+//!
+//! # Examples
+//!
+//! ```
+//! use zigzag::float::{from_total_order_f64, total_order_f64, xor_decode_f64, xor_encode_f64};
+//!
+//! let mut keys: Vec<u64> = [2.5, -0.0, f64::NEG_INFINITY, -1.0, 0.0]
+//!     .iter()
+//!     .map(|&x| total_order_f64(x))
+//!     .collect();
+//! keys.sort();
+//! let sorted: Vec<f64> = keys.iter().map(|&key| from_total_order_f64(key)).collect();
+//! assert_eq!(sorted, [f64::NEG_INFINITY, -1.0, -0.0, 0.0, 2.5]);
+//!
+//! let series = [21.5, 21.5, 21.75, 22.0];
+//! let mut xored = [0u64; 4];
+//! xor_encode_f64(&series, &mut xored);
+//! assert_eq!(xored[0], 21.5f64.to_bits());
+//! assert_eq!(xored[1], 0);
+//! assert!(xored[2].leading_zeros() >= 12 && xored[2].trailing_zeros() >= 40);
+//!
+//! let mut decoded = [0.0; 4];
+//! xor_decode_f64(&xored, &mut decoded);
+//! assert_eq!(decoded, series);
+//! ```
+
+macro_rules! impl_float {
+    ($(
+        $float:ident, $bits:ident,
+        $total_order:ident, $from_total_order:ident,
+        $xor_delta:ident, $xor_undelta:ident,
+        $xor_encode:ident, $xor_decode:ident;
+    )*) => {
+        $(
+            #[doc = concat!("Maps an `", stringify!($float), "` to a `", stringify!($bits), "` which sorts like it does under [`", stringify!($float), "::total_cmp`].")]
+            #[inline]
+            pub fn $total_order(x: $float) -> $bits {
+                let bits = x.to_bits();
+                let sign = 1 << ($bits::BITS - 1);
+                if bits & sign == 0 {
+                    bits ^ sign
+                } else {
+                    !bits
+                }
+            }
+
+            #[doc = concat!("Recovers the `", stringify!($float), "` mapped to `x` by [`", stringify!($total_order), "`].")]
+            #[inline]
+            pub fn $from_total_order(x: $bits) -> $float {
+                let sign = 1 << ($bits::BITS - 1);
+                $float::from_bits(if x & sign == 0 { !x } else { x ^ sign })
+            }
+
+            #[doc = concat!("Returns the XOR of the bits of `x` and those of the `", stringify!($float), "` before it, `prev`.")]
+            #[inline]
+            pub fn $xor_delta(prev: $float, x: $float) -> $bits {
+                prev.to_bits() ^ x.to_bits()
+            }
+
+            #[doc = concat!("Recovers the `", stringify!($float), "` XORed with `prev` by [`", stringify!($xor_delta), "`].")]
+            #[inline]
+            pub fn $xor_undelta(prev: $float, delta: $bits) -> $float {
+                $float::from_bits(prev.to_bits() ^ delta)
+            }
+
+            #[doc = concat!("XORs the bits of every `", stringify!($float), "` of `src` with those of the value before it, the first with those of `0.0`, into the corresponding slot of `dst`.")]
+            ///
+            /// # Panics
+            ///
+            /// Panics if `src` and `dst` have different lengths.
+            pub fn $xor_encode(src: &[$float], dst: &mut [$bits]) {
+                assert_eq!(
+                    src.len(),
+                    dst.len(),
+                    "source and destination slices have different lengths"
+                );
+                let mut prev = 0.0;
+                for (d, &x) in dst.iter_mut().zip(src) {
+                    *d = $xor_delta(prev, x);
+                    prev = x;
+                }
+            }
+
+            #[doc = concat!("Recovers every `", stringify!($float), "` XORed by [`", stringify!($xor_encode), "`] into the corresponding slot of `dst`.")]
+            ///
+            /// # Panics
+            ///
+            /// Panics if `src` and `dst` have different lengths.
+            pub fn $xor_decode(src: &[$bits], dst: &mut [$float]) {
+                assert_eq!(
+                    src.len(),
+                    dst.len(),
+                    "source and destination slices have different lengths"
+                );
+                let mut prev = 0.0;
+                for (d, &delta) in dst.iter_mut().zip(src) {
+                    prev = $xor_undelta(prev, delta);
+                    *d = prev;
+                }
+            }
+        )*
+    };
+}
+
+impl_float! {
+    f32, u32,
+    total_order_f32, from_total_order_f32,
+    xor_delta_f32, xor_undelta_f32,
+    xor_encode_f32, xor_decode_f32;
+    f64, u64,
+    total_order_f64, from_total_order_f64,
+    xor_delta_f64, xor_undelta_f64,
+    xor_encode_f64, xor_decode_f64;
+}
@@ -0,0 +1,71 @@
+//! Length-delimited framing of byte records.
+//!
+//! Each frame is the length of its payload as an unsigned varint followed by
+//! the payload itself, so a stream of frames can be split back into records
+//! without any separator bytes. The length is never negative, so it isn't
+//! zigzag encoded.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::varint::read_uvarint;
+#[cfg(feature = "alloc")]
+use crate::varint::write_uvarint;
+use crate::VarintError;
+
+/// Appends `payload` to `out` as a frame.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::frame::write_frame;
+///
+/// let mut out = Vec::new();
+/// write_frame(b"hi", &mut out);
+/// assert_eq!(out, [0x02, b'h', b'i']);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn write_frame(payload: &[u8], out: &mut Vec<u8>) {
+    write_uvarint(payload.len() as u64, out);
+    out.extend_from_slice(payload);
+}
+
+/// Reads a frame from the start of `input`.
+///
+/// On success, returns the frame's payload and the input remaining after
+/// the frame.
+///
+/// # Errors
+///
+/// Returns [`VarintError::Truncated`] if `input` ends before the end of the
+/// frame, and [`VarintError::Overflow`] if the length prefix is malformed or
+/// doesn't fit in a `usize`.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use zigzag::frame::{read_frame, write_frame};
+///
+/// let mut out = Vec::new();
+/// write_frame(b"first", &mut out);
+/// write_frame(b"", &mut out);
+///
+/// let (payload, rest) = read_frame(&out)?;
+/// assert_eq!(payload, b"first");
+/// let (payload, rest) = read_frame(rest)?;
+/// assert!(payload.is_empty() && rest.is_empty());
+/// # }
+/// # Ok::<(), zigzag::VarintError>(())
+/// ```
+pub fn read_frame(input: &[u8]) -> Result<(&[u8], &[u8]), VarintError> {
+    let (len, prefix_len) = read_uvarint(input)?;
+    let len = usize::try_from(len).map_err(|_| VarintError::Overflow)?;
+    let rest = &input[prefix_len..];
+    if rest.len() < len {
+        return Err(VarintError::Truncated);
+    }
+    Ok(rest.split_at(len))
+}
@@ -0,0 +1,107 @@
+//! Zigzag encoding of any integer type implementing
+//! [`num_traits::PrimInt`](https://docs.rs/num-traits).
+//!
+//! [`ZigZagEncode`](crate::ZigZagEncode) and
+//! [`ZigZagDecode`](crate::ZigZagDecode) are implemented for each pair of
+//! types individually. The functions of this module are instead generic
+//! over any signed `PrimInt` and any unsigned `PrimInt` of the same width,
+//! so they also cover integer newtypes and wider types from other crates,
+//! and let generic numeric code bound on `num-traits` alone.
+//!
+//! They compute the same encoding as the traits, using only the operations
+//! `PrimInt` provides, so for the primitive types the traits are faster.
+//!
+//! # Examples
+//!
+//! ```
+//! use num_traits::{PrimInt, Signed, Unsigned};
+//! use zigzag::generic::{decode_generic, encode_generic};
+//!
+//! fn round_trip<S: PrimInt + Signed, U: PrimInt + Unsigned>(x: S) -> S {
+//!     decode_generic::<U, S>(encode_generic::<S, U>(x))
+//! }
+//!
+//! assert_eq!(encode_generic::<i32, u32>(-1), 1);
+//! assert_eq!(encode_generic::<i64, u64>(i64::MIN), u64::MAX);
+//! assert_eq!(decode_generic::<u16, i16>(3), -2);
+//! assert_eq!(round_trip::<i128, u128>(i128::MIN + 1), i128::MIN + 1);
+//! ```
+
+use num_traits::{PrimInt, Signed, Unsigned};
+
+/// Returns the number of bits of `T`.
+#[inline]
+fn bits<T: PrimInt>() -> u32 {
+    T::zero().count_zeros()
+}
+
+#[inline]
+fn assert_same_width<S: PrimInt, U: PrimInt>() {
+    assert_eq!(
+        bits::<S>(),
+        bits::<U>(),
+        "signed and unsigned types have different widths"
+    );
+}
+
+/// Zigzag encodes `x` into the unsigned type of the same width.
+///
+/// # Panics
+///
+/// Panics if `S` and `U` have different widths.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::generic::encode_generic;
+///
+/// assert_eq!(encode_generic::<i8, u8>(-64), 127);
+/// assert_eq!(encode_generic::<isize, usize>(1), 2);
+/// ```
+#[inline]
+pub fn encode_generic<S, U>(x: S) -> U
+where
+    S: PrimInt + Signed,
+    U: PrimInt + Unsigned,
+{
+    assert_same_width::<S, U>();
+    let doubled = x << 1;
+    let encoded = if x.is_negative() { !doubled } else { doubled };
+    // `encoded` holds the bits of the result, which can't be converted to
+    // `U` directly when its top bit is set.
+    let low = U::from(encoded & S::max_value()).unwrap();
+    if encoded.is_negative() {
+        low | U::one() << (bits::<U>() as usize - 1)
+    } else {
+        low
+    }
+}
+
+/// Zigzag decodes `x` into the signed type of the same width.
+///
+/// # Panics
+///
+/// Panics if `U` and `S` have different widths.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::generic::decode_generic;
+///
+/// assert_eq!(decode_generic::<u8, i8>(255), i8::MIN);
+/// assert_eq!(decode_generic::<u64, i64>(4), 2);
+/// ```
+#[inline]
+pub fn decode_generic<U, S>(x: U) -> S
+where
+    U: PrimInt + Unsigned,
+    S: PrimInt + Signed,
+{
+    assert_same_width::<S, U>();
+    let half = S::from(x >> 1).unwrap();
+    if x & U::one() == U::one() {
+        !half
+    } else {
+        half
+    }
+}
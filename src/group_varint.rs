@@ -0,0 +1,135 @@
+//! Group varint encoding of four 32-bit integers at a time.
+//!
+//! A group starts with a tag byte holding four 2-bit fields, the `i`th of
+//! which—counting from the least significant bits—stores one less than the
+//! number of bytes used by the `i`th value. The tag is followed by the four
+//! values in order, each in little-endian byte order using only as many bytes
+//! as it needs. Because all four lengths are known after reading a single
+//! byte, decoding needs no per-byte continuation checks.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::ZigZagEncode;
+use crate::{VarintError, ZigZagDecode};
+
+/// The maximum number of bytes a single group occupies.
+pub const MAX_GROUP_LEN: usize = 17;
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn byte_len(x: u32) -> usize {
+    4 - (x | 1).leading_zeros() as usize / 8
+}
+
+/// Appends a group of four unsigned values to `out`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::group_varint::encode_group_varint;
+///
+/// let mut out = Vec::new();
+/// encode_group_varint(&[1, 256, 0, 0x0102_0304], &mut out);
+/// assert_eq!(out, [0b11_00_01_00, 1, 0, 1, 0, 4, 3, 2, 1]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_group_varint(values: &[u32; 4], out: &mut Vec<u8>) {
+    let mut tag = 0u8;
+    let tag_index = out.len();
+    out.push(0);
+    for (i, &value) in values.iter().enumerate() {
+        let len = byte_len(value);
+        tag |= ((len - 1) as u8) << (2 * i);
+        out.extend_from_slice(&value.to_le_bytes()[..len]);
+    }
+    out[tag_index] = tag;
+}
+
+/// Reads a group of four unsigned values from the start of `input`.
+///
+/// On success, returns the values and the number of bytes the group
+/// occupied, including its tag byte.
+///
+/// # Errors
+///
+/// Returns [`VarintError::Truncated`] if `input` is shorter than the group
+/// its tag byte describes.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use zigzag::group_varint::{decode_group_varint, encode_group_varint};
+///
+/// let values = [7, 300, 70_000, u32::MAX];
+/// let mut out = Vec::new();
+/// encode_group_varint(&values, &mut out);
+/// assert_eq!(out.len(), 1 + 1 + 2 + 3 + 4);
+/// assert_eq!(decode_group_varint(&out), Ok((values, out.len())));
+/// # }
+/// ```
+pub fn decode_group_varint(input: &[u8]) -> Result<([u32; 4], usize), VarintError> {
+    let (&tag, data) = input.split_first().ok_or(VarintError::Truncated)?;
+    let lens = [
+        usize::from(tag & 3) + 1,
+        usize::from((tag >> 2) & 3) + 1,
+        usize::from((tag >> 4) & 3) + 1,
+        usize::from(tag >> 6) + 1,
+    ];
+    let total = lens.iter().sum::<usize>();
+    if data.len() < total {
+        return Err(VarintError::Truncated);
+    }
+
+    let mut values = [0u32; 4];
+    let mut offset = 0;
+    for (value, &len) in values.iter_mut().zip(&lens) {
+        let mut bytes = [0u8; 4];
+        bytes[..len].copy_from_slice(&data[offset..offset + len]);
+        *value = u32::from_le_bytes(bytes);
+        offset += len;
+    }
+    Ok((values, 1 + total))
+}
+
+/// Zigzag encodes four signed values and appends them to `out` as a group.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::group_varint::{decode_group_zigzag, encode_group_zigzag};
+///
+/// let values = [-1, 200, -40_000, i32::MIN];
+/// let mut out = Vec::new();
+/// encode_group_zigzag(&values, &mut out);
+/// assert_eq!(decode_group_zigzag(&out), Ok((values, out.len())));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_group_zigzag(values: &[i32; 4], out: &mut Vec<u8>) {
+    let encoded = [
+        values[0].zigzag_encode(),
+        values[1].zigzag_encode(),
+        values[2].zigzag_encode(),
+        values[3].zigzag_encode(),
+    ];
+    encode_group_varint(&encoded, out);
+}
+
+/// Reads a group of four zigzag-encoded values from the start of `input`.
+///
+/// # Errors
+///
+/// See [`decode_group_varint`].
+pub fn decode_group_zigzag(input: &[u8]) -> Result<([i32; 4], usize), VarintError> {
+    let (values, len) = decode_group_varint(input)?;
+    let decoded = [
+        values[0].zigzag_decode(),
+        values[1].zigzag_decode(),
+        values[2].zigzag_decode(),
+        values[3].zigzag_decode(),
+    ];
+    Ok((decoded, len))
+}
@@ -0,0 +1,79 @@
+//! Encoding pairs of signed streams, such as the coordinates of a point
+//! cloud, into a single interleaved varint stream.
+//!
+//! The values of the two streams alternate, `x0, y0, x1, y1, ...`, each as a
+//! zigzag-encoded varint.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::varint::{read_uvarint, write_uvarint};
+use crate::{VarintError, ZigZagDecode, ZigZagEncode};
+
+/// Appends the values of `xs` and `ys` to `out`, alternating between them.
+///
+/// # Panics
+///
+/// Panics if `xs` and `ys` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::interleave::encode_interleaved;
+///
+/// let mut out = Vec::new();
+/// encode_interleaved(&[1, 2], &[-1, -2], &mut out);
+/// assert_eq!(out, [0x02, 0x01, 0x04, 0x03]);
+/// ```
+pub fn encode_interleaved(xs: &[i32], ys: &[i32], out: &mut Vec<u8>) {
+    assert_eq!(
+        xs.len(),
+        ys.len(),
+        "interleaved slices have different lengths"
+    );
+    for (&x, &y) in xs.iter().zip(ys) {
+        write_uvarint(u64::from(x.zigzag_encode()), out);
+        write_uvarint(u64::from(y.zigzag_encode()), out);
+    }
+}
+
+/// Splits an interleaved varint stream back into its two streams.
+///
+/// # Errors
+///
+/// Returns [`VarintError::Truncated`] if `input` ends partway through a
+/// varint or holds an odd number of them, and [`VarintError::Overflow`] if a
+/// varint doesn't fit in an `i32`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::interleave::decode_interleaved;
+///
+/// let (xs, ys) = decode_interleaved(&[0x02, 0x01, 0x04, 0x03])?;
+/// assert_eq!(xs, [1, 2]);
+/// assert_eq!(ys, [-1, -2]);
+/// # Ok::<(), zigzag::VarintError>(())
+/// ```
+pub fn decode_interleaved(mut input: &[u8]) -> Result<(Vec<i32>, Vec<i32>), VarintError> {
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+    while !input.is_empty() {
+        let (x, len) = read_i32(input)?;
+        input = &input[len..];
+        if input.is_empty() {
+            return Err(VarintError::Truncated);
+        }
+        let (y, len) = read_i32(input)?;
+        input = &input[len..];
+        xs.push(x);
+        ys.push(y);
+    }
+    Ok((xs, ys))
+}
+
+fn read_i32(input: &[u8]) -> Result<(i32, usize), VarintError> {
+    let (raw, len) = read_uvarint(input)?;
+    let raw = u32::try_from(raw).map_err(|_| VarintError::Overflow)?;
+    Ok((raw.zigzag_decode(), len))
+}
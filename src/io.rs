@@ -0,0 +1,271 @@
+//! Adapters for zigzag encoding data passing through [`std::io`] streams.
+//!
+//! Besides [`ZigZagTranscoder`], the [`ZigZagWriteExt`] and [`ZigZagReadExt`]
+//! extension traits add methods to every writer and reader which stream
+//! signed integers as zigzag-encoded varints.
+//!
+//! Buffers from the `bytes` crate have their own extension traits in
+//! `zigzag::buf`, with the `bytes` feature enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! use zigzag::io::{ZigZagReadExt, ZigZagWriteExt};
+//!
+//! let mut out = Vec::new();
+//! out.write_zigzag_varint_i64(-1)?;
+//! out.write_zigzag_varint_i32(150)?;
+//! assert_eq!(out, [0x01, 0xac, 0x02]);
+//!
+//! let mut input = &out[..];
+//! assert_eq!(input.read_zigzag_varint_i64()?, -1);
+//! assert_eq!(input.read_zigzag_varint_i32()?, 150);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::convert::TryFrom;
+use std::io::{self, Read, Write};
+use std::vec::Vec;
+
+use crate::varint::{encode_uvarint, write_zigzag_varint, VarintDecoderState, MAX_VARINT_LEN};
+use crate::{DecodeError, ZigZagEncode};
+
+/// A writer which reinterprets the bytes written to it as little-endian
+/// `i32`s and forwards each one to the inner writer as a zigzag-encoded
+/// varint.
+///
+/// The integers may be split across `write` calls arbitrarily; any trailing
+/// bytes of an incomplete integer are held until the rest of it arrives. Call
+/// [`finish`](ZigZagTranscoder::finish) once done to check that no partial
+/// integer was left over.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use zigzag::io::ZigZagTranscoder;
+/// use zigzag::varint::zigzag_encode_all;
+///
+/// let values: Vec<i32> = (-5..5).collect();
+/// let raw: Vec<u8> = values.iter().flat_map(|x| x.to_le_bytes()).collect();
+///
+/// let mut transcoder = ZigZagTranscoder::new(Vec::new());
+/// for chunk in raw.chunks(7) {
+///     transcoder.write_all(chunk)?;
+/// }
+/// let out = transcoder.finish()?;
+///
+/// let wide: Vec<i64> = values.iter().map(|&x| i64::from(x)).collect();
+/// assert_eq!(out, zigzag_encode_all(&wide));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct ZigZagTranscoder<W: Write> {
+    inner: W,
+    pending: [u8; 4],
+    pending_len: usize,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> ZigZagTranscoder<W> {
+    /// Creates a transcoder which writes its output to `inner`.
+    pub fn new(inner: W) -> Self {
+        ZigZagTranscoder {
+            inner,
+            pending: [0; 4],
+            pending_len: 0,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Returns a reference to the inner writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    ///
+    /// Writing to it directly interleaves its output with the transcoder's.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Flushes the inner writer and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`io::ErrorKind::UnexpectedEof`] if the bytes
+    /// written so far end partway through an integer, as well as any error
+    /// from flushing the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.pending_len != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "input ended partway through an i32",
+            ));
+        }
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for ZigZagTranscoder<W> {
+    /// Transcodes every complete integer in `buf` to the inner writer.
+    ///
+    /// All of `buf` is always consumed. If writing to the inner writer
+    /// fails, the integers completed by this call are lost.
+    fn write(&mut self, mut buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len();
+        self.buf.clear();
+        while !buf.is_empty() {
+            let take = (4 - self.pending_len).min(buf.len());
+            self.pending[self.pending_len..self.pending_len + take].copy_from_slice(&buf[..take]);
+            self.pending_len += take;
+            buf = &buf[take..];
+            if self.pending_len == 4 {
+                let value = i32::from_le_bytes(self.pending);
+                write_zigzag_varint(i64::from(value), &mut self.buf);
+                self.pending_len = 0;
+            }
+        }
+        self.inner.write_all(&self.buf)?;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Extends every [`Write`]r with methods which write signed integers as
+/// zigzag-encoded varints.
+///
+/// Each method writes exactly the bytes
+/// [`write_zigzag_varint`] would append,
+/// and returns how many there were.
+pub trait ZigZagWriteExt: Write {
+    /// Writes `x` as a zigzag-encoded varint.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to `self`.
+    #[inline]
+    fn write_zigzag_varint_i64(&mut self, x: i64) -> io::Result<usize> {
+        let mut buf = [0; MAX_VARINT_LEN];
+        let len = encode_uvarint(x.zigzag_encode(), &mut buf);
+        self.write_all(&buf[..len])?;
+        Ok(len)
+    }
+
+    /// Writes `x` as a zigzag-encoded varint.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to `self`.
+    #[inline]
+    fn write_zigzag_varint_i32(&mut self, x: i32) -> io::Result<usize> {
+        self.write_zigzag_varint_i64(i64::from(x))
+    }
+
+    /// Writes `x` as a zigzag-encoded varint.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to `self`.
+    #[inline]
+    fn write_zigzag_varint_i16(&mut self, x: i16) -> io::Result<usize> {
+        self.write_zigzag_varint_i64(i64::from(x))
+    }
+}
+
+impl<W: Write + ?Sized> ZigZagWriteExt for W {}
+
+/// Extends every [`Read`]er with methods which read zigzag-encoded varints as
+/// signed integers.
+///
+/// The methods read one byte at a time and never past the end of the varint,
+/// so an unbuffered reader, such as a [`File`](std::fs::File) or a socket,
+/// should be wrapped in a [`BufReader`](std::io::BufReader).
+///
+/// # Errors
+///
+/// Every method returns an error of kind [`io::ErrorKind::UnexpectedEof`],
+/// wrapping [`DecodeError::Truncated`], if the input ends before the varint
+/// does, and an error of kind [`io::ErrorKind::InvalidData`], wrapping
+/// [`DecodeError::Overflow`], if the varint encodes a value which doesn't fit
+/// in the type being read. Any other error from reading `self` is returned
+/// as is.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+/// use zigzag::io::ZigZagReadExt;
+/// use zigzag::DecodeError;
+///
+/// let err = (&[0xac][..]).read_zigzag_varint_i64().unwrap_err();
+/// assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+/// let inner = err.into_inner().unwrap();
+/// assert_eq!(inner.downcast_ref(), Some(&DecodeError::Truncated { needed: 1 }));
+///
+/// let err = (&[0x80, 0x80, 0x04][..]).read_zigzag_varint_i16().unwrap_err();
+/// assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+/// assert_eq!(err.into_inner().unwrap().downcast_ref(), Some(&DecodeError::Overflow));
+/// ```
+pub trait ZigZagReadExt: Read {
+    /// Reads a zigzag-encoded varint as an `i64`.
+    #[inline]
+    fn read_zigzag_varint_i64(&mut self) -> io::Result<i64> {
+        read_zigzag_varint(self)
+    }
+
+    /// Reads a zigzag-encoded varint as an `i32`.
+    #[inline]
+    fn read_zigzag_varint_i32(&mut self) -> io::Result<i32> {
+        i32::try_from(read_zigzag_varint(self)?).map_err(|_| overflow())
+    }
+
+    /// Reads a zigzag-encoded varint as an `i16`.
+    #[inline]
+    fn read_zigzag_varint_i16(&mut self) -> io::Result<i16> {
+        i16::try_from(read_zigzag_varint(self)?).map_err(|_| overflow())
+    }
+}
+
+impl<R: Read + ?Sized> ZigZagReadExt for R {}
+
+#[inline]
+pub(crate) fn overflow() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, DecodeError::Overflow)
+}
+
+#[inline]
+pub(crate) fn truncated() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        DecodeError::Truncated { needed: 1 },
+    )
+}
+
+/// Reads a zigzag-encoded varint from `reader` a byte at a time, following
+/// the same rules as [`read_zigzag_varint`](crate::varint::read_zigzag_varint).
+fn read_zigzag_varint<R: Read + ?Sized>(reader: &mut R) -> io::Result<i64> {
+    let mut decoder = VarintDecoderState::new();
+    loop {
+        let mut byte = [0];
+        reader.read_exact(&mut byte).map_err(|err| {
+            if err.kind() == io::ErrorKind::UnexpectedEof {
+                truncated()
+            } else {
+                err
+            }
+        })?;
+        match decoder.feed(byte[0]) {
+            None => {}
+            Some(Ok(value)) => return Ok(value),
+            Some(Err(_)) => return Err(overflow()),
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Iterator adapters that zigzag encode or decode every item of an iterator.
+//!
+//! The adapters are created through the [`ZigZagEncodeIterExt`] and
+//! [`ZigZagDecodeIterExt`] extension traits, which are implemented for every
+//! [`Iterator`]. The adapters forward [`DoubleEndedIterator`],
+//! [`ExactSizeIterator`] and [`FusedIterator`] from the iterator they wrap,
+//! so they can be reversed, or have their length taken, whenever it can.
+
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use crate::{ZigZagDecode, ZigZagEncode};
+
+/// An extension trait for iterators over values that implement
+/// [`ZigZagEncode`].
+pub trait ZigZagEncodeIterExt: Iterator {
+    /// Creates an iterator which zigzag encodes every item of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zigzag::ZigZagEncodeIterExt;
+    ///
+    /// let encoded: Vec<u32> = vec![0i32, -1, 1].into_iter().zigzag_encoded().collect();
+    /// assert_eq!(encoded, [0, 1, 2]);
+    /// ```
+    #[inline]
+    fn zigzag_encoded<U>(self) -> ZigZagEncodeIter<Self, U>
+    where
+        Self: Sized,
+        Self::Item: ZigZagEncode<Output = U>,
+    {
+        ZigZagEncodeIter {
+            iter: self,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<I: Iterator> ZigZagEncodeIterExt for I {}
+
+/// An extension trait for iterators over values that implement
+/// [`ZigZagDecode`].
+pub trait ZigZagDecodeIterExt: Iterator {
+    /// Creates an iterator which zigzag decodes every item of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zigzag::ZigZagDecodeIterExt;
+    ///
+    /// let decoded: Vec<i32> = vec![0u32, 1, 2].into_iter().zigzag_decoded().collect();
+    /// assert_eq!(decoded, [0, -1, 1]);
+    /// ```
+    #[inline]
+    fn zigzag_decoded<S>(self) -> ZigZagDecodeIter<Self, S>
+    where
+        Self: Sized,
+        Self::Item: ZigZagDecode<Output = S>,
+    {
+        ZigZagDecodeIter {
+            iter: self,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<I: Iterator> ZigZagDecodeIterExt for I {}
+
+/// An iterator that zigzag encodes the items of another iterator.
+///
+/// This `struct` is created by the [`zigzag_encoded`] method on
+/// [`ZigZagEncodeIterExt`].
+///
+/// [`zigzag_encoded`]: ZigZagEncodeIterExt::zigzag_encoded
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ZigZagEncodeIter<I, U> {
+    iter: I,
+    marker: PhantomData<fn() -> U>,
+}
+
+impl<I, U> Iterator for ZigZagEncodeIter<I, U>
+where
+    I: Iterator,
+    I::Item: ZigZagEncode<Output = U>,
+{
+    type Item = U;
+
+    #[inline]
+    fn next(&mut self) -> Option<U> {
+        self.iter.next().map(ZigZagEncode::zigzag_encode)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<U> {
+        self.iter.nth(n).map(ZigZagEncode::zigzag_encode)
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, U) -> B,
+    {
+        self.iter
+            .fold(init, move |acc, x| f(acc, x.zigzag_encode()))
+    }
+}
+
+impl<I, U> DoubleEndedIterator for ZigZagEncodeIter<I, U>
+where
+    I: DoubleEndedIterator,
+    I::Item: ZigZagEncode<Output = U>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<U> {
+        self.iter.next_back().map(ZigZagEncode::zigzag_encode)
+    }
+
+    #[inline]
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, U) -> B,
+    {
+        self.iter
+            .rfold(init, move |acc, x| f(acc, x.zigzag_encode()))
+    }
+}
+
+impl<I, U> ExactSizeIterator for ZigZagEncodeIter<I, U>
+where
+    I: ExactSizeIterator,
+    I::Item: ZigZagEncode<Output = U>,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<I, U> FusedIterator for ZigZagEncodeIter<I, U>
+where
+    I: FusedIterator,
+    I::Item: ZigZagEncode<Output = U>,
+{
+}
+
+/// An iterator that zigzag decodes the items of another iterator.
+///
+/// This `struct` is created by the [`zigzag_decoded`] method on
+/// [`ZigZagDecodeIterExt`].
+///
+/// [`zigzag_decoded`]: ZigZagDecodeIterExt::zigzag_decoded
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ZigZagDecodeIter<I, S> {
+    iter: I,
+    marker: PhantomData<fn() -> S>,
+}
+
+impl<I, S> Iterator for ZigZagDecodeIter<I, S>
+where
+    I: Iterator,
+    I::Item: ZigZagDecode<Output = S>,
+{
+    type Item = S;
+
+    #[inline]
+    fn next(&mut self) -> Option<S> {
+        self.iter.next().map(ZigZagDecode::zigzag_decode)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<S> {
+        self.iter.nth(n).map(ZigZagDecode::zigzag_decode)
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, S) -> B,
+    {
+        self.iter
+            .fold(init, move |acc, x| f(acc, x.zigzag_decode()))
+    }
+}
+
+impl<I, S> DoubleEndedIterator for ZigZagDecodeIter<I, S>
+where
+    I: DoubleEndedIterator,
+    I::Item: ZigZagDecode<Output = S>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<S> {
+        self.iter.next_back().map(ZigZagDecode::zigzag_decode)
+    }
+
+    #[inline]
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, S) -> B,
+    {
+        self.iter
+            .rfold(init, move |acc, x| f(acc, x.zigzag_decode()))
+    }
+}
+
+impl<I, S> ExactSizeIterator for ZigZagDecodeIter<I, S>
+where
+    I: ExactSizeIterator,
+    I::Item: ZigZagDecode<Output = S>,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<I, S> FusedIterator for ZigZagDecodeIter<I, S>
+where
+    I: FusedIterator,
+    I::Item: ZigZagDecode<Output = S>,
+{
+}
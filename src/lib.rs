@@ -76,18 +76,228 @@
 //!
 //! assert_eq!(usize::MAX.zigzag_decode(), isize::MIN);
 //! ```
+//!
+//! Both traits are also implemented for the non-zero integer types, since
+//! zigzag encoding maps zero to zero:
+//!
+//! ```
+//! use std::num::{NonZeroI32, NonZeroU32};
+//! use zigzag::{ZigZagDecode, ZigZagEncode};
+//!
+//! let x = NonZeroI32::new(-1).unwrap();
+//! assert_eq!(x.zigzag_encode(), NonZeroU32::new(1).unwrap());
+//! assert_eq!(x.zigzag_encode().zigzag_decode(), x);
+//! ```
+//!
+//! Likewise, `Option`s, arrays and `Vec`s of encodable values encode to the
+//! same containers of the encoded values, and `Wrapping` and `Saturating`
+//! values encode to the same wrapper around the encoded value:
+//!
+//! ```
+//! use std::num::Wrapping;
+//! use zigzag::ZigZagEncode;
+//!
+//! assert_eq!(Some(-1i32).zigzag_encode(), Some(1u32));
+//! assert_eq!(None::<i32>.zigzag_encode(), None::<u32>);
+//! assert_eq!([-1i32, 1].zigzag_encode(), [1u32, 2]);
+//! assert_eq!(Wrapping(-1i32).zigzag_encode(), Wrapping(1u32));
+//! ```
+//!
+//! The [`encode`] and [`decode`] functions infer the type of their result, so
+//! they never need the annotations the trait methods sometimes do:
+//!
+//! ```
+//! let encoded = zigzag::encode(-1i32);
+//! assert_eq!(encoded, 1u32);
+//! assert_eq!(zigzag::decode(encoded), -1i32);
+//! ```
+//!
+//! # Iterators
+//!
+//! The [`ZigZagEncodeIterExt`] and [`ZigZagDecodeIterExt`] traits add
+//! adapters to every iterator:
+//!
+//! ```
+//! use zigzag::{ZigZagDecodeIterExt, ZigZagEncodeIterExt};
+//!
+//! let deltas = [3i64, -2, 0, -7];
+//! let encoded: Vec<u64> = deltas.iter().copied().zigzag_encoded().collect();
+//! assert_eq!(encoded, [6, 3, 0, 13]);
+//!
+//! let decoded: Vec<i64> = encoded.into_iter().zigzag_decoded().collect();
+//! assert_eq!(decoded, deltas);
+//! ```
+//!
+//! # `no_std`
+//!
+//! The crate is `#![no_std]`. The traits, and every function which reads from
+//! or writes to a caller-provided slice, only need `core`. The default `std`
+//! feature implements [`std::error::Error`] for the error types and enables
+//! the [`io`] and [`time`] modules. Without it, the `alloc` feature still
+//! provides the helpers which write to a `Vec` or otherwise allocate.
+//!
+//! # Derive Macros
+//!
+//! With the `derive` feature enabled, `#[derive(ZigZagEncode)]` generates a
+//! mirror of a struct with every integer field zigzag encoded, and
+//! `#[derive(ZigZagDecode)]` converts the mirror back:
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use zigzag::{ZigZagDecode, ZigZagEncode};
+//!
+//! #[derive(ZigZagEncode, ZigZagDecode, Debug, PartialEq)]
+//! struct Move {
+//!     dx: i8,
+//!     dy: i8,
+//! }
+//!
+//! let encoded: EncodedMove = Move { dx: -1, dy: 1 }.zigzag_encode();
+//! assert_eq!((encoded.dx, encoded.dy), (1u8, 2u8));
+//! assert_eq!(encoded.zigzag_decode(), Move { dx: -1, dy: 1 });
+//! # }
+//! ```
+
+#![no_std]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
-use std::mem::size_of;
+use core::convert::TryFrom;
+use core::mem::size_of;
+
+#[cfg(feature = "tokio")]
+pub mod async_io;
+#[cfg(feature = "alloc")]
+pub mod bigint;
+pub mod bits;
+pub mod bitstream;
+#[cfg(feature = "alloc")]
+pub mod block;
+#[cfg(feature = "bnum")]
+mod bnum;
+#[cfg(feature = "bytes")]
+pub mod buf;
+pub mod const_fn;
+#[cfg(feature = "alloc")]
+pub mod decimal;
+pub mod delta;
+pub mod encoded;
+pub mod endian;
+mod error;
+#[cfg(feature = "ethnum")]
+mod ethnum;
+#[cfg(feature = "fast")]
+mod fast;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fixed;
+pub mod float;
+pub mod frame;
+#[cfg(feature = "num-traits")]
+pub mod generic;
+pub mod group_varint;
+#[cfg(feature = "alloc")]
+pub mod interleave;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod iter;
+pub mod lut;
+#[cfg(feature = "memmap2")]
+pub mod mmap;
+mod nonzero;
+#[cfg(feature = "num-bigint")]
+mod num_bigint;
+pub mod offset;
+pub mod parallel;
+#[cfg(feature = "alloc")]
+pub mod parquet_delta;
+pub mod prefix_varint;
+#[cfg(feature = "alloc")]
+pub mod relative;
+pub mod separated;
+#[cfg(feature = "serde")]
+pub mod serde_zigzag;
+#[cfg(feature = "serde")]
+pub use serde_zigzag as serde;
+#[cfg(feature = "simd")]
+pub mod simd;
+pub mod slice;
+pub mod stats;
+pub mod streamvbyte;
+#[cfg(feature = "std")]
+pub mod time;
+pub mod timestamps;
+pub mod varint;
+pub mod varint_be;
+mod verify;
+pub mod width;
+mod wrapper;
+
+pub use error::{BufferTooSmall, DecodeError, LengthError, OutOfRange, TimeError, VarintError};
+pub use iter::{ZigZagDecodeIterExt, ZigZagEncodeIterExt};
+pub use verify::{find_first_roundtrip_failure, find_first_roundtrip_failure_with, self_test};
+#[cfg(feature = "derive")]
+pub use zigzag_derive::{ZigZagDecode, ZigZagEncode};
 
 const BITS_PER_BYTE: usize = 8;
 
+/// Returns the number of bits in the representation of `T`.
+///
+/// This is the width the zigzag formulas are computed at, exposed so that
+/// code targeting unusual platforms can check it, such as for `isize` and
+/// `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::type_bits;
+///
+/// assert_eq!(type_bits::<u32>(), 32);
+/// const _: () = assert!(type_bits::<usize>() == usize::BITS as usize);
+/// ```
+#[inline]
+pub const fn type_bits<T>() -> usize {
+    size_of::<T>() * BITS_PER_BYTE
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_sealed {
+    ($($ty:ty),*) => {
+        $(impl private::Sealed for $ty {})*
+    };
+}
+
+impl_sealed!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
 /// A trait intended to extend signed integer types with the ability to get
 /// their unsigned representation as derived by zigzag encoding.
 ///
 /// This trait does so by implementing the [`zigzag_encode`] method.
 ///
+/// This trait is sealed, so that methods can be added to it without breaking
+/// downstream code. To give your own integer newtype a zigzag encoding, use
+/// [`impl_zigzag_newtype!`] and [`ZigZagExt`] instead.
+///
+/// Trait methods can't be called in const contexts; the [`const_fn`] module
+/// has a `const fn` for each primitive type instead.
+///
+/// The encoded type is the associated [`Output`], so generic code can name
+/// it as `T::Output` and bound it as `T: ZigZagEncode<Output = u32>`.
+///
 /// [`zigzag_encode`]: ZigZagEncode::zigzag_encode
-pub trait ZigZagEncode<U> {
+/// [`Output`]: ZigZagEncode::Output
+pub trait ZigZagEncode: private::Sealed {
+    /// The unsigned type that `Self` zigzag encodes to.
+    type Output;
+
     /// Decodes `self` into its unsigned counterpart by using zigzag encoding.
     ///
     /// For more information on zigzag encoding, see its section in the
@@ -102,25 +312,54 @@ pub trait ZigZagEncode<U> {
     /// assert_eq!((-1i8).zigzag_encode(), 1u8);
     /// assert_eq!(1i8.zigzag_encode(), 2u8);
     /// ```
-    fn zigzag_encode(self) -> U;
+    fn zigzag_encode(self) -> Self::Output;
+
+    /// Encodes every value of `src` into the corresponding slot of `dst`,
+    /// which has the same length. This backs
+    /// [`slice::zigzag_encode_slice`], so that types with a vectorized
+    /// implementation can provide it.
+    #[doc(hidden)]
+    #[inline]
+    fn encode_slice(src: &[Self], dst: &mut [Self::Output])
+    where
+        Self: Copy,
+    {
+        for (d, &s) in dst.iter_mut().zip(src) {
+            *d = s.zigzag_encode();
+        }
+    }
 }
 
 macro_rules! impl_encode {
-    ($signed:ty, $unsigned:ty) => {
-        impl ZigZagEncode<$unsigned> for $signed {
+    ($signed:ty, $unsigned:ty $(, $fast:ident)?) => {
+        impl ZigZagEncode for $signed {
+            type Output = $unsigned;
+
             #[inline]
             fn zigzag_encode(self) -> $unsigned {
-                const TYPE_BITS: usize = size_of::<$unsigned>() * BITS_PER_BYTE;
+                const TYPE_BITS: usize = type_bits::<$unsigned>();
+                // Shifting left discards the sign bit rather than overflowing:
+                // overflow checks only apply to the shift amount, which is
+                // always in range here.
                 (self >> TYPE_BITS - 1) as $unsigned ^ (self << 1) as $unsigned
             }
+
+            $(
+                #[cfg(feature = "fast")]
+                #[inline]
+                fn encode_slice(src: &[$signed], dst: &mut [$unsigned]) {
+                    fast::$fast(src, dst)
+                }
+            )?
         }
     };
 }
 
+#[cfg(not(feature = "lut"))]
 impl_encode!(i8, u8);
-impl_encode!(i16, u16);
-impl_encode!(i32, u32);
-impl_encode!(i64, u64);
+impl_encode!(i16, u16, encode_i16);
+impl_encode!(i32, u32, encode_i32);
+impl_encode!(i64, u64, encode_i64);
 impl_encode!(i128, u128);
 impl_encode!(isize, usize);
 
@@ -129,8 +368,13 @@ impl_encode!(isize, usize);
 ///
 /// This trait does so by implementing the [`zigzag_decode`] method.
 ///
+/// Like [`ZigZagEncode`], this trait is sealed.
+///
 /// [`zigzag_decode`]: ZigZagDecode::zigzag_decode
-pub trait ZigZagDecode<S> {
+pub trait ZigZagDecode: private::Sealed {
+    /// The signed type that `Self` zigzag decodes to.
+    type Output;
+
     /// Decodes `self` into its signed counterpart by using zigzag decoding.
     ///
     /// For more information on zigzag decoding, see its section in the
@@ -145,23 +389,402 @@ pub trait ZigZagDecode<S> {
     /// assert_eq!(1u8.zigzag_decode(), -1i8);
     /// assert_eq!(2u8.zigzag_decode(), 1i8);
     /// ```
-    fn zigzag_decode(self) -> S;
+    fn zigzag_decode(self) -> Self::Output;
+
+    /// Decodes every value of `src` into the corresponding slot of `dst`,
+    /// which has the same length. This backs
+    /// [`slice::zigzag_decode_slice`], so that types with a vectorized
+    /// implementation can provide it.
+    #[doc(hidden)]
+    #[inline]
+    fn decode_slice(src: &[Self], dst: &mut [Self::Output])
+    where
+        Self: Copy,
+    {
+        for (d, &s) in dst.iter_mut().zip(src) {
+            *d = s.zigzag_decode();
+        }
+    }
 }
 
 macro_rules! impl_decode {
-    ($unsigned:ty, $signed:ty) => {
-        impl ZigZagDecode<$signed> for $unsigned {
+    ($unsigned:ty, $signed:ty $(, $fast:ident)?) => {
+        impl ZigZagDecode for $unsigned {
+            type Output = $signed;
+
             #[inline]
             fn zigzag_decode(self) -> $signed {
                 (self >> 1) as $signed ^ -((self & 1) as $signed)
             }
+
+            $(
+                #[cfg(feature = "fast")]
+                #[inline]
+                fn decode_slice(src: &[$unsigned], dst: &mut [$signed]) {
+                    fast::$fast(src, dst)
+                }
+            )?
         }
     };
 }
 
+#[cfg(not(feature = "lut"))]
 impl_decode!(u8, i8);
-impl_decode!(u16, i16);
-impl_decode!(u32, i32);
-impl_decode!(u64, i64);
+#[cfg(not(feature = "lut16"))]
+impl_decode!(u16, i16, decode_u16);
+impl_decode!(u32, i32, decode_u32);
+impl_decode!(u64, i64, decode_u64);
 impl_decode!(u128, i128);
 impl_decode!(usize, isize);
+
+/// A trait tying a signed integer type to the unsigned type it zigzag encodes
+/// to.
+///
+/// This is useful for generic code that needs to name both halves of a pair,
+/// such as a type that stores `T`'s encoded form.
+///
+/// Only integer types implement it. Containers such as `Option`s and arrays
+/// have no single width, so they implement just [`ZigZagEncode`] and
+/// [`ZigZagDecode`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::{ZigZag, ZigZagEncode};
+///
+/// fn encode_all<T: ZigZag>(values: &[T]) -> Vec<T::Unsigned> {
+///     values.iter().map(|&x| x.zigzag_encode()).collect()
+/// }
+///
+/// assert_eq!(encode_all(&[-1i16, 1]), [1u16, 2]);
+/// ```
+pub trait ZigZag: Copy + ZigZagEncode<Output = Self::Unsigned> {
+    /// The unsigned type that `Self` zigzag encodes to.
+    type Unsigned: Copy + ZigZagDecode<Output = Self> + ZigZagUnsigned<Signed = Self>;
+
+    /// The number of bits in the representation of `Self`, and therefore in
+    /// its encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zigzag::ZigZag;
+    ///
+    /// assert_eq!(<i32 as ZigZag>::BITS, 32);
+    /// assert_eq!(<isize as ZigZag>::BITS, usize::BITS);
+    ///
+    /// let buf = [0u8; <i64 as ZigZag>::BITS as usize / 8];
+    /// assert_eq!(buf.len(), 8);
+    /// ```
+    const BITS: u32;
+
+    /// The encoding of `-1`, which is `1` at every width.
+    ///
+    /// This lets generic code use the encoding of `-1`, such as a sentinel,
+    /// without encoding it at runtime. The encoding of zero is
+    /// [`ZigZagZero::ENCODED_ZERO`] instead, since the non-zero integer types
+    /// can't represent it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zigzag::ZigZag;
+    ///
+    /// const SENTINEL: u32 = <i32 as ZigZag>::ENCODED_NEG_ONE;
+    /// assert_eq!(SENTINEL, 1);
+    /// ```
+    const ENCODED_NEG_ONE: Self::Unsigned;
+
+    /// Zigzag encodes `self` at its own width, then losslessly widens the
+    /// result into `U`.
+    ///
+    /// This is useful for wire formats with a fixed, wider field size. The
+    /// encoding is always computed at the width of `Self`; widening `self`
+    /// first and then encoding it happens to give the same result, but
+    /// narrowing never does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zigzag::ZigZag;
+    ///
+    /// assert_eq!((-1i16).zigzag_encode_as::<u64>(), 1u64);
+    /// assert_eq!(i16::MIN.zigzag_encode_as::<u64>(), 65535u64);
+    /// assert_eq!(i16::MAX.zigzag_encode_as::<u32>(), 65534u32);
+    /// ```
+    #[inline]
+    fn zigzag_encode_as<U>(self) -> U
+    where
+        U: From<Self::Unsigned>,
+    {
+        U::from(self.zigzag_encode())
+    }
+
+    /// Zigzag encodes `self` at its own width, then narrows the result into
+    /// `U`.
+    ///
+    /// Narrowing the encoded value succeeds exactly when `self` fits in the
+    /// signed counterpart of `U`, and gives the same result as narrowing
+    /// `self` first and then encoding it. Casting `self` to a narrower type
+    /// before encoding would instead silently truncate out-of-range values.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OutOfRange`] if the encoded value doesn't fit in `U`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zigzag::{OutOfRange, ZigZag};
+    ///
+    /// assert_eq!((-3i64).zigzag_encode_into::<u16>(), Ok(5u16));
+    /// assert_eq!(i64::from(i16::MIN).zigzag_encode_into::<u16>(), Ok(u16::MAX));
+    /// assert_eq!(40_000i64.zigzag_encode_into::<u16>(), Err(OutOfRange));
+    /// ```
+    #[inline]
+    fn zigzag_encode_into<U>(self) -> Result<U, OutOfRange>
+    where
+        U: TryFrom<Self::Unsigned>,
+    {
+        U::try_from(self.zigzag_encode()).map_err(|_| OutOfRange)
+    }
+}
+
+/// A [`ZigZag`] type which can represent zero, giving the encoding of zero as
+/// a constant.
+///
+/// This is implemented for every [`ZigZag`] type except the non-zero
+/// integers.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::{ZigZag, ZigZagZero};
+///
+/// fn count_zeros<T: ZigZagZero>(encoded: &[T::Unsigned]) -> usize
+/// where
+///     T::Unsigned: PartialEq,
+/// {
+///     encoded.iter().filter(|&&x| x == T::ENCODED_ZERO).count()
+/// }
+///
+/// assert_eq!(count_zeros::<i32>(&[0, 1, 0, 2]), 2);
+/// ```
+pub trait ZigZagZero: ZigZag {
+    /// The encoding of `0`, which is `0` at every width.
+    const ENCODED_ZERO: Self::Unsigned;
+}
+
+/// The counterpart of [`ZigZag`], tying an unsigned integer type to the
+/// signed type it zigzag decodes to.
+///
+/// Together, the two traits let generic code name the other half of a pair
+/// from either side: [`ZigZagEncode::Output`] and [`ZigZagDecode::Output`]
+/// only lead one way. They are implemented for the types whose encoding
+/// decodes back to the same type, and are effectively sealed along with
+/// [`ZigZagEncode`] and [`ZigZagDecode`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::{ZigZagDecode, ZigZagUnsigned};
+///
+/// fn decode_all<U: ZigZagUnsigned>(values: &[U]) -> Vec<U::Signed> {
+///     values.iter().map(|&x| x.zigzag_decode()).collect()
+/// }
+///
+/// assert_eq!(decode_all(&[1u16, 2]), [-1i16, 1]);
+/// ```
+pub trait ZigZagUnsigned: Copy + ZigZagDecode<Output = Self::Signed> {
+    /// The signed type that `Self` zigzag decodes to.
+    type Signed: ZigZag<Unsigned = Self>;
+
+    /// Zigzag decodes `self` at its own width, then losslessly widens the
+    /// result into `S`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zigzag::ZigZagUnsigned;
+    ///
+    /// assert_eq!(u16::MAX.zigzag_decode_as::<i64>(), -32768i64);
+    /// assert_eq!(1u8.zigzag_decode_as::<i32>(), -1i32);
+    /// ```
+    #[inline]
+    fn zigzag_decode_as<S>(self) -> S
+    where
+        S: From<Self::Signed>,
+    {
+        S::from(self.zigzag_decode())
+    }
+
+    /// Zigzag decodes `self` at its own width, then narrows the result into
+    /// `S`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OutOfRange`] if the decoded value doesn't fit in `S`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zigzag::{OutOfRange, ZigZagUnsigned};
+    ///
+    /// assert_eq!(5u64.zigzag_decode_into::<i16>(), Ok(-3i16));
+    /// assert_eq!(80_000u64.zigzag_decode_into::<i16>(), Err(OutOfRange));
+    /// ```
+    #[inline]
+    fn zigzag_decode_into<S>(self) -> Result<S, OutOfRange>
+    where
+        S: TryFrom<Self::Signed>,
+    {
+        S::try_from(self.zigzag_decode()).map_err(|_| OutOfRange)
+    }
+}
+
+/// Zigzag encodes `x`, inferring the type of the result from the type of
+/// `x`.
+///
+/// This is [`ZigZagEncode::zigzag_encode`] as a free function, which reads
+/// better in iterator adapters such as `.map(zigzag::encode)`.
+///
+/// # Examples
+///
+/// ```
+/// let encoded = zigzag::encode(-2i16);
+/// assert_eq!(encoded, 3u16);
+/// assert_eq!(zigzag::encode(i64::MIN), u64::MAX);
+/// ```
+#[inline]
+pub fn encode<T: ZigZagEncode>(x: T) -> T::Output {
+    x.zigzag_encode()
+}
+
+/// Zigzag decodes `x`, inferring the type of the result from the type of
+/// `x`.
+///
+/// This is the inverse of [`encode`].
+///
+/// # Examples
+///
+/// ```
+/// let decoded = zigzag::decode(3u16);
+/// assert_eq!(decoded, -2i16);
+/// assert_eq!(zigzag::decode(zigzag::encode(-7i128)), -7);
+/// ```
+#[inline]
+pub fn decode<U: ZigZagDecode>(x: U) -> U::Output {
+    x.zigzag_decode()
+}
+
+macro_rules! impl_zigzag {
+    ($signed:ty, $unsigned:ty) => {
+        impl ZigZag for $signed {
+            type Unsigned = $unsigned;
+            const BITS: u32 = type_bits::<$signed>() as u32;
+            const ENCODED_NEG_ONE: $unsigned = 1;
+        }
+
+        impl ZigZagZero for $signed {
+            const ENCODED_ZERO: $unsigned = 0;
+        }
+
+        impl ZigZagUnsigned for $unsigned {
+            type Signed = $signed;
+        }
+    };
+}
+
+impl_zigzag!(i8, u8);
+impl_zigzag!(i16, u16);
+impl_zigzag!(i32, u32);
+impl_zigzag!(i64, u64);
+impl_zigzag!(i128, u128);
+impl_zigzag!(isize, usize);
+
+/// Zigzag encoding for types defined outside of this crate.
+///
+/// [`ZigZagEncode`] and [`ZigZagDecode`] are sealed, so a newtype such as
+/// `struct Meters(i32)` can't implement them. This trait is an escape hatch
+/// that anyone can implement, usually with [`impl_zigzag_newtype!`].
+///
+/// The trade-off is that implementing `ZigZagExt` doesn't make a type work
+/// with the rest of this crate: the iterator adapters, the [`ZigZag`] trait
+/// and everything built on top of it are bounded by the sealed traits, so
+/// generic code has to unwrap the newtype first.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::ZigZagExt;
+///
+/// struct Meters(i32);
+///
+/// impl ZigZagExt for Meters {
+///     type Encoded = u32;
+///
+///     fn zigzag_encode(self) -> u32 {
+///         zigzag::ZigZagEncode::zigzag_encode(self.0)
+///     }
+///
+///     fn zigzag_decode(encoded: u32) -> Self {
+///         Meters(zigzag::ZigZagDecode::zigzag_decode(encoded))
+///     }
+/// }
+///
+/// assert_eq!(Meters(-1).zigzag_encode(), 1);
+/// assert_eq!(Meters::zigzag_decode(3).0, -2);
+/// ```
+pub trait ZigZagExt: Sized {
+    /// The type that `Self` zigzag encodes to.
+    type Encoded;
+
+    /// Zigzag encodes `self`.
+    fn zigzag_encode(self) -> Self::Encoded;
+
+    /// Decodes a value previously returned by [`zigzag_encode`].
+    ///
+    /// [`zigzag_encode`]: ZigZagExt::zigzag_encode
+    fn zigzag_decode(encoded: Self::Encoded) -> Self;
+}
+
+/// Implements [`ZigZagExt`] for a tuple struct wrapping a signed integer.
+///
+/// The first argument is the newtype, which must have a single field holding
+/// the second argument, the signed integer type. The third argument is the
+/// unsigned type the newtype encodes to. Both conversions delegate to the
+/// wrapped integer.
+///
+/// See [`ZigZagExt`] for how this differs from the sealed traits.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::{impl_zigzag_newtype, ZigZagExt};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Meters(i32);
+///
+/// impl_zigzag_newtype!(Meters, i32, u32);
+///
+/// assert_eq!(Meters(-3).zigzag_encode(), 5u32);
+/// assert_eq!(Meters::zigzag_decode(5), Meters(-3));
+/// ```
+#[macro_export]
+macro_rules! impl_zigzag_newtype {
+    ($name:ty, $signed:ty, $unsigned:ty) => {
+        impl $crate::ZigZagExt for $name {
+            type Encoded = $unsigned;
+
+            #[inline]
+            fn zigzag_encode(self) -> $unsigned {
+                <$signed as $crate::ZigZagEncode>::zigzag_encode(self.0)
+            }
+
+            #[inline]
+            fn zigzag_decode(encoded: $unsigned) -> Self {
+                Self(<$unsigned as $crate::ZigZagDecode>::zigzag_decode(encoded))
+            }
+        }
+    };
+}
@@ -0,0 +1,140 @@
+//! Lookup tables holding the zigzag encoding of every 8-bit integer.
+//!
+//! The tables are built at compile time and are always available. With the
+//! `lut` feature enabled, the `i8` and `u8` implementations of
+//! [`ZigZagEncode`] and [`ZigZagDecode`] use them instead of computing the
+//! formula, which is faster on some microcontrollers without a barrel
+//! shifter.
+//!
+//! With the `lut16` feature enabled, there is also a 128 KiB table holding
+//! the decoding of every `u16`, which the `u16` implementation of
+//! [`ZigZagDecode`] uses. It suits devices with ample flash but slow shifts.
+//!
+//! [`ZigZagEncode`]: crate::ZigZagEncode
+//! [`ZigZagDecode`]: crate::ZigZagDecode
+
+/// The zigzag encoding of every `i8`, indexed by the value's bits as a `u8`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::lut::ZIGZAG_ENCODE_I8;
+///
+/// static TABLE: [u8; 256] = ZIGZAG_ENCODE_I8;
+///
+/// assert_eq!(TABLE[(-1i8) as u8 as usize], 1);
+/// assert_eq!(TABLE[i8::MIN as u8 as usize], u8::MAX);
+/// ```
+pub const ZIGZAG_ENCODE_I8: [u8; 256] = encode_table();
+
+/// The zigzag decoding of every `u8`, indexed by the value.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::lut::ZIGZAG_DECODE_U8;
+///
+/// static TABLE: [i8; 256] = ZIGZAG_DECODE_U8;
+///
+/// assert_eq!(TABLE[1], -1);
+/// assert_eq!(TABLE[u8::MAX as usize], i8::MIN);
+/// ```
+pub const ZIGZAG_DECODE_U8: [i8; 256] = decode_table();
+
+/// The zigzag decoding of every `u16`, indexed by the value.
+///
+/// This is a `static` rather than a `const`, so that the 128 KiB table is
+/// stored once instead of being copied into every use.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::lut::ZIGZAG_DECODE_U16;
+///
+/// assert_eq!(ZIGZAG_DECODE_U16[1], -1);
+/// assert_eq!(ZIGZAG_DECODE_U16[u16::MAX as usize], i16::MIN);
+/// ```
+#[cfg(feature = "lut16")]
+pub static ZIGZAG_DECODE_U16: [i16; 65536] = decode_table_u16();
+
+const fn encode_table() -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < table.len() {
+        let x = i as u8 as i8;
+        table[i] = ((x >> 7) ^ (x << 1)) as u8;
+        i += 1;
+    }
+    table
+}
+
+const fn decode_table() -> [i8; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < table.len() {
+        let x = i as u8;
+        table[i] = (x >> 1) as i8 ^ -((x & 1) as i8);
+        i += 1;
+    }
+    table
+}
+
+#[cfg(feature = "lut16")]
+const fn decode_table_u16() -> [i16; 65536] {
+    let mut table = [0; 65536];
+    let mut i = 0;
+    while i < table.len() {
+        let x = i as u16 as i16;
+        table[((x >> 15) ^ (x << 1)) as u16 as usize] = x;
+        i += 1;
+    }
+
+    // The table was built by inverting the encoding formula, so checking it
+    // against the decoding formula, while the static is evaluated at compile
+    // time, verifies that the two are a bijection.
+    let mut i = 0;
+    while i < table.len() {
+        let x = i as u16;
+        assert!(table[i] == (x >> 1) as i16 ^ -((x & 1) as i16));
+        i += 1;
+    }
+    table
+}
+
+#[cfg(feature = "lut")]
+impl crate::ZigZagEncode for i8 {
+    type Output = u8;
+
+    #[inline]
+    fn zigzag_encode(self) -> u8 {
+        ZIGZAG_ENCODE_I8[self as u8 as usize]
+    }
+}
+
+#[cfg(feature = "lut")]
+impl crate::ZigZagDecode for u8 {
+    type Output = i8;
+
+    #[inline]
+    fn zigzag_decode(self) -> i8 {
+        ZIGZAG_DECODE_U8[self as usize]
+    }
+}
+
+#[cfg(feature = "lut16")]
+impl crate::ZigZagDecode for u16 {
+    type Output = i16;
+
+    #[inline]
+    fn zigzag_decode(self) -> i16 {
+        ZIGZAG_DECODE_U16[self as usize]
+    }
+
+    // Single values go through the table, but whole slices are still faster
+    // with the vector kernels.
+    #[cfg(feature = "fast")]
+    #[inline]
+    fn decode_slice(src: &[u16], dst: &mut [i16]) {
+        crate::fast::decode_u16(src, dst)
+    }
+}
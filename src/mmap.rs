@@ -0,0 +1,103 @@
+//! Reading zigzag-encoded varints straight out of a memory-mapped file.
+//!
+//! This suits read-heavy workloads over large on-disk columns: the operating
+//! system pages the file in as it's read, so it never has to fit in memory.
+
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::varint::{VarintCursor, ZigZagVarintIter, CONTINUATION_BIT};
+
+/// A memory-mapped file holding a stream of zigzag-encoded varints, such as
+/// one written by [`zigzag_encode_all`](crate::varint::zigzag_encode_all).
+///
+/// # Examples
+///
+/// ```no_run
+/// use zigzag::mmap::MappedZigZagBlock;
+///
+/// // SAFETY: nothing else modifies the file while it's mapped.
+/// let block = unsafe { MappedZigZagBlock::open("column.bin")? };
+/// let sum: i64 = block.iter().map(|x| x.unwrap()).sum();
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct MappedZigZagBlock {
+    map: Mmap,
+}
+
+impl MappedZigZagBlock {
+    /// Memory maps the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while opening or mapping the file.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, by this or any other process, while it
+    /// is mapped. See [`Mmap::map`] for details.
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Mmap::map(&file).map(MappedZigZagBlock::from_mmap)
+    }
+
+    /// Wraps a file which has already been memory mapped.
+    #[inline]
+    pub fn from_mmap(map: Mmap) -> Self {
+        MappedZigZagBlock { map }
+    }
+
+    /// Returns the mapped bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.map
+    }
+
+    /// Returns an iterator which lazily decodes the varints in the file.
+    ///
+    /// If the file ends partway through a varint, the last item is an error.
+    #[inline]
+    pub fn iter(&self) -> ZigZagVarintIter<'_> {
+        ZigZagVarintIter::new(&self.map)
+    }
+
+    /// Returns a cursor which lazily decodes the varints in the file, and
+    /// which can resume after a malformed one.
+    #[inline]
+    pub fn cursor(&self) -> VarintCursor<'_> {
+        VarintCursor::new(&self.map)
+    }
+
+    /// Returns the number of items [`iter`](Self::iter) yields for a
+    /// well-formed file.
+    ///
+    /// This counts the bytes which end a varint, plus one for a truncated
+    /// final varint, so it takes time proportional to the size of the file.
+    /// For a file with an overlong varint, the iterator stops early at the
+    /// resulting error, so it yields fewer items than this.
+    pub fn len_hint(&self) -> usize {
+        let ends = self
+            .map
+            .iter()
+            .filter(|&&byte| byte & CONTINUATION_BIT == 0)
+            .count();
+        match self.map.last() {
+            Some(&last) if last & CONTINUATION_BIT != 0 => ends + 1,
+            _ => ends,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a MappedZigZagBlock {
+    type Item = <ZigZagVarintIter<'a> as Iterator>::Item;
+    type IntoIter = ZigZagVarintIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,59 @@
+//! Implementations of [`ZigZagEncode`] and [`ZigZagDecode`] for the non-zero
+//! integer types.
+//!
+//! Zigzag encoding maps zero, and only zero, to zero. Encoding or decoding a
+//! non-zero value therefore always produces a non-zero value, so the result
+//! can be wrapped without checking it again.
+
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+use crate::{private, ZigZag, ZigZagDecode, ZigZagEncode, ZigZagUnsigned};
+
+macro_rules! impl_nonzero {
+    ($signed:ty, $unsigned:ty) => {
+        impl private::Sealed for $signed {}
+        impl private::Sealed for $unsigned {}
+
+        impl ZigZagEncode for $signed {
+            type Output = $unsigned;
+
+            #[inline]
+            fn zigzag_encode(self) -> $unsigned {
+                // SAFETY: `self` is non-zero and zigzag encoding only maps
+                // zero to zero.
+                unsafe { <$unsigned>::new_unchecked(self.get().zigzag_encode()) }
+            }
+        }
+
+        impl ZigZagDecode for $unsigned {
+            type Output = $signed;
+
+            #[inline]
+            fn zigzag_decode(self) -> $signed {
+                // SAFETY: `self` is non-zero and zigzag decoding only maps
+                // zero to zero.
+                unsafe { <$signed>::new_unchecked(self.get().zigzag_decode()) }
+            }
+        }
+
+        impl ZigZag for $signed {
+            type Unsigned = $unsigned;
+            const BITS: u32 = <$signed>::BITS;
+            const ENCODED_NEG_ONE: $unsigned = <$unsigned>::MIN;
+        }
+
+        impl ZigZagUnsigned for $unsigned {
+            type Signed = $signed;
+        }
+    };
+}
+
+impl_nonzero!(NonZeroI8, NonZeroU8);
+impl_nonzero!(NonZeroI16, NonZeroU16);
+impl_nonzero!(NonZeroI32, NonZeroU32);
+impl_nonzero!(NonZeroI64, NonZeroU64);
+impl_nonzero!(NonZeroI128, NonZeroU128);
+impl_nonzero!(NonZeroIsize, NonZeroUsize);
@@ -0,0 +1,70 @@
+//! Implementations of [`ZigZagEncode`] and [`ZigZagDecode`] for the
+//! arbitrary-precision integers of the [`num-bigint`](::num_bigint) crate.
+//!
+//! A [`BigInt`] encodes to the [`BigUint`] `2 * x` if it's non-negative and
+//! `2 * |x| - 1` if it's negative, the same mapping as the primitive
+//! integers, so a value which fits in `i128` encodes to the same number as
+//! the `i128` does. Each direction is implemented both for values and for
+//! references, which avoids a clone when the input is still needed.
+
+use ::num_bigint::{BigInt, BigUint, Sign};
+
+use crate::{private, ZigZagDecode, ZigZagEncode};
+
+impl private::Sealed for BigInt {}
+impl private::Sealed for BigUint {}
+impl private::Sealed for &BigInt {}
+impl private::Sealed for &BigUint {}
+
+impl ZigZagEncode for BigInt {
+    type Output = BigUint;
+
+    #[inline]
+    fn zigzag_encode(self) -> BigUint {
+        let (sign, magnitude) = self.into_parts();
+        let doubled = magnitude << 1u32;
+        // `2 * |x| - 1` can't underflow, since `|x|` is at least one.
+        if sign == Sign::Minus {
+            doubled - 1u32
+        } else {
+            doubled
+        }
+    }
+}
+
+impl ZigZagEncode for &BigInt {
+    type Output = BigUint;
+
+    #[inline]
+    fn zigzag_encode(self) -> BigUint {
+        let doubled = self.magnitude() << 1u32;
+        if self.sign() == Sign::Minus {
+            doubled - 1u32
+        } else {
+            doubled
+        }
+    }
+}
+
+impl ZigZagDecode for BigUint {
+    type Output = BigInt;
+
+    #[inline]
+    fn zigzag_decode(self) -> BigInt {
+        (&self).zigzag_decode()
+    }
+}
+
+impl ZigZagDecode for &BigUint {
+    type Output = BigInt;
+
+    #[inline]
+    fn zigzag_decode(self) -> BigInt {
+        // An odd encoding is `2 * |x| - 1`, so `|x|` is half of one more.
+        if self.bit(0) {
+            -BigInt::from((self + 1u32) >> 1u32)
+        } else {
+            BigInt::from(self >> 1u32)
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Offset-binary encoding, which preserves the order of signed integers.
+//!
+//! Zigzag encoding orders values by magnitude—`0, -1, 1, -2, 2, ...`—which
+//! suits variable-length encodings but not keys that must sort in numeric
+//! order, such as those of a sorted key-value store. Offset binary instead
+//! adds `2^(n-1)` to each `n`-bit value, which amounts to flipping its sign
+//! bit. That maps `iN::MIN..=iN::MAX` onto `0..=uN::MAX` in order, so the
+//! encoded values, and their big-endian bytes, sort like the signed ones.
+//!
+//! The [`OrderedEncode`] and [`OrderedDecode`] traits have the same shape as
+//! [`ZigZagEncode`] and [`ZigZagDecode`], and are likewise sealed.
+//!
+//! # Examples
+//!
+//! ```
+//! use zigzag::offset::{OrderedDecode, OrderedEncode};
+//!
+//! let mut values = [2i16, -3, 0, i16::MIN, -1, i16::MAX];
+//! let mut keys: Vec<[u8; 2]> = values
+//!     .iter()
+//!     .map(|&x| OrderedEncode::<u16>::ordered_encode(x).to_be_bytes())
+//!     .collect();
+//! keys.sort();
+//! values.sort();
+//!
+//! let decoded: Vec<i16> = keys
+//!     .iter()
+//!     .map(|&key| u16::from_be_bytes(key).ordered_decode())
+//!     .collect();
+//! assert_eq!(decoded, values);
+//! ```
+//!
+//! [`ZigZagEncode`]: crate::ZigZagEncode
+//! [`ZigZagDecode`]: crate::ZigZagDecode
+
+use crate::private;
+
+/// A trait extending signed integer types with their offset-binary
+/// encoding.
+///
+/// This trait is sealed.
+pub trait OrderedEncode<U>: private::Sealed {
+    /// Encodes `self` by flipping its sign bit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zigzag::offset::OrderedEncode;
+    ///
+    /// assert_eq!(i8::MIN.ordered_encode(), 0u8);
+    /// assert_eq!((-1i8).ordered_encode(), 0x7fu8);
+    /// assert_eq!(0i8.ordered_encode(), 0x80u8);
+    /// assert_eq!(i8::MAX.ordered_encode(), u8::MAX);
+    /// ```
+    fn ordered_encode(self) -> U;
+}
+
+/// A trait extending unsigned integer types with the ability to decode their
+/// offset-binary encoding.
+///
+/// This trait is sealed.
+pub trait OrderedDecode<S>: private::Sealed {
+    /// Decodes `self` by flipping its most significant bit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zigzag::offset::OrderedDecode;
+    ///
+    /// assert_eq!(0u8.ordered_decode(), i8::MIN);
+    /// assert_eq!(0x80u8.ordered_decode(), 0i8);
+    /// ```
+    fn ordered_decode(self) -> S;
+}
+
+macro_rules! impl_ordered {
+    ($($signed:ty, $unsigned:ty;)*) => {
+        $(
+            impl OrderedEncode<$unsigned> for $signed {
+                #[inline]
+                fn ordered_encode(self) -> $unsigned {
+                    self as $unsigned ^ 1 << (<$unsigned>::BITS - 1)
+                }
+            }
+
+            impl OrderedDecode<$signed> for $unsigned {
+                #[inline]
+                fn ordered_decode(self) -> $signed {
+                    (self ^ 1 << (<$unsigned>::BITS - 1)) as $signed
+                }
+            }
+        )*
+    };
+}
+
+impl_ordered! {
+    i8, u8;
+    i16, u16;
+    i32, u32;
+    i64, u64;
+    i128, u128;
+    isize, usize;
+}
@@ -0,0 +1,395 @@
+//! Parallel bulk encoding and decoding built on
+//! [`rayon`](https://docs.rs/rayon).
+//!
+//! Every element is encoded independently of the others, so fixed-width
+//! output can be split across threads at any point. Varint output is
+//! variable-length, which means each thread's write offset depends on the
+//! lengths of all the values before it; [`par_zigzag_varint_encode`] and
+//! [`par_delta_zigzag_encode`] therefore make a separate pass which computes
+//! those lengths first. [`par_delta_zigzag_decode`] splits its input after
+//! any byte which ends a varint, and adds up each chunk's deltas with a
+//! prefix sum over the chunks.
+//!
+//! With the `rayon` feature disabled, or for inputs shorter than
+//! [`MIN_PARALLEL_LEN`], every function runs its sequential counterpart on
+//! the calling thread instead, so callers needn't gate their own code on the
+//! feature. The output is the same either way.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "rayon")]
+use core::mem;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+#[cfg(all(feature = "rayon", not(feature = "simd")))]
+use crate::varint::zigzag_varint_len;
+#[cfg(feature = "rayon")]
+use crate::varint::{encode_uvarint, read_zigzag_varint, CONTINUATION_BIT};
+#[cfg(feature = "alloc")]
+use crate::VarintError;
+use crate::{ZigZagDecode, ZigZagEncode};
+
+/// The length below which inputs are handled on the calling thread, where
+/// the overhead of splitting them up would outweigh any speedup.
+pub const MIN_PARALLEL_LEN: usize = 1 << 14;
+
+/// The number of values, or bytes of varints, each task handles.
+#[cfg(feature = "rayon")]
+const CHUNK_LEN: usize = 4096;
+
+/// Zigzag encodes every value of `src` into the corresponding slot of `dst`,
+/// using the rayon thread pool.
+///
+/// Each task encodes its chunk with
+/// [`zigzag_encode_slice`](crate::slice::zigzag_encode_slice), so the `fast`
+/// feature speeds up every thread. The output is identical to encoding each
+/// value sequentially.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::parallel::par_zigzag_encode_slice;
+///
+/// let src = [0i32, -1, 1, i32::MIN];
+/// let mut dst = [0u32; 4];
+/// par_zigzag_encode_slice(&src, &mut dst);
+/// assert_eq!(dst, [0, 1, 2, u32::MAX]);
+/// ```
+pub fn par_zigzag_encode_slice<S, U>(src: &[S], dst: &mut [U])
+where
+    S: ZigZagEncode<Output = U> + Copy + Sync,
+    U: Send,
+{
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination slices have different lengths"
+    );
+    #[cfg(feature = "rayon")]
+    {
+        if src.len() >= MIN_PARALLEL_LEN {
+            dst.par_chunks_mut(CHUNK_LEN)
+                .zip(src.par_chunks(CHUNK_LEN))
+                .for_each(|(d, s)| S::encode_slice(s, d));
+            return;
+        }
+    }
+    S::encode_slice(src, dst);
+}
+
+/// Zigzag decodes every value of `src` into the corresponding slot of `dst`,
+/// using the rayon thread pool.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::parallel::par_zigzag_decode_slice;
+///
+/// let src = [0u32, 1, 2, u32::MAX];
+/// let mut dst = [0i32; 4];
+/// par_zigzag_decode_slice(&src, &mut dst);
+/// assert_eq!(dst, [0, -1, 1, i32::MIN]);
+/// ```
+pub fn par_zigzag_decode_slice<U, S>(src: &[U], dst: &mut [S])
+where
+    U: ZigZagDecode<Output = S> + Copy + Sync,
+    S: Send,
+{
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination slices have different lengths"
+    );
+    #[cfg(feature = "rayon")]
+    {
+        if src.len() >= MIN_PARALLEL_LEN {
+            dst.par_chunks_mut(CHUNK_LEN)
+                .zip(src.par_chunks(CHUNK_LEN))
+                .for_each(|(d, s)| U::decode_slice(s, d));
+            return;
+        }
+    }
+    U::decode_slice(src, dst);
+}
+
+/// A shorter name for [`par_zigzag_encode_slice`].
+pub use par_zigzag_encode_slice as par_encode_slice;
+
+/// A shorter name for [`par_zigzag_decode_slice`].
+pub use par_zigzag_decode_slice as par_decode_slice;
+
+/// Returns the total zigzag-encoded varint length of `chunk`.
+#[cfg(all(feature = "rayon", not(feature = "simd")))]
+fn chunk_varint_len(chunk: &[i64]) -> usize {
+    chunk.iter().map(|&x| zigzag_varint_len(x)).sum()
+}
+
+/// Returns the total zigzag-encoded varint length of `chunk`, which must hold
+/// at most [`CHUNK_LEN`] values.
+#[cfg(all(feature = "rayon", feature = "simd"))]
+fn chunk_varint_len(chunk: &[i64]) -> usize {
+    let mut lens = [0u8; CHUNK_LEN];
+    let lens = &mut lens[..chunk.len()];
+    crate::simd::zigzag_varint_len_simd(chunk, lens);
+    lens.iter().map(|&len| usize::from(len)).sum()
+}
+
+/// Appends the zigzag-encoded varints of the values `map` produces for every
+/// chunk of `src` to `out`, after computing each chunk's encoded length with
+/// `chunk_len`.
+///
+/// Both are given the index in `src` at which the chunk starts and the chunk
+/// itself, so they can look back at the value before the chunk.
+#[cfg(feature = "rayon")]
+fn par_write_varints<'a, L, M, I>(src: &'a [i64], out: &mut Vec<u8>, chunk_len: L, map: M)
+where
+    L: Fn(usize, &'a [i64]) -> usize + Sync,
+    M: Fn(usize, &'a [i64]) -> I + Sync,
+    I: Iterator<Item = i64>,
+{
+    let chunk_lens: Vec<usize> = src
+        .par_chunks(CHUNK_LEN)
+        .enumerate()
+        .map(|(i, chunk)| chunk_len(i * CHUNK_LEN, chunk))
+        .collect();
+
+    let start = out.len();
+    out.resize(start + chunk_lens.iter().sum::<usize>(), 0);
+    let mut slots = Vec::with_capacity(chunk_lens.len());
+    let mut rest = &mut out[start..];
+    for &len in &chunk_lens {
+        let (slot, tail) = mem::take(&mut rest).split_at_mut(len);
+        slots.push(slot);
+        rest = tail;
+    }
+
+    slots
+        .into_par_iter()
+        .zip(src.par_chunks(CHUNK_LEN).enumerate())
+        .for_each(|(slot, (i, chunk))| {
+            let mut offset = 0;
+            for x in map(i * CHUNK_LEN, chunk) {
+                offset += encode_uvarint(x.zigzag_encode(), &mut slot[offset..]);
+            }
+        });
+}
+
+/// Encodes every value of `src` as a zigzag-encoded varint, one after the
+/// other, using the rayon thread pool.
+///
+/// The input is split into chunks whose encoded lengths are computed in
+/// parallel. A prefix sum over those lengths gives every chunk its exact
+/// offset into the output buffer, which is allocated once and then filled in
+/// parallel. The output is byte-for-byte identical to
+/// [`zigzag_encode_all`](crate::varint::zigzag_encode_all).
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::parallel::par_zigzag_varint_encode;
+///
+/// assert_eq!(par_zigzag_varint_encode(&[0, -1, 150]), [0x00, 0x01, 0xac, 0x02]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn par_zigzag_varint_encode(src: &[i64]) -> Vec<u8> {
+    #[cfg(feature = "rayon")]
+    {
+        if src.len() >= MIN_PARALLEL_LEN {
+            let mut out = Vec::new();
+            par_write_varints(
+                src,
+                &mut out,
+                |_, chunk| chunk_varint_len(chunk),
+                |_, chunk| chunk.iter().copied(),
+            );
+            return out;
+        }
+    }
+    crate::varint::zigzag_encode_all(src)
+}
+
+/// Returns the deltas of `chunk`, which starts at index `start` of `values`.
+#[cfg(feature = "rayon")]
+fn chunk_deltas<'a>(
+    values: &'a [i64],
+    start: usize,
+    chunk: &'a [i64],
+) -> impl Iterator<Item = i64> + 'a {
+    let mut prev = if start == 0 { 0 } else { values[start - 1] };
+    chunk.iter().map(move |&value| {
+        let delta = value.wrapping_sub(prev);
+        prev = value;
+        delta
+    })
+}
+
+/// Appends the delta encoding of `values` to `out`, using the rayon thread
+/// pool.
+///
+/// Every delta only depends on two neighbouring values, so the deltas of
+/// each chunk are computed and encoded in parallel, the same way as by
+/// [`par_zigzag_varint_encode`]. The output is byte-for-byte identical to
+/// [`delta_zigzag_encode`](crate::delta::delta_zigzag_encode).
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::delta::delta_zigzag_decode;
+/// use zigzag::parallel::par_delta_zigzag_encode;
+///
+/// let values: Vec<i64> = (0..100_000).map(|i| 1_000_000 + i * 3 - i % 7).collect();
+/// let mut out = Vec::new();
+/// par_delta_zigzag_encode(&values, &mut out);
+/// assert_eq!(delta_zigzag_decode(&out), Ok(values));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn par_delta_zigzag_encode(values: &[i64], out: &mut Vec<u8>) {
+    #[cfg(feature = "rayon")]
+    {
+        if values.len() >= MIN_PARALLEL_LEN {
+            par_write_varints(
+                values,
+                out,
+                |start, chunk| {
+                    chunk_deltas(values, start, chunk)
+                        .map(crate::varint::zigzag_varint_len)
+                        .sum()
+                },
+                |start, chunk| chunk_deltas(values, start, chunk),
+            );
+            return;
+        }
+    }
+    crate::delta::delta_zigzag_encode(values, out);
+}
+
+/// Splits `input` into chunks of about [`CHUNK_LEN`] bytes, each of which
+/// starts at the beginning of a varint.
+///
+/// Every byte without the continuation bit ends a varint, so the input can
+/// be split after any of them without reading the varints before it.
+#[cfg(feature = "rayon")]
+fn varint_chunks(input: &[u8]) -> Vec<&[u8]> {
+    let mut chunks = Vec::with_capacity(input.len() / CHUNK_LEN + 1);
+    let mut rest = input;
+    while rest.len() > CHUNK_LEN {
+        let end = rest[CHUNK_LEN..]
+            .iter()
+            .position(|&byte| byte & CONTINUATION_BIT == 0)
+            .map_or(rest.len(), |i| CHUNK_LEN + i + 1);
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+/// Decodes the deltas in `chunk` into `out`, each added to all those before
+/// it in the chunk, and returns their sum.
+///
+/// `out` must have a slot for every byte of `chunk` without the continuation
+/// bit, which is at least as many as there are varints.
+#[cfg(feature = "rayon")]
+fn decode_chunk_deltas(mut chunk: &[u8], out: &mut [i64]) -> Result<i64, VarintError> {
+    let mut sum = 0i64;
+    let mut i = 0;
+    while !chunk.is_empty() {
+        let (delta, len) = read_zigzag_varint(chunk)?;
+        chunk = &chunk[len..];
+        sum = sum.wrapping_add(delta);
+        out[i] = sum;
+        i += 1;
+    }
+    Ok(sum)
+}
+
+/// Decodes a delta-encoded stream into its absolute values, using the rayon
+/// thread pool.
+///
+/// The input is split into chunks which each start at a varint, and each
+/// chunk's deltas are decoded and summed in parallel. A prefix sum over the
+/// chunks' totals then gives every chunk the value before it, which is added
+/// to its values in parallel. The output, including the error for malformed
+/// input, is identical to that of
+/// [`delta_zigzag_decode`](crate::delta::delta_zigzag_decode).
+///
+/// # Errors
+///
+/// Returns an error if `input` doesn't consist entirely of valid varints.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::delta::delta_zigzag_encode;
+/// use zigzag::parallel::par_delta_zigzag_decode;
+///
+/// let values: Vec<i64> = (0..100_000).map(|i| 1_000_000 + i * 3 - i % 7).collect();
+/// let mut out = Vec::new();
+/// delta_zigzag_encode(&values, &mut out);
+/// assert_eq!(par_delta_zigzag_decode(&out), Ok(values));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn par_delta_zigzag_decode(input: &[u8]) -> Result<Vec<i64>, VarintError> {
+    #[cfg(feature = "rayon")]
+    {
+        if input.len() >= MIN_PARALLEL_LEN {
+            let chunks = varint_chunks(input);
+            let counts: Vec<usize> = chunks
+                .par_iter()
+                .map(|chunk| {
+                    chunk
+                        .iter()
+                        .filter(|&&byte| byte & CONTINUATION_BIT == 0)
+                        .count()
+                })
+                .collect();
+
+            let mut out = alloc::vec![0i64; counts.iter().sum()];
+            let mut slots = Vec::with_capacity(counts.len());
+            let mut rest = &mut out[..];
+            for &count in &counts {
+                let (slot, tail) = mem::take(&mut rest).split_at_mut(count);
+                slots.push(slot);
+                rest = tail;
+            }
+
+            let sums: Vec<Result<i64, VarintError>> = slots
+                .par_iter_mut()
+                .zip(chunks.par_iter())
+                .map(|(slot, chunk)| decode_chunk_deltas(chunk, slot))
+                .collect();
+            // Every chunk before the first malformed one is valid, so the
+            // sequential decoder would have reached the same error.
+            let mut offsets = Vec::with_capacity(sums.len());
+            let mut prev = 0i64;
+            for sum in sums {
+                offsets.push(prev);
+                prev = prev.wrapping_add(sum?);
+            }
+
+            slots
+                .into_par_iter()
+                .zip(offsets)
+                .for_each(|(slot, offset)| {
+                    for value in slot {
+                        *value = value.wrapping_add(offset);
+                    }
+                });
+            return Ok(out);
+        }
+    }
+    crate::delta::delta_zigzag_decode(input)
+}
@@ -0,0 +1,303 @@
+//! Reading and writing zigzag-encoded integers stored as prefix varints.
+//!
+//! A prefix varint stores the length of the whole varint in its first byte,
+//! instead of in a continuation bit on every byte. The number of leading one
+//! bits of the first byte is the number of bytes that follow it, from zero
+//! to eight. Below those, and the zero bit ending them, the first byte holds
+//! the least significant bits of the value, and the bytes that follow hold
+//! the rest in little-endian order. A first byte of `0xff` is followed by all
+//! 64 bits of the value.
+//!
+//! Every length up to eight bytes holds as many bits as the LEB128 varints of
+//! the [`varint`](crate::varint) module do, and all 64 bits fit in nine bytes
+//! rather than ten. Since a decoder learns the length from the first byte, it
+//! reads the rest with a single unconditional copy instead of testing every
+//! byte. This is the layout of `vu128`, among others.
+//!
+//! The functions of this module have the same names and signatures as those
+//! of the `varint` module, so switching between the two formats only takes
+//! changing an import.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use zigzag::prefix_varint::{read_zigzag_varint, write_zigzag_varint};
+//!
+//! let mut out = Vec::new();
+//! write_zigzag_varint(-1, &mut out);
+//! write_zigzag_varint(150, &mut out);
+//! assert_eq!(out, [0x01, 0b10_101100, 0x04]);
+//!
+//! assert_eq!(read_zigzag_varint(&out), Ok((-1, 1)));
+//! assert_eq!(read_zigzag_varint(&out[1..]), Ok((150, 2)));
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{DecodeError, VarintError, ZigZagDecode, ZigZagEncode};
+
+/// The maximum number of bytes a prefix varint encoding a 64-bit integer
+/// occupies.
+pub const MAX_VARINT_LEN: usize = 9;
+
+/// Returns the number of bytes `x` occupies as an unsigned prefix varint.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::prefix_varint::uvarint_len;
+///
+/// assert_eq!(uvarint_len(0), 1);
+/// assert_eq!(uvarint_len(127), 1);
+/// assert_eq!(uvarint_len(128), 2);
+/// assert_eq!(uvarint_len(1 << 56), 9);
+/// assert_eq!(uvarint_len(u64::MAX), 9);
+/// ```
+#[inline]
+pub const fn uvarint_len(x: u64) -> usize {
+    let bits = 64 - (x | 1).leading_zeros() as usize;
+    if bits > 56 {
+        MAX_VARINT_LEN
+    } else {
+        bits.div_ceil(7)
+    }
+}
+
+/// Returns the number of bytes `x` occupies as a zigzag-encoded prefix
+/// varint.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::prefix_varint::zigzag_varint_len;
+///
+/// assert_eq!(zigzag_varint_len(-64), 1);
+/// assert_eq!(zigzag_varint_len(64), 2);
+/// assert_eq!(zigzag_varint_len(i64::MIN), 9);
+/// ```
+#[inline]
+pub fn zigzag_varint_len(x: i64) -> usize {
+    uvarint_len(x.zigzag_encode())
+}
+
+/// Writes `x` as an unsigned prefix varint to the start of `buf`, which must
+/// be at least `uvarint_len(x)` bytes long, and returns the number of bytes
+/// written.
+#[inline]
+fn encode_uvarint(x: u64, buf: &mut [u8]) -> usize {
+    let len = uvarint_len(x);
+    if len == MAX_VARINT_LEN {
+        buf[0] = 0xff;
+        buf[1..MAX_VARINT_LEN].copy_from_slice(&x.to_le_bytes());
+        return len;
+    }
+    let extra = len - 1;
+    buf[0] = !(0xff >> extra) | (x as u8 & (0x7f >> extra));
+    buf[1..len].copy_from_slice(&(x >> (7 - extra)).to_le_bytes()[..extra]);
+    len
+}
+
+/// Appends `x` to `out` as an unsigned prefix varint and returns the number
+/// of bytes appended.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::prefix_varint::write_uvarint;
+///
+/// let mut out = Vec::new();
+/// assert_eq!(write_uvarint(150, &mut out), 2);
+/// assert_eq!(out, [0b10_010110, 0x02]);
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn write_uvarint(x: u64, out: &mut Vec<u8>) -> usize {
+    let mut buf = [0; MAX_VARINT_LEN];
+    let len = encode_uvarint(x, &mut buf);
+    out.extend_from_slice(&buf[..len]);
+    len
+}
+
+/// Appends `x` to `out` as a zigzag-encoded prefix varint and returns the
+/// number of bytes appended, which is always
+/// [`zigzag_varint_len(x)`](zigzag_varint_len).
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::prefix_varint::write_zigzag_varint;
+///
+/// let mut out = Vec::new();
+/// assert_eq!(write_zigzag_varint(i64::MIN, &mut out), 9);
+/// assert_eq!(out, [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn write_zigzag_varint(x: i64, out: &mut Vec<u8>) -> usize {
+    write_uvarint(x.zigzag_encode(), out)
+}
+
+/// Writes `x` as a zigzag-encoded prefix varint to the start of `out`.
+///
+/// Returns the number of bytes written, or `None`—leaving `out`
+/// untouched—if `out` is too short to hold the varint.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::prefix_varint::write_zigzag_varint_slice;
+///
+/// let mut buf = [0; 2];
+/// assert_eq!(write_zigzag_varint_slice(150, &mut buf), Some(2));
+/// assert_eq!(write_zigzag_varint_slice(-10_000, &mut buf), None);
+/// ```
+#[inline]
+pub fn write_zigzag_varint_slice(x: i64, out: &mut [u8]) -> Option<usize> {
+    let x = x.zigzag_encode();
+    if out.len() < uvarint_len(x) {
+        return None;
+    }
+    Some(encode_uvarint(x, out))
+}
+
+/// Encodes every value of `src` as a zigzag-encoded prefix varint, one after
+/// the other.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::prefix_varint::zigzag_encode_all;
+///
+/// assert_eq!(zigzag_encode_all(&[0, -1, 150]), [0x00, 0x01, 0xac, 0x04]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn zigzag_encode_all(src: &[i64]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(src.len());
+    for &x in src {
+        write_zigzag_varint(x, &mut out);
+    }
+    out
+}
+
+/// Reads an unsigned prefix varint from the start of `input`.
+///
+/// On success, returns the value and the number of bytes it occupied. Any
+/// bytes after the varint are ignored. Every sequence of bytes with enough
+/// of them for the length its first byte announces is a valid varint, so
+/// unlike LEB128 varints, prefix varints can't overflow.
+///
+/// # Errors
+///
+/// Returns [`VarintError::Truncated`] if `input` is shorter than the varint.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::prefix_varint::read_uvarint;
+/// use zigzag::VarintError;
+///
+/// assert_eq!(read_uvarint(&[0b10_010110, 0x02]), Ok((150, 2)));
+/// assert_eq!(read_uvarint(&[0xff; 9]), Ok((u64::MAX, 9)));
+/// assert_eq!(read_uvarint(&[0b110_00000, 0x00]), Err(VarintError::Truncated));
+/// ```
+pub fn read_uvarint(input: &[u8]) -> Result<(u64, usize), VarintError> {
+    let (&first, rest) = input.split_first().ok_or(VarintError::Truncated)?;
+    let extra = first.leading_ones() as usize;
+    let bytes = rest.get(..extra).ok_or(VarintError::Truncated)?;
+
+    let mut buf = [0; 8];
+    buf[..extra].copy_from_slice(bytes);
+    let high = u64::from_le_bytes(buf);
+    let value = if extra == 8 {
+        high
+    } else {
+        u64::from(first & (0x7f >> extra)) | high << (7 - extra)
+    };
+    Ok((value, 1 + extra))
+}
+
+/// Reads a zigzag-encoded prefix varint from the start of `input`.
+///
+/// On success, returns the decoded value and the number of bytes it occupied.
+/// Any bytes after the varint are ignored.
+///
+/// # Errors
+///
+/// See [`read_uvarint`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::prefix_varint::read_zigzag_varint;
+///
+/// assert_eq!(read_zigzag_varint(&[0x01]), Ok((-1, 1)));
+/// assert_eq!(read_zigzag_varint(&[0xac, 0x04, 0xff]), Ok((150, 2)));
+/// ```
+#[inline]
+pub fn read_zigzag_varint(input: &[u8]) -> Result<(i64, usize), VarintError> {
+    read_uvarint(input).map(|(raw, len)| (raw.zigzag_decode(), len))
+}
+
+/// Decodes a zigzag-encoded prefix varint which makes up the whole of
+/// `input`.
+///
+/// # Errors
+///
+/// Returns [`DecodeError::Truncated`], with the exact number of bytes
+/// missing, if `input` is shorter than the varint its first byte announces,
+/// and [`DecodeError::TrailingGarbage`] if `input` continues past the end of
+/// the varint.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::prefix_varint::decode_varint_from_slice;
+/// use zigzag::DecodeError;
+///
+/// assert_eq!(decode_varint_from_slice(&[0xac, 0x04]), Ok(150));
+/// assert_eq!(decode_varint_from_slice(&[0xff, 0xff]), Err(DecodeError::Truncated { needed: 7 }));
+/// assert_eq!(decode_varint_from_slice(&[0x01, 0x00]), Err(DecodeError::TrailingGarbage));
+/// ```
+pub fn decode_varint_from_slice(input: &[u8]) -> Result<i64, DecodeError> {
+    let first = *input.first().ok_or(DecodeError::Truncated { needed: 1 })?;
+    let len = 1 + first.leading_ones() as usize;
+    if input.len() < len {
+        return Err(DecodeError::Truncated {
+            needed: len - input.len(),
+        });
+    }
+    if input.len() > len {
+        return Err(DecodeError::TrailingGarbage);
+    }
+    Ok(read_zigzag_varint(input)?.0)
+}
+
+/// Decodes a stream of zigzag-encoded prefix varints, which must make up the
+/// whole of `input`.
+///
+/// # Errors
+///
+/// Returns [`VarintError::Truncated`] if the last varint is cut short.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::prefix_varint::{zigzag_decode_all, zigzag_encode_all};
+///
+/// let values = [0, -1, 150, i64::MIN];
+/// assert_eq!(zigzag_decode_all(&zigzag_encode_all(&values)), Ok(values.to_vec()));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn zigzag_decode_all(mut input: &[u8]) -> Result<Vec<i64>, VarintError> {
+    let mut values = Vec::new();
+    while !input.is_empty() {
+        let (value, len) = read_zigzag_varint(input)?;
+        values.push(value);
+        input = &input[len..];
+    }
+    Ok(values)
+}
@@ -0,0 +1,89 @@
+//! Frame-of-reference encoding of values relative to a shared base.
+//!
+//! A relative-encoded stream starts with the base value, followed by the
+//! difference between every value and the base. Each is stored as a
+//! zigzag-encoded varint, so values clustered around the base encode to few
+//! bytes each, whatever their order.
+//!
+//! Differences are computed with wrapping arithmetic, so every series of
+//! `i64`s round-trips with any base.
+
+use alloc::vec::Vec;
+
+use crate::varint::{read_zigzag_varint, write_zigzag_varint};
+use crate::VarintError;
+
+/// Appends `base`, followed by the difference between every value of
+/// `values` and `base`, to `out`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::relative::encode_relative;
+///
+/// let mut out = Vec::new();
+/// encode_relative(&[1000, 998, 1003], 1000, &mut out);
+/// assert_eq!(out, [0xd0, 0x0f, 0x00, 0x03, 0x06]);
+/// ```
+pub fn encode_relative(values: &[i64], base: i64, out: &mut Vec<u8>) {
+    write_zigzag_varint(base, out);
+    for &value in values {
+        write_zigzag_varint(value.wrapping_sub(base), out);
+    }
+}
+
+/// Decodes a relative-encoded stream into its absolute values.
+///
+/// # Errors
+///
+/// Returns [`VarintError::Truncated`] if `input` is empty, since every stream
+/// starts with its base. Otherwise, returns an error if `input` doesn't
+/// consist entirely of valid varints.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::relative::{decode_relative, encode_relative};
+///
+/// let values = [1000, 998, 1003, i64::MIN];
+/// let mut out = Vec::new();
+/// encode_relative(&values, 1000, &mut out);
+/// assert_eq!(decode_relative(&out), Ok(values.to_vec()));
+/// ```
+pub fn decode_relative(input: &[u8]) -> Result<Vec<i64>, VarintError> {
+    let (base, mut offset) = read_zigzag_varint(input)?;
+    let mut values = Vec::new();
+    while offset < input.len() {
+        let (delta, len) = read_zigzag_varint(&input[offset..])?;
+        values.push(base.wrapping_add(delta));
+        offset += len;
+    }
+    Ok(values)
+}
+
+/// Returns the median of `values`, or `0` if `values` is empty.
+///
+/// The median minimizes the sum of the absolute differences between the
+/// values and the base, which makes it a good base for [`encode_relative`].
+/// Since varint lengths grow with the logarithm of a difference rather than
+/// with the difference itself, it isn't guaranteed to give the shortest
+/// possible encoding. For an even number of values, the lower of the two
+/// middle values is returned.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::relative::optimal_base;
+///
+/// assert_eq!(optimal_base(&[5, 1000, 3, 1001, 998]), 998);
+/// assert_eq!(optimal_base(&[4, 1, 3, 2]), 2);
+/// assert_eq!(optimal_base(&[]), 0);
+/// ```
+pub fn optimal_base(values: &[i64]) -> i64 {
+    if values.is_empty() {
+        return 0;
+    }
+    let mut sorted = values.to_vec();
+    let mid = (sorted.len() - 1) / 2;
+    *sorted.select_nth_unstable(mid).1
+}
@@ -0,0 +1,130 @@
+//! Varints which never contain a `0x00` byte, so that `0x00` can separate
+//! records.
+//!
+//! A plain LEB128 varint only contains a `0x00` byte when it encodes zero:
+//! every byte but the last has its continuation bit set, and the last byte
+//! of a minimal encoding of any other value holds its non-zero most
+//! significant group of seven bits. The varints in this module therefore
+//! bias the zigzag encoding of every value by one before writing it. The
+//! biased value takes up to 65 bits, so a varint is still at most
+//! [`MAX_VARINT_LEN`] bytes long.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::varint::{CONTINUATION_BIT, MAX_VARINT_LEN, PAYLOAD_BITS};
+#[cfg(feature = "alloc")]
+use crate::ZigZagEncode;
+use crate::{VarintError, ZigZagDecode};
+
+/// Appends `x` to `out` as a zigzag-encoded varint, biased by one so that
+/// none of its bytes is `0x00`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::separated::write_zigzag_varint_nonzero_terminal;
+///
+/// let mut out = Vec::new();
+/// write_zigzag_varint_nonzero_terminal(0, &mut out);
+/// write_zigzag_varint_nonzero_terminal(-1, &mut out);
+/// write_zigzag_varint_nonzero_terminal(64, &mut out);
+/// assert_eq!(out, [0x01, 0x02, 0x81, 0x01]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn write_zigzag_varint_nonzero_terminal(x: i64, out: &mut Vec<u8>) {
+    let encoded: u64 = x.zigzag_encode();
+    let mut biased = u128::from(encoded) + 1;
+    while biased >= u128::from(CONTINUATION_BIT) {
+        out.push(biased as u8 | CONTINUATION_BIT);
+        biased >>= 7;
+    }
+    out.push(biased as u8);
+}
+
+/// Reads a varint written by [`write_zigzag_varint_nonzero_terminal`] from
+/// the start of `input`.
+///
+/// On success, returns the decoded value and the number of bytes the varint
+/// occupied.
+///
+/// # Errors
+///
+/// Returns [`VarintError::Truncated`] if `input` ends before the final byte
+/// of the varint, [`VarintError::OutOfRange`] if the varint is `0x00`, which
+/// no value encodes to, and [`VarintError::Overflow`] if the varint is
+/// longer than [`MAX_VARINT_LEN`] bytes or encodes a value that doesn't fit
+/// in an `i64`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::separated::read_zigzag_varint_nonzero_terminal;
+///
+/// assert_eq!(read_zigzag_varint_nonzero_terminal(&[0x02]), Ok((-1, 1)));
+/// assert_eq!(read_zigzag_varint_nonzero_terminal(&[0x81, 0x01, 0x00]), Ok((64, 2)));
+/// assert!(read_zigzag_varint_nonzero_terminal(&[0x00]).is_err());
+/// ```
+pub fn read_zigzag_varint_nonzero_terminal(input: &[u8]) -> Result<(i64, usize), VarintError> {
+    let mut biased = 0u128;
+    for (i, &byte) in input.iter().take(MAX_VARINT_LEN).enumerate() {
+        biased |= u128::from(byte & PAYLOAD_BITS) << (7 * i);
+        if byte & CONTINUATION_BIT == 0 {
+            if biased == 0 {
+                return Err(VarintError::OutOfRange);
+            }
+            let encoded = u64::try_from(biased - 1).map_err(|_| VarintError::Overflow)?;
+            return Ok((encoded.zigzag_decode(), i + 1));
+        }
+    }
+    if input.len() >= MAX_VARINT_LEN {
+        Err(VarintError::Overflow)
+    } else {
+        Err(VarintError::Truncated)
+    }
+}
+
+/// Reads varints written by [`write_zigzag_varint_nonzero_terminal`] from
+/// the start of `input` until the byte `sep`.
+///
+/// On success, returns the values of the record and the number of bytes it
+/// occupied, including the separator. The separator is only looked for where
+/// a varint would start, so with `sep` as `0x00`, which no varint contains,
+/// records are never split in the wrong place.
+///
+/// # Errors
+///
+/// Returns [`VarintError::Truncated`] if `input` ends before a separator is
+/// found. Otherwise, see [`read_zigzag_varint_nonzero_terminal`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::separated::{read_until_separator, write_zigzag_varint_nonzero_terminal};
+///
+/// let mut out = Vec::new();
+/// write_zigzag_varint_nonzero_terminal(0, &mut out);
+/// write_zigzag_varint_nonzero_terminal(-1, &mut out);
+/// out.push(0x00);
+/// out.push(0x00);
+///
+/// assert_eq!(read_until_separator(&out, 0x00), Ok((vec![0, -1], 3)));
+/// assert_eq!(read_until_separator(&out[3..], 0x00), Ok((vec![], 1)));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn read_until_separator(input: &[u8], sep: u8) -> Result<(Vec<i64>, usize), VarintError> {
+    let mut values = Vec::new();
+    let mut offset = 0;
+    loop {
+        match input.get(offset) {
+            None => return Err(VarintError::Truncated),
+            Some(&byte) if byte == sep => return Ok((values, offset + 1)),
+            Some(_) => {
+                let (value, len) = read_zigzag_varint_nonzero_terminal(&input[offset..])?;
+                values.push(value);
+                offset += len;
+            }
+        }
+    }
+}
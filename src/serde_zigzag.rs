@@ -0,0 +1,116 @@
+//! Serializing signed integer fields in their zigzag-encoded form.
+//!
+//! The functions in this module follow serde's `with` convention, so a field
+//! of any type implementing [`ZigZag`](crate::ZigZag) can be annotated with
+//! `#[serde(with = "zigzag::serde")]` instead of changing its type. The field
+//! is then serialized as its unsigned encoding, which formats with
+//! variable-length unsigned integers, such as bincode and postcard, store
+//! compactly. The module is also available as `zigzag::serde_zigzag`.
+//!
+//! Alternatively, wrapping a value in [`ZigZag`] gives it the same
+//! serialization wherever it appears, such as inside a `Vec` or an `Option`,
+//! where a `with` attribute can't reach.
+//!
+//! # Examples
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Sample {
+//!     #[serde(with = "zigzag::serde")]
+//!     delta: i64,
+//! }
+//!
+//! let json = serde_json::to_string(&Sample { delta: -2 })?;
+//! assert_eq!(json, r#"{"delta":3}"#);
+//! assert_eq!(serde_json::from_str::<Sample>(&json)?, Sample { delta: -2 });
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::ZigZagDecode;
+
+/// Serializes `value` as its zigzag encoding.
+///
+/// # Errors
+///
+/// Returns any error from `serializer`.
+#[inline]
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: crate::ZigZag,
+    T::Unsigned: Serialize,
+    S: Serializer,
+{
+    value.zigzag_encode().serialize(serializer)
+}
+
+/// Deserializes a zigzag encoding and decodes it.
+///
+/// # Errors
+///
+/// Returns any error from `deserializer`.
+#[inline]
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: crate::ZigZag,
+    T::Unsigned: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::Unsigned::deserialize(deserializer).map(ZigZagDecode::zigzag_decode)
+}
+
+/// A signed integer which serializes as its zigzag encoding.
+///
+/// This is the newtype version of this module's `with` functions, for values
+/// that aren't a field of their own. The wrapper is `#[repr(transparent)]`,
+/// and compares and hashes like the value it wraps.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::serde::ZigZag;
+///
+/// let deltas = vec![ZigZag(-1i32), ZigZag(2)];
+/// let json = serde_json::to_string(&deltas)?;
+/// assert_eq!(json, "[1,4]");
+/// assert_eq!(serde_json::from_str::<Vec<ZigZag<i32>>>(&json)?, deltas);
+///
+/// let missing: Option<ZigZag<i8>> = serde_json::from_str("null")?;
+/// assert_eq!(missing, None);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct ZigZag<T>(pub T);
+
+impl<T> From<T> for ZigZag<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        ZigZag(value)
+    }
+}
+
+impl<T> Serialize for ZigZag<T>
+where
+    T: crate::ZigZag,
+    T::Unsigned: Serialize,
+{
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for ZigZag<T>
+where
+    T: crate::ZigZag,
+    T::Unsigned: Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(ZigZag)
+    }
+}
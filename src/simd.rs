@@ -0,0 +1,193 @@
+//! Vectorized bulk encoding and decoding built on [`core::simd`].
+//!
+//! This module requires a nightly compiler, since portable SIMD is not yet
+//! stable. Each function processes its input in vectors of [`LANES`] values
+//! and handles any remainder with the scalar implementation, so the results
+//! are bit-for-bit identical to encoding or decoding one value at a time.
+
+use core::simd::prelude::*;
+
+use crate::varint::zigzag_varint_len;
+use crate::{ZigZagDecode, ZigZagEncode};
+
+/// The number of 32-bit lanes processed per vector.
+pub const LANES: usize = 8;
+
+/// Zigzag encodes every lane of `v`.
+///
+/// This is the building block of [`zigzag_encode_simd`], for code that
+/// already works with vectors.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(portable_simd)]
+/// use std::simd::Simd;
+/// use zigzag::simd::zigzag_encode_lanes;
+///
+/// let v = Simd::from_array([0, -1, 1, i32::MIN]);
+/// assert_eq!(zigzag_encode_lanes(v), Simd::from_array([0, 1, 2, u32::MAX]));
+/// ```
+#[inline]
+pub fn zigzag_encode_lanes<const N: usize>(v: Simd<i32, N>) -> Simd<u32, N> {
+    ((v << 1) ^ (v >> 31)).cast::<u32>()
+}
+
+/// Zigzag decodes every lane of `v`.
+///
+/// Each lane computes `(x >> 1) ^ -(x & 1)`. The low bit is negated as a
+/// signed lane, so a set bit becomes an all-ones mask and a clear bit stays
+/// zero, exactly as in the scalar formula.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(portable_simd)]
+/// use std::simd::Simd;
+/// use zigzag::simd::zigzag_decode_lanes;
+///
+/// let v = Simd::from_array([0, 1, 2, u32::MAX]);
+/// assert_eq!(zigzag_decode_lanes(v), Simd::from_array([0, -1, 1, i32::MIN]));
+/// ```
+#[inline]
+pub fn zigzag_decode_lanes<const N: usize>(v: Simd<u32, N>) -> Simd<i32, N> {
+    let sign = -(v & Simd::splat(1)).cast::<i32>();
+    (v >> 1).cast::<i32>() ^ sign
+}
+
+/// Zigzag encodes every value of `src` into the corresponding slot of `dst`.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::simd::zigzag_encode_simd;
+///
+/// let src = [0, -1, 1, -2, 2, i32::MAX, i32::MIN, 7, -7];
+/// let mut dst = [0; 9];
+/// zigzag_encode_simd(&src, &mut dst);
+/// assert_eq!(dst, [0, 1, 2, 3, 4, u32::MAX - 1, u32::MAX, 14, 13]);
+/// ```
+pub fn zigzag_encode_simd(src: &[i32], dst: &mut [u32]) {
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination slices have different lengths"
+    );
+    let mut src_chunks = src.chunks_exact(LANES);
+    let mut dst_chunks = dst.chunks_exact_mut(LANES);
+    for (s, d) in (&mut src_chunks).zip(&mut dst_chunks) {
+        zigzag_encode_lanes(Simd::<i32, LANES>::from_slice(s)).copy_to_slice(d);
+    }
+    for (s, d) in src_chunks
+        .remainder()
+        .iter()
+        .zip(dst_chunks.into_remainder())
+    {
+        *d = s.zigzag_encode();
+    }
+}
+
+/// Zigzag decodes every value of `src` into the corresponding slot of `dst`.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::simd::zigzag_decode_simd;
+///
+/// let src = [0, 1, 2, 3, 4, u32::MAX - 1, u32::MAX, 14, 13];
+/// let mut dst = [0; 9];
+/// zigzag_decode_simd(&src, &mut dst);
+/// assert_eq!(dst, [0, -1, 1, -2, 2, i32::MAX, i32::MIN, 7, -7]);
+/// ```
+pub fn zigzag_decode_simd(src: &[u32], dst: &mut [i32]) {
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination slices have different lengths"
+    );
+    let mut src_chunks = src.chunks_exact(LANES);
+    let mut dst_chunks = dst.chunks_exact_mut(LANES);
+    for (s, d) in (&mut src_chunks).zip(&mut dst_chunks) {
+        zigzag_decode_lanes(Simd::<u32, LANES>::from_slice(s)).copy_to_slice(d);
+    }
+    for (s, d) in src_chunks
+        .remainder()
+        .iter()
+        .zip(dst_chunks.into_remainder())
+    {
+        *d = s.zigzag_decode();
+    }
+}
+
+/// Computes the zigzag-encoded varint length of every value of `src` into the
+/// corresponding slot of `lens`.
+///
+/// Each lane counts the significant bits `b` of its encoded value, treating
+/// zero as having one, and computes `ceil(b / 7)` as `(9 * b + 64) >> 6`,
+/// which is exact for every `b` from 1 to 64 and avoids a lane-wise
+/// division. This is the length pass that
+/// [`par_zigzag_varint_encode`](crate::parallel::par_zigzag_varint_encode)
+/// uses when this feature is enabled.
+///
+/// # Panics
+///
+/// Panics if `src` and `lens` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::simd::zigzag_varint_len_simd;
+///
+/// let src = [0, -1, -64, 64, 150, i64::MAX, i64::MIN, -8192, 8192];
+/// let mut lens = [0; 9];
+/// zigzag_varint_len_simd(&src, &mut lens);
+/// assert_eq!(lens, [1, 1, 1, 2, 2, 10, 10, 2, 3]);
+/// ```
+pub fn zigzag_varint_len_simd(src: &[i64], lens: &mut [u8]) {
+    assert_eq!(
+        src.len(),
+        lens.len(),
+        "source and destination slices have different lengths"
+    );
+    let mut src_chunks = src.chunks_exact(LANES);
+    let mut len_chunks = lens.chunks_exact_mut(LANES);
+    for (s, l) in (&mut src_chunks).zip(&mut len_chunks) {
+        let x = Simd::<i64, LANES>::from_slice(s);
+        let encoded = ((x << 1) ^ (x >> 63)).cast::<u64>();
+        let bits = Simd::splat(64) - (encoded | Simd::splat(1)).leading_zeros();
+        ((bits * Simd::splat(9) + Simd::splat(64)) >> 6)
+            .cast::<u8>()
+            .copy_to_slice(l);
+    }
+    for (s, l) in src_chunks
+        .remainder()
+        .iter()
+        .zip(len_chunks.into_remainder())
+    {
+        *l = zigzag_varint_len(*s) as u8;
+    }
+}
+
+/// Decodes `chunk` as sixteen one-byte varints, or returns `None` if any of
+/// its bytes has its continuation bit set.
+///
+/// This is the fast path of
+/// [`decode_varints_into`](crate::varint::decode_varints_into) when this
+/// feature is enabled.
+#[inline]
+pub(crate) fn decode_one_byte_varints(chunk: &[u8; 16]) -> Option<[i64; 16]> {
+    let v = Simd::<u8, 16>::from_array(*chunk);
+    if v.simd_ge(Simd::splat(0x80)).any() {
+        return None;
+    }
+    let x = v.cast::<i64>();
+    Some(((x >> 1) ^ -(x & Simd::splat(1))).to_array())
+}
@@ -0,0 +1,271 @@
+//! Bulk encoding and decoding of slices of integers.
+//!
+//! Besides copying between a slice of signed values and a slice of their
+//! encodings, a buffer of any primitive integer width can be encoded or
+//! decoded in place, without allocating: every signed type has the same
+//! size and alignment as its unsigned counterpart, so the buffer is
+//! reinterpreted once its values have been transformed. With the `bytemuck`
+//! feature enabled, the same goes for the allocation of a `Vec`, which can be
+//! encoded or decoded without allocating a second one alongside it.
+
+#[cfg(feature = "bytemuck")]
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+
+use crate::{private, ZigZag, ZigZagDecode, ZigZagEncode};
+
+/// Zigzag encodes every value of `src` into the corresponding slot of `dst`.
+///
+/// With the `fast` feature enabled, `i16`, `i32` and `i64` slices are encoded
+/// with the widest SIMD instructions the running CPU supports.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::slice::zigzag_encode_slice;
+///
+/// let mut dst = [0u64; 4];
+/// zigzag_encode_slice(&[0i64, -1, 1, i64::MIN], &mut dst);
+/// assert_eq!(dst, [0, 1, 2, u64::MAX]);
+/// ```
+pub fn zigzag_encode_slice<S, U>(src: &[S], dst: &mut [U])
+where
+    S: ZigZagEncode<Output = U> + Copy,
+{
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination slices have different lengths"
+    );
+    S::encode_slice(src, dst);
+}
+
+/// Zigzag decodes every value of `src` into the corresponding slot of `dst`.
+///
+/// With the `fast` feature enabled, `u16`, `u32` and `u64` slices are decoded
+/// with the widest SIMD instructions the running CPU supports.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::slice::zigzag_decode_slice;
+///
+/// let mut dst = [0i64; 4];
+/// zigzag_decode_slice(&[0u64, 1, 2, u64::MAX], &mut dst);
+/// assert_eq!(dst, [0, -1, 1, i64::MIN]);
+/// ```
+pub fn zigzag_decode_slice<U, S>(src: &[U], dst: &mut [S])
+where
+    U: ZigZagDecode<Output = S> + Copy,
+{
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination slices have different lengths"
+    );
+    U::decode_slice(src, dst);
+}
+
+/// A signed primitive integer type whose buffers can be zigzag encoded and
+/// decoded in place.
+///
+/// This trait is sealed and implemented for every signed primitive integer
+/// type, each of which has the same layout as its [`ZigZag::Unsigned`]
+/// counterpart. It is used through [`zigzag_encode_in_place`] and
+/// [`zigzag_decode_in_place`].
+pub trait ZigZagInPlace: ZigZag + private::Sealed {
+    #[doc(hidden)]
+    fn encode_in_place(buf: &mut [Self]) -> &mut [Self::Unsigned];
+
+    #[doc(hidden)]
+    fn decode_in_place(buf: &mut [Self::Unsigned]) -> &mut [Self];
+}
+
+macro_rules! impl_in_place {
+    ($($signed:ty, $unsigned:ty);* $(;)?) => {
+        $(
+            impl ZigZagInPlace for $signed {
+                #[inline]
+                fn encode_in_place(buf: &mut [$signed]) -> &mut [$unsigned] {
+                    for x in buf.iter_mut() {
+                        let encoded: $unsigned = x.zigzag_encode();
+                        *x = encoded as $signed;
+                    }
+                    // SAFETY: the two types have the same size and alignment,
+                    // and every bit pattern is valid for both.
+                    unsafe { &mut *(buf as *mut [$signed] as *mut [$unsigned]) }
+                }
+
+                #[inline]
+                fn decode_in_place(buf: &mut [$unsigned]) -> &mut [$signed] {
+                    for x in buf.iter_mut() {
+                        let decoded: $signed = x.zigzag_decode();
+                        *x = decoded as $unsigned;
+                    }
+                    // SAFETY: as above.
+                    unsafe { &mut *(buf as *mut [$unsigned] as *mut [$signed]) }
+                }
+            }
+        )*
+    };
+}
+
+impl_in_place! {
+    i8, u8;
+    i16, u16;
+    i32, u32;
+    i64, u64;
+    i128, u128;
+    isize, usize;
+}
+
+/// Zigzag encodes every value of `buf` in place, and returns the buffer as a
+/// slice of the encoded values.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::slice::zigzag_encode_in_place;
+///
+/// let mut buf = [0i64, -1, 1, i64::MIN];
+/// let encoded = zigzag_encode_in_place(&mut buf);
+/// assert_eq!(encoded, [0, 1, 2, u64::MAX]);
+/// ```
+#[inline]
+pub fn zigzag_encode_in_place<T: ZigZagInPlace>(buf: &mut [T]) -> &mut [T::Unsigned] {
+    T::encode_in_place(buf)
+}
+
+/// Zigzag decodes every value of `buf` in place, and returns the buffer as a
+/// slice of the decoded values.
+///
+/// This is the inverse of [`zigzag_encode_in_place`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::slice::zigzag_decode_in_place;
+///
+/// let mut buf = [0u16, 1, 2, u16::MAX];
+/// let decoded = zigzag_decode_in_place::<i16>(&mut buf);
+/// assert_eq!(decoded, [0, -1, 1, i16::MIN]);
+/// ```
+#[inline]
+pub fn zigzag_decode_in_place<T: ZigZagInPlace>(buf: &mut [T::Unsigned]) -> &mut [T] {
+    T::decode_in_place(buf)
+}
+
+/// Zigzag encodes every value of `values` in place, and returns the same
+/// allocation as a `Vec` of the encoded values.
+///
+/// Peak memory stays at a single buffer, which matters for very long
+/// vectors. The allocation is handed over by
+/// [`bytemuck::allocation::cast_vec`], which checks that the two types have
+/// the same layout.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::slice::zigzag_encode_vec;
+///
+/// let values = vec![0i64, -1, 1, i64::MIN];
+/// let ptr = values.as_ptr() as usize;
+/// let encoded = zigzag_encode_vec(values);
+/// assert_eq!(encoded, [0u64, 1, 2, u64::MAX]);
+/// assert_eq!(encoded.as_ptr() as usize, ptr);
+/// ```
+#[cfg(feature = "bytemuck")]
+pub fn zigzag_encode_vec<T>(mut values: Vec<T>) -> Vec<T::Unsigned>
+where
+    T: ZigZagInPlace + Pod,
+    T::Unsigned: Pod,
+{
+    T::encode_in_place(&mut values);
+    bytemuck::allocation::cast_vec(values)
+}
+
+/// Zigzag decodes every value of `values` in place, and returns the same
+/// allocation as a `Vec` of the decoded values.
+///
+/// This is the inverse of [`zigzag_encode_vec`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::slice::zigzag_decode_vec;
+///
+/// let decoded = zigzag_decode_vec::<i16>(vec![0, 1, 2, u16::MAX]);
+/// assert_eq!(decoded, [0, -1, 1, i16::MIN]);
+/// ```
+#[cfg(feature = "bytemuck")]
+pub fn zigzag_decode_vec<T>(mut values: Vec<T::Unsigned>) -> Vec<T>
+where
+    T: ZigZagInPlace + Pod,
+    T::Unsigned: Pod,
+{
+    T::decode_in_place(&mut values);
+    bytemuck::allocation::cast_vec(values)
+}
+
+/// Zigzag encodes every value of `src` into the corresponding slot of the
+/// possibly uninitialized `dst`, and returns `dst` as an initialized slice.
+///
+/// This lets a freshly reserved buffer be filled without first zeroing it,
+/// such as the spare capacity of a `Vec`.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+///
+/// # Safety
+///
+/// The lengths are checked, so every value of the returned slice is
+/// initialized. Code that goes on to treat the buffer behind `dst` as
+/// initialized, such as by calling `Vec::set_len`, may only count the
+/// `src.len()` slots written here.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::slice::zigzag_encode_slice_uninit;
+///
+/// let src = [0, -1, 1, i32::MIN];
+/// let mut dst = Vec::with_capacity(src.len());
+///
+/// // SAFETY: the spare capacity holds exactly `src.len()` slots.
+/// let encoded = unsafe { zigzag_encode_slice_uninit(&src, &mut dst.spare_capacity_mut()[..src.len()]) };
+/// assert_eq!(encoded, [0, 1, 2, u32::MAX]);
+///
+/// // SAFETY: the first `src.len()` values were initialized above.
+/// unsafe { dst.set_len(src.len()) };
+/// assert_eq!(dst, [0, 1, 2, u32::MAX]);
+/// ```
+#[inline]
+pub unsafe fn zigzag_encode_slice_uninit<'a>(
+    src: &[i32],
+    dst: &'a mut [MaybeUninit<u32>],
+) -> &'a mut [u32] {
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination slices have different lengths"
+    );
+    for (d, &s) in dst.iter_mut().zip(src) {
+        d.write(s.zigzag_encode());
+    }
+    // SAFETY: `dst` was checked to be as long as `src` above, so every
+    // value of `dst` was initialized above, and `MaybeUninit<u32>` has the
+    // same layout as `u32`.
+    &mut *(dst as *mut [MaybeUninit<u32>] as *mut [u32])
+}
@@ -0,0 +1,118 @@
+//! Aggregates computed over zigzag-encoded values as if they were decoded,
+//! and estimates of how well a dataset compresses.
+//!
+//! Summing or comparing encoded values directly gives meaningless results,
+//! since the encoding orders values by magnitude rather than by sign. These
+//! helpers decode each value on the fly instead.
+
+use crate::varint::zigzag_varint_len;
+use crate::ZigZagDecode;
+
+/// Sums zigzag-encoded `i32`s by their decoded values.
+///
+/// The sum is accumulated in an `i64`, which can't overflow for fewer than
+/// 2<sup>32</sup> values.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::stats::sum_encoded_i32;
+///
+/// // The encodings of -1, 1, and -2.
+/// assert_eq!(sum_encoded_i32(&[1, 2, 3]), -2);
+/// assert_eq!(sum_encoded_i32(&[u32::MAX, u32::MAX]), 2 * i64::from(i32::MIN));
+/// ```
+pub fn sum_encoded_i32(encoded: &[u32]) -> i64 {
+    encoded
+        .iter()
+        .map(|&x| i64::from(ZigZagDecode::zigzag_decode(x)))
+        .sum()
+}
+
+/// Returns the smallest decoded value of a slice of zigzag-encoded values, or
+/// `None` if the slice is empty.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::stats::min_encoded;
+///
+/// assert_eq!(min_encoded(&[1u32, 2, 3]), Some(-2i32));
+/// assert_eq!(min_encoded::<u32, i32>(&[]), None);
+/// ```
+pub fn min_encoded<U, S>(encoded: &[U]) -> Option<S>
+where
+    U: ZigZagDecode<Output = S> + Copy,
+    S: Ord,
+{
+    encoded.iter().map(|&x| x.zigzag_decode()).min()
+}
+
+/// Returns the largest decoded value of a slice of zigzag-encoded values, or
+/// `None` if the slice is empty.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::stats::max_encoded;
+///
+/// // The largest raw value, 3, is the encoding of -2.
+/// assert_eq!(max_encoded(&[1u32, 2, 3]), Some(1i32));
+/// ```
+pub fn max_encoded<U, S>(encoded: &[U]) -> Option<S>
+where
+    U: ZigZagDecode<Output = S> + Copy,
+    S: Ord,
+{
+    encoded.iter().map(|&x| x.zigzag_decode()).max()
+}
+
+/// Returns the number of bytes [`zigzag_encode_all`] would produce for `src`,
+/// without encoding it.
+///
+/// Comparing the result against `src.len() * 8` gives the space saved over
+/// storing the values at a fixed width.
+///
+/// [`zigzag_encode_all`]: crate::varint::zigzag_encode_all
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use zigzag::stats::estimate_varint_size;
+/// use zigzag::varint::zigzag_encode_all;
+///
+/// let src: Vec<i64> = (-50..50).collect();
+/// assert_eq!(estimate_varint_size(&src), zigzag_encode_all(&src).len());
+/// assert!(estimate_varint_size(&src) * 4 < src.len() * 8);
+/// # }
+/// ```
+pub fn estimate_varint_size(src: &[i64]) -> usize {
+    src.iter().map(|&x| zigzag_varint_len(x)).sum()
+}
+
+/// Returns the number of bytes [`delta_zigzag_encode`] would produce for
+/// `src`, without encoding it.
+///
+/// [`delta_zigzag_encode`]: crate::delta::delta_zigzag_encode
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::stats::{estimate_delta_varint_size, estimate_varint_size};
+///
+/// let timestamps: Vec<i64> = (0..100).map(|i| 1_600_000_000 + i * 5).collect();
+/// assert_eq!(estimate_varint_size(&timestamps), 500);
+/// assert_eq!(estimate_delta_varint_size(&timestamps), 5 + 99);
+/// ```
+pub fn estimate_delta_varint_size(src: &[i64]) -> usize {
+    let mut prev = 0i64;
+    src.iter()
+        .map(|&x| {
+            let len = zigzag_varint_len(x.wrapping_sub(prev));
+            prev = x;
+            len
+        })
+        .sum()
+}
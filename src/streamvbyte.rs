@@ -0,0 +1,250 @@
+//! Stream VByte encoding of zigzag-encoded `i32` blocks.
+//!
+//! Stream VByte uses the same 2-bit length fields as [group
+//! varint](crate::group_varint), but stores all of a block's tag bytes, here
+//! called control bytes, up front in a control stream, followed by a data
+//! stream of every value in order. The `i`th value's length is in the bits
+//! `2 * (i % 4)..2 * (i % 4) + 2` of control byte `i / 4`, and the value
+//! itself uses that many little-endian bytes of the data stream. Keeping the
+//! two apart lets a decoder look up where four values lie with a single
+//! byte, then gather them with one shuffle.
+//!
+//! A block doesn't record how many values it holds; that has to be stored
+//! alongside it. With the `fast` feature enabled, [`decode_block`] uses
+//! SSSE3 on x86 CPUs which support it.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use zigzag::streamvbyte::{decode_block, encode_block};
+//!
+//! let values = [0, -1, 300, i32::MIN, 5];
+//! let mut block = Vec::new();
+//! encode_block(&values, &mut block);
+//! assert_eq!(block, [0b11_01_00_00, 0b00, 0, 1, 0x58, 0x02, 0xff, 0xff, 0xff, 0xff, 10]);
+//!
+//! let mut decoded = [0; 5];
+//! assert_eq!(decode_block(&block, &mut decoded), Ok(block.len()));
+//! assert_eq!(decoded, values);
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::group_varint::byte_len;
+#[cfg(feature = "alloc")]
+use crate::ZigZagEncode;
+use crate::{DecodeError, ZigZagDecode};
+
+/// Returns the number of control bytes in a block of `count` values.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::streamvbyte::control_len;
+///
+/// assert_eq!(control_len(0), 0);
+/// assert_eq!(control_len(4), 1);
+/// assert_eq!(control_len(5), 2);
+/// ```
+#[inline]
+pub const fn control_len(count: usize) -> usize {
+    count.div_ceil(4)
+}
+
+/// Returns the largest number of bytes a block of `count` values can
+/// occupy.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::streamvbyte::max_encoded_len;
+///
+/// assert_eq!(max_encoded_len(5), 2 + 5 * 4);
+/// ```
+#[inline]
+pub const fn max_encoded_len(count: usize) -> usize {
+    control_len(count) + count * 4
+}
+
+/// Zigzag encodes `values` and appends them to `out` as a block, returning
+/// the number of bytes appended.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::streamvbyte::{control_len, encode_block};
+///
+/// let mut out = Vec::new();
+/// let len = encode_block(&[1, -1, 1000, -1000, 0, 0], &mut out);
+/// assert_eq!(len, control_len(6) + 1 + 1 + 2 + 2 + 1 + 1);
+/// assert_eq!(out[..2], [0b01_01_00_00, 0b00_00]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_block(values: &[i32], out: &mut Vec<u8>) -> usize {
+    let start = out.len();
+    out.resize(start + control_len(values.len()), 0);
+    for (i, &x) in values.iter().enumerate() {
+        let encoded: u32 = x.zigzag_encode();
+        let len = byte_len(encoded);
+        out[start + i / 4] |= ((len - 1) as u8) << (2 * (i % 4));
+        out.extend_from_slice(&encoded.to_le_bytes()[..len]);
+    }
+    out.len() - start
+}
+
+/// Returns the length of the `i`th value of a block from its control bytes.
+#[inline]
+fn value_len(control: &[u8], i: usize) -> usize {
+    usize::from((control[i / 4] >> (2 * (i % 4))) & 3) + 1
+}
+
+/// Decodes a block of `out.len()` values from the start of `input` into
+/// `out`.
+///
+/// On success, returns the number of bytes the block occupied.
+///
+/// # Errors
+///
+/// Returns [`DecodeError::Truncated`] if `input` is shorter than the block
+/// its control bytes describe, with as many bytes needed as can be told from
+/// the control bytes that are present. The contents of `out` are unspecified
+/// in that case.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::streamvbyte::decode_block;
+/// use zigzag::DecodeError;
+///
+/// let mut out = [0; 2];
+/// assert_eq!(decode_block(&[0b01_00, 3, 0x80, 0x02], &mut out), Ok(4));
+/// assert_eq!(out, [-2, 320]);
+/// assert_eq!(
+///     decode_block(&[0b01_00, 3, 0x80], &mut out),
+///     Err(DecodeError::Truncated { needed: 1 }),
+/// );
+/// ```
+pub fn decode_block(input: &[u8], out: &mut [i32]) -> Result<usize, DecodeError> {
+    let count = out.len();
+    let control_len = control_len(count);
+    if input.len() < control_len {
+        return Err(DecodeError::Truncated {
+            needed: control_len - input.len(),
+        });
+    }
+    let (control, data) = input.split_at(control_len);
+
+    let (decoded, mut offset) = decode_groups(control, data, out);
+    for (i, x) in out.iter_mut().enumerate().skip(decoded) {
+        let len = value_len(control, i);
+        let bytes = match data.get(offset..offset + len) {
+            Some(bytes) => bytes,
+            None => {
+                let rest: usize = (i..count).map(|i| value_len(control, i)).sum();
+                return Err(DecodeError::Truncated {
+                    needed: offset + rest - data.len(),
+                });
+            }
+        };
+        let mut buf = [0u8; 4];
+        buf[..len].copy_from_slice(bytes);
+        *x = u32::from_le_bytes(buf).zigzag_decode();
+        offset += len;
+    }
+    Ok(control_len + offset)
+}
+
+/// Decodes as many whole groups as possible with vector instructions,
+/// returning the number of values decoded and data bytes consumed.
+#[cfg(all(feature = "fast", any(target_arch = "x86", target_arch = "x86_64")))]
+fn decode_groups(control: &[u8], data: &[u8], out: &mut [i32]) -> (usize, usize) {
+    if std::arch::is_x86_feature_detected!("ssse3") {
+        // SAFETY: the CPU supports SSSE3.
+        unsafe { ssse3::decode_groups(control, data, out) }
+    } else {
+        (0, 0)
+    }
+}
+
+#[cfg(not(all(feature = "fast", any(target_arch = "x86", target_arch = "x86_64"))))]
+fn decode_groups(_control: &[u8], _data: &[u8], _out: &mut [i32]) -> (usize, usize) {
+    (0, 0)
+}
+
+#[cfg(all(feature = "fast", any(target_arch = "x86", target_arch = "x86_64")))]
+mod ssse3 {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    /// Returns the number of data bytes described by a control byte.
+    #[inline]
+    fn group_len(control: u8) -> usize {
+        usize::from(control & 3)
+            + usize::from((control >> 2) & 3)
+            + usize::from((control >> 4) & 3)
+            + usize::from(control >> 6)
+            + 4
+    }
+
+    /// For every control byte, the shuffle which moves each of the four
+    /// values of a group from the data stream into its own 32-bit lane, with
+    /// the unused high bytes zeroed.
+    static SHUFFLES: [[u8; 16]; 256] = {
+        let mut shuffles = [[0x80; 16]; 256];
+        let mut control = 0;
+        while control < 256 {
+            let mut offset = 0;
+            let mut i = 0;
+            while i < 4 {
+                let len = ((control >> (2 * i)) & 3) + 1;
+                let mut k = 0;
+                while k < len {
+                    shuffles[control][4 * i + k] = (offset + k) as u8;
+                    k += 1;
+                }
+                offset += len;
+                i += 1;
+            }
+            control += 1;
+        }
+        shuffles
+    };
+
+    /// Decodes whole groups for as long as at least one full vector of data
+    /// remains, returning the number of values decoded and data bytes
+    /// consumed.
+    #[target_feature(enable = "ssse3")]
+    pub(super) unsafe fn decode_groups(
+        control: &[u8],
+        data: &[u8],
+        out: &mut [i32],
+    ) -> (usize, usize) {
+        let (mut decoded, mut offset) = (0, 0);
+        for (&control, chunk) in control.iter().zip(out.chunks_exact_mut(4)) {
+            if data.len() - offset < 16 {
+                break;
+            }
+            // SAFETY: at least 16 bytes of `data` remain past `offset`, and
+            // `chunk` holds exactly four values.
+            let v = _mm_loadu_si128(data.as_ptr().add(offset) as *const __m128i);
+            let shuffle =
+                _mm_loadu_si128(SHUFFLES[usize::from(control)].as_ptr() as *const __m128i);
+            let v = _mm_shuffle_epi8(v, shuffle);
+            let v = _mm_xor_si128(
+                _mm_srli_epi32(v, 1),
+                _mm_sub_epi32(_mm_setzero_si128(), _mm_and_si128(v, _mm_set1_epi32(1))),
+            );
+            _mm_storeu_si128(chunk.as_mut_ptr() as *mut __m128i, v);
+            decoded += 4;
+            offset += group_len(control);
+        }
+        (decoded, offset)
+    }
+}
@@ -0,0 +1,89 @@
+//! Zigzag encoding of the signed difference between two [`SystemTime`]s.
+//!
+//! A delta is measured in nanoseconds as an `i128`, which holds the
+//! difference between any two `SystemTime`s, including ones before the Unix
+//! epoch, without losing precision.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime};
+
+use crate::{TimeError, ZigZagDecode, ZigZagEncode};
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// Computes `a - b` in nanoseconds and zigzag encodes it.
+///
+/// The difference is negative when `a` is earlier than `b`.
+///
+/// # Errors
+///
+/// Returns [`TimeError`] if the difference doesn't fit in an `i128`. No
+/// platform's `SystemTime` spans a range that wide, but the check is kept
+/// rather than assumed.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use zigzag::time::encode_systemtime_delta;
+///
+/// let a = UNIX_EPOCH + Duration::from_nanos(5);
+/// assert_eq!(encode_systemtime_delta(a, UNIX_EPOCH), Ok(10));
+/// assert_eq!(encode_systemtime_delta(UNIX_EPOCH, a), Ok(9));
+/// ```
+pub fn encode_systemtime_delta(a: SystemTime, b: SystemTime) -> Result<u128, TimeError> {
+    let nanos = match a.duration_since(b) {
+        Ok(forward) => i128::try_from(forward.as_nanos()).map_err(|_| TimeError)?,
+        Err(backward) => -i128::try_from(backward.duration().as_nanos()).map_err(|_| TimeError)?,
+    };
+    Ok(nanos.zigzag_encode())
+}
+
+/// Decodes a delta produced by [`encode_systemtime_delta`] into signed
+/// nanoseconds.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::time::decode_systemtime_delta;
+///
+/// assert_eq!(decode_systemtime_delta(10), 5);
+/// assert_eq!(decode_systemtime_delta(9), -5);
+/// ```
+#[inline]
+pub fn decode_systemtime_delta(encoded: u128) -> i128 {
+    encoded.zigzag_decode()
+}
+
+/// Adds a signed nanosecond delta to `base`.
+///
+/// This undoes [`encode_systemtime_delta`]: if `encode_systemtime_delta(a,
+/// b)` returned `x`, then `apply_systemtime_delta(b, decode_systemtime_delta(x))`
+/// returns `a`.
+///
+/// # Errors
+///
+/// Returns [`TimeError`] if the result is outside the range of `SystemTime`
+/// on this platform.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use zigzag::time::apply_systemtime_delta;
+///
+/// let before_epoch = UNIX_EPOCH - Duration::from_secs(1);
+/// assert_eq!(apply_systemtime_delta(UNIX_EPOCH, -1_000_000_000), Ok(before_epoch));
+/// assert!(apply_systemtime_delta(UNIX_EPOCH, i128::MAX).is_err());
+/// ```
+pub fn apply_systemtime_delta(base: SystemTime, nanos: i128) -> Result<SystemTime, TimeError> {
+    let magnitude = nanos.unsigned_abs();
+    let secs = u64::try_from(magnitude / NANOS_PER_SEC).map_err(|_| TimeError)?;
+    let duration = Duration::new(secs, (magnitude % NANOS_PER_SEC) as u32);
+    if nanos < 0 {
+        base.checked_sub(duration)
+    } else {
+        base.checked_add(duration)
+    }
+    .ok_or(TimeError)
+}
@@ -0,0 +1,268 @@
+//! Delta-of-delta compression of timestamps, as in Facebook's Gorilla.
+//!
+//! Samples of a time series usually arrive at a nearly fixed interval, so
+//! the difference between consecutive deltas is almost always zero or close
+//! to it. The first timestamp is stored in 64 bits. Every later one is
+//! stored as the zigzag encoding of its delta of delta, behind a prefix code
+//! giving the number of bits that follow:
+//!
+//! | Prefix | Bits | Delta of delta |
+//! |--------|------|----------------|
+//! | `0`    | 0    | 0              |
+//! | `10`   | 7    | -64 to 63      |
+//! | `110`  | 9    | -256 to 255    |
+//! | `1110` | 12   | -2048 to 2047  |
+//! | `1111` | 64   | any other      |
+//!
+//! These are the bit widths of the paper. Its last bucket holds 32 bits,
+//! which suffices for the seconds-granularity timestamps of a two-hour block
+//! it was designed for; this module uses 64 instead, so any series of `i64`s
+//! round-trips. Deltas are computed with wrapping arithmetic to the same
+//! end.
+//!
+//! An encoded series starts with the number of timestamps in it, as an
+//! unsigned varint, which lets the decoder tell the padding of the last byte
+//! from more timestamps.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use zigzag::timestamps::{decode_timestamps, encode_timestamps};
+//!
+//! let timestamps = [1_700_000_000, 1_700_000_060, 1_700_000_120, 1_700_000_181];
+//! let encoded = encode_timestamps(&timestamps);
+//! // The count, the first timestamp in 64 bits, and then 83 bits: 2 + 7 for
+//! // the first delta, 1 for the unchanged second one, and 2 + 7 for the last.
+//! assert_eq!(encoded.len(), 1 + 11);
+//! assert_eq!(decode_timestamps(&encoded), Ok(timestamps.to_vec()));
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::iter::FusedIterator;
+
+use crate::bitstream::BitReader;
+#[cfg(feature = "alloc")]
+use crate::bitstream::BitWriter;
+use crate::varint::read_uvarint;
+#[cfg(feature = "alloc")]
+use crate::varint::write_uvarint;
+#[cfg(feature = "alloc")]
+use crate::ZigZagEncode;
+use crate::{DecodeError, ZigZagDecode};
+
+/// The number of bits of the zigzag-encoded delta of delta which follow
+/// each number of leading one bits of the prefix code.
+const BUCKET_BITS: [u32; 5] = [0, 7, 9, 12, 64];
+
+/// Encodes timestamps one at a time.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::timestamps::{encode_timestamps, TimestampEncoder};
+///
+/// let mut encoder = TimestampEncoder::new();
+/// encoder.encode(-5);
+/// encoder.encode_slice(&[10, 25]);
+/// assert_eq!(encoder.len(), 3);
+/// assert_eq!(encoder.finish(), encode_timestamps(&[-5, 10, 25]));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub struct TimestampEncoder {
+    writer: BitWriter,
+    len: u64,
+    prev: i64,
+    prev_delta: i64,
+}
+
+#[cfg(feature = "alloc")]
+impl TimestampEncoder {
+    /// Creates an encoder for a new series.
+    #[inline]
+    pub const fn new() -> Self {
+        TimestampEncoder {
+            writer: BitWriter::new(),
+            len: 0,
+            prev: 0,
+            prev_delta: 0,
+        }
+    }
+
+    /// Returns the number of timestamps encoded so far.
+    #[inline]
+    pub const fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if no timestamps have been encoded yet.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends `timestamp` to the series.
+    pub fn encode(&mut self, timestamp: i64) {
+        if self.len == 0 {
+            self.writer.write_bits(timestamp as u64, 64);
+        } else {
+            let delta = timestamp.wrapping_sub(self.prev);
+            let encoded = delta.wrapping_sub(self.prev_delta).zigzag_encode();
+            let bucket = BUCKET_BITS[..4]
+                .iter()
+                .position(|&bits| encoded >> bits == 0)
+                .unwrap_or(4);
+            // `bucket` one bits, then a zero bit unless all four are ones.
+            if bucket < 4 {
+                let prefix = ((1 << bucket) - 1) << 1;
+                self.writer.write_bits(prefix, bucket as u32 + 1);
+            } else {
+                self.writer.write_bits(0b1111, 4);
+            }
+            self.writer.write_bits(encoded, BUCKET_BITS[bucket]);
+            self.prev_delta = delta;
+        }
+        self.prev = timestamp;
+        self.len += 1;
+    }
+
+    /// Appends every timestamp of `timestamps` to the series.
+    pub fn encode_slice(&mut self, timestamps: &[i64]) {
+        for &timestamp in timestamps {
+            self.encode(timestamp);
+        }
+    }
+
+    /// Returns the encoded series.
+    pub fn finish(self) -> Vec<u8> {
+        let bytes = self.writer.into_bytes();
+        let mut out = Vec::with_capacity(bytes.len() + 1);
+        write_uvarint(self.len, &mut out);
+        out.extend_from_slice(&bytes);
+        out
+    }
+}
+
+/// Encodes a series of timestamps.
+///
+/// See the [module documentation](self) for the format.
+#[cfg(feature = "alloc")]
+pub fn encode_timestamps(timestamps: &[i64]) -> Vec<u8> {
+    let mut encoder = TimestampEncoder::new();
+    encoder.encode_slice(timestamps);
+    encoder.finish()
+}
+
+/// An iterator which lazily decodes a series of timestamps.
+///
+/// After an error, the iterator yields nothing more.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use zigzag::timestamps::{encode_timestamps, TimestampDecoder};
+///
+/// let encoded = encode_timestamps(&[100, 110, 120, 130]);
+/// let mut decoder = TimestampDecoder::new(&encoded)?;
+/// assert_eq!(decoder.remaining(), 4);
+/// assert_eq!(decoder.next(), Some(Ok(100)));
+/// assert_eq!(decoder.collect::<Result<Vec<_>, _>>()?, [110, 120, 130]);
+/// # }
+/// # Ok::<(), zigzag::DecodeError>(())
+/// ```
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TimestampDecoder<'a> {
+    reader: BitReader<'a>,
+    remaining: usize,
+    started: bool,
+    prev: i64,
+    prev_delta: i64,
+}
+
+impl<'a> TimestampDecoder<'a> {
+    /// Creates a decoder for the series encoded in `input`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` doesn't start with a valid count, or if
+    /// the count doesn't fit in a `usize`.
+    pub fn new(input: &'a [u8]) -> Result<Self, DecodeError> {
+        let (count, len) = read_uvarint(input)?;
+        Ok(TimestampDecoder {
+            reader: BitReader::new(&input[len..]),
+            remaining: usize::try_from(count).map_err(|_| DecodeError::OutOfRange)?,
+            started: false,
+            prev: 0,
+            prev_delta: 0,
+        })
+    }
+
+    /// Returns the number of timestamps left to decode.
+    #[inline]
+    pub const fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    fn decode(&mut self) -> Result<i64, DecodeError> {
+        if !self.started {
+            let first = self.reader.read_bits(64)?;
+            self.started = true;
+            return Ok(first as i64);
+        }
+        let mut bucket = 0;
+        while bucket < 4 && self.reader.read_bits(1)? == 1 {
+            bucket += 1;
+        }
+        let encoded = self.reader.read_bits(BUCKET_BITS[bucket])?;
+        self.prev_delta = self.prev_delta.wrapping_add(encoded.zigzag_decode());
+        Ok(self.prev.wrapping_add(self.prev_delta))
+    }
+}
+
+impl Iterator for TimestampDecoder<'_> {
+    type Item = Result<i64, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        match self.decode() {
+            Ok(timestamp) => {
+                self.remaining -= 1;
+                self.prev = timestamp;
+                Some(Ok(timestamp))
+            }
+            Err(err) => {
+                self.remaining = 0;
+                Some(Err(err))
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining))
+    }
+}
+
+impl FusedIterator for TimestampDecoder<'_> {}
+
+/// Decodes a series of timestamps encoded by [`encode_timestamps`] or
+/// [`TimestampEncoder`].
+///
+/// # Errors
+///
+/// Returns [`DecodeError::Truncated`] if `input` ends before the last
+/// timestamp, and the errors of [`TimestampDecoder::new`].
+#[cfg(feature = "alloc")]
+pub fn decode_timestamps(input: &[u8]) -> Result<Vec<i64>, DecodeError> {
+    TimestampDecoder::new(input)?.collect()
+}
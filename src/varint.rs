@@ -0,0 +1,1972 @@
+//! Reading and writing zigzag-encoded integers stored as LEB128 varints.
+//!
+//! A varint stores an unsigned integer in groups of seven bits, starting with
+//! the least significant group. Every byte except the last has its most
+//! significant bit—the continuation bit—set. Combined with zigzag encoding,
+//! small signed integers of either sign take up few bytes, which is the
+//! scheme used by protobuf's `sint32`/`sint64` and Avro's `int`/`long`.
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::Hasher;
+use core::iter::{FromIterator, FusedIterator};
+use core::ops::{ControlFlow, Deref};
+
+use crate::{BufferTooSmall, DecodeError, VarintError, ZigZagDecode, ZigZagEncode};
+#[cfg(feature = "alloc")]
+use crate::{LengthError, ZigZag};
+
+/// The maximum number of bytes a varint encoding a 64-bit integer occupies.
+pub const MAX_VARINT_LEN: usize = 10;
+
+pub(crate) const CONTINUATION_BIT: u8 = 0x80;
+pub(crate) const PAYLOAD_BITS: u8 = 0x7f;
+
+/// Returns the number of bytes `x` occupies as an unsigned varint.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::uvarint_len;
+///
+/// assert_eq!(uvarint_len(0), 1);
+/// assert_eq!(uvarint_len(127), 1);
+/// assert_eq!(uvarint_len(128), 2);
+/// assert_eq!(uvarint_len(u64::MAX), 10);
+/// ```
+#[inline]
+pub const fn uvarint_len(x: u64) -> usize {
+    let bits = 64 - (x | 1).leading_zeros() as usize;
+    bits.div_ceil(7)
+}
+
+/// Returns the number of bytes `x` occupies as a zigzag-encoded varint.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::zigzag_varint_len;
+///
+/// assert_eq!(zigzag_varint_len(-64), 1);
+/// assert_eq!(zigzag_varint_len(64), 2);
+/// assert_eq!(zigzag_varint_len(i64::MIN), 10);
+/// ```
+#[inline]
+pub fn zigzag_varint_len(x: i64) -> usize {
+    uvarint_len(x.zigzag_encode())
+}
+
+/// Writes `x` as an unsigned varint to the start of `buf`, which must be at
+/// least `uvarint_len(x)` bytes long, and returns the number of bytes
+/// written.
+#[inline]
+pub(crate) fn encode_uvarint(mut x: u64, buf: &mut [u8]) -> usize {
+    let mut i = 0;
+    while x >= u64::from(CONTINUATION_BIT) {
+        buf[i] = x as u8 | CONTINUATION_BIT;
+        x >>= 7;
+        i += 1;
+    }
+    buf[i] = x as u8;
+    i + 1
+}
+
+/// Appends `x` to `out` as an unsigned varint and returns the number of
+/// bytes appended.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::write_uvarint;
+///
+/// let mut out = Vec::new();
+/// assert_eq!(write_uvarint(150, &mut out), 2);
+/// assert_eq!(out, [0x96, 0x01]);
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn write_uvarint(x: u64, out: &mut Vec<u8>) -> usize {
+    let mut buf = [0; MAX_VARINT_LEN];
+    let len = encode_uvarint(x, &mut buf);
+    out.extend_from_slice(&buf[..len]);
+    len
+}
+
+/// Appends `x` to `out` as a zigzag-encoded varint and returns the number of
+/// bytes appended, which is always [`zigzag_varint_len(x)`](zigzag_varint_len).
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::write_zigzag_varint;
+///
+/// let mut out = Vec::new();
+/// assert_eq!(write_zigzag_varint(-1, &mut out), 1);
+/// assert_eq!(write_zigzag_varint(150, &mut out), 2);
+/// assert_eq!(out, [0x01, 0xac, 0x02]);
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn write_zigzag_varint(x: i64, out: &mut Vec<u8>) -> usize {
+    write_uvarint(x.zigzag_encode(), out)
+}
+
+/// Passes the bytes of the zigzag-encoded varint of `x` to `sink`, one at a
+/// time and in order.
+///
+/// This leaves the destination of the bytes entirely up to the caller, such
+/// as a ring buffer, a hasher or a socket, without the encoder needing to
+/// know about it.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::encode_zigzag_varint_with;
+///
+/// let mut bytes = Vec::new();
+/// encode_zigzag_varint_with(150, |byte| bytes.push(byte));
+/// assert_eq!(bytes, [0xac, 0x02]);
+/// ```
+#[inline]
+pub fn encode_zigzag_varint_with<F: FnMut(u8)>(x: i64, mut sink: F) {
+    let mut buf = [0; MAX_VARINT_LEN];
+    let len = encode_uvarint(x.zigzag_encode(), &mut buf);
+    buf[..len].iter().for_each(|&byte| sink(byte));
+}
+
+/// Appends `x` to `out` as a zigzag-encoded varint and returns the number of
+/// bytes appended.
+///
+/// The bytes are identical to protobuf's `sint64` and Avro's `long`
+/// encodings. Since zigzag encoding a sign-extended value only adds leading
+/// zeros, they are also identical to protobuf's `sint32` and Avro's `int`
+/// encodings of any value which fits in an `i32`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::VecDeque;
+/// use zigzag::varint::encode_varint;
+///
+/// let mut out = VecDeque::new();
+/// assert_eq!(encode_varint(-1, &mut out), 1);
+/// assert_eq!(encode_varint(i32::MIN.into(), &mut out), 5);
+/// assert_eq!(out, [0x01, 0xff, 0xff, 0xff, 0xff, 0x0f]);
+/// ```
+#[inline]
+pub fn encode_varint(x: i64, out: &mut impl Extend<u8>) -> usize {
+    let mut buf = [0; MAX_VARINT_LEN];
+    let len = encode_uvarint(x.zigzag_encode(), &mut buf);
+    out.extend(buf[..len].iter().copied());
+    len
+}
+
+/// Writes `x` as a zigzag-encoded varint to the start of `out`.
+///
+/// Returns the number of bytes written, or `None`—leaving `out`
+/// untouched—if `out` is too short to hold the varint.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::write_zigzag_varint_slice;
+///
+/// let mut buf = [0; 2];
+/// assert_eq!(write_zigzag_varint_slice(150, &mut buf), Some(2));
+/// assert_eq!(buf, [0xac, 0x02]);
+/// assert_eq!(write_zigzag_varint_slice(-10_000, &mut buf), None);
+/// ```
+#[inline]
+pub fn write_zigzag_varint_slice(x: i64, out: &mut [u8]) -> Option<usize> {
+    let x = x.zigzag_encode();
+    if out.len() < uvarint_len(x) {
+        return None;
+    }
+    Some(encode_uvarint(x, out))
+}
+
+/// Writes every value of `values` as a zigzag-encoded varint into
+/// `region[start..]`, one after the other, and returns the offset just past
+/// the last byte written.
+///
+/// Either the whole batch is written or nothing is: the encoded length is
+/// computed up front, and `region` is left untouched if the batch doesn't
+/// fit. This suits appending to a pre-reserved region, such as a memory-mapped
+/// file, where the committed length must only advance once the whole batch
+/// is in place.
+///
+/// # Errors
+///
+/// Returns [`BufferTooSmall`] if the batch doesn't fit in `region[start..]`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::append_zigzag_batch;
+///
+/// let mut region = [0; 4];
+/// assert_eq!(append_zigzag_batch(&mut region, 1, &[-1, 150]), Ok(4));
+/// assert_eq!(region, [0x00, 0x01, 0xac, 0x02]);
+///
+/// let mut region = [0; 4];
+/// assert!(append_zigzag_batch(&mut region, 2, &[-1, 150]).is_err());
+/// assert_eq!(region, [0; 4]);
+/// ```
+pub fn append_zigzag_batch(
+    region: &mut [u8],
+    start: usize,
+    values: &[i64],
+) -> Result<usize, BufferTooSmall> {
+    let needed: usize = values.iter().map(|&x| zigzag_varint_len(x)).sum();
+    let available = region.len().saturating_sub(start);
+    if needed > available {
+        return Err(BufferTooSmall { needed, available });
+    }
+
+    let mut offset = start;
+    for &x in values {
+        offset += encode_uvarint(x.zigzag_encode(), &mut region[offset..]);
+    }
+    Ok(offset)
+}
+
+/// A single varint stored inline, without allocating.
+///
+/// `VarintBytes` dereferences to the encoded bytes, so it can be passed
+/// anywhere a `&[u8]` is expected. Signed integers of up to 64 bits convert
+/// into `VarintBytes` with [`From`], which zigzag encodes them on the way.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::VarintBytes;
+///
+/// assert_eq!(VarintBytes::from_zigzag(-1).as_ref(), &[0x01]);
+/// assert_eq!(&*VarintBytes::from_zigzag(150), &[0xac, 0x02]);
+/// assert_eq!(VarintBytes::from_zigzag(i64::MIN).len(), 10);
+/// assert_eq!(VarintBytes::from(-1i32), VarintBytes::from_zigzag(-1));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VarintBytes {
+    // Bytes past `len` are always zero, so the derived impls only depend on
+    // the encoded bytes.
+    buf: [u8; MAX_VARINT_LEN],
+    len: u8,
+}
+
+impl VarintBytes {
+    /// Encodes `x` as an unsigned varint.
+    #[inline]
+    pub fn from_uvarint(x: u64) -> Self {
+        let mut buf = [0; MAX_VARINT_LEN];
+        let len = encode_uvarint(x, &mut buf) as u8;
+        VarintBytes { buf, len }
+    }
+
+    /// Encodes `x` as a zigzag-encoded varint.
+    #[inline]
+    pub fn from_zigzag(x: i64) -> Self {
+        VarintBytes::from_uvarint(x.zigzag_encode())
+    }
+
+    /// Copies the varint at the start of `input`, returning it along with the
+    /// input remaining after it.
+    ///
+    /// The bytes are copied as they are, so a varint that isn't minimally
+    /// encoded stays that way.
+    ///
+    /// # Errors
+    ///
+    /// See [`read_uvarint`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zigzag::varint::VarintBytes;
+    ///
+    /// let (bytes, rest) = VarintBytes::parse(&[0xac, 0x02, 0x01])?;
+    /// assert_eq!(&*bytes, &[0xac, 0x02]);
+    /// assert_eq!(bytes.decode(), 150);
+    /// assert_eq!(rest, &[0x01]);
+    /// # Ok::<(), zigzag::VarintError>(())
+    /// ```
+    #[inline]
+    pub fn parse(input: &[u8]) -> Result<(Self, &[u8]), VarintError> {
+        let (_, len) = read_uvarint(input)?;
+        let mut buf = [0; MAX_VARINT_LEN];
+        buf[..len].copy_from_slice(&input[..len]);
+        let bytes = VarintBytes {
+            buf,
+            len: len as u8,
+        };
+        Ok((bytes, &input[len..]))
+    }
+
+    /// Decodes the varint as a zigzag-encoded value.
+    #[inline]
+    pub fn decode(&self) -> i64 {
+        self.decode_uvarint().zigzag_decode()
+    }
+
+    /// Decodes the varint as an unsigned value.
+    #[inline]
+    pub fn decode_uvarint(&self) -> u64 {
+        // Every constructor only ever stores a single, valid varint.
+        read_uvarint(self).map_or(0, |(x, _)| x)
+    }
+}
+
+macro_rules! impl_from_signed {
+    ($($signed:ty),*) => {
+        $(
+            /// Zigzag encodes the value and serializes it as a varint.
+            ///
+            /// Zigzag encoding is independent of the integer's width, so this
+            /// produces the same bytes as encoding the value as an `i64`.
+            impl From<$signed> for VarintBytes {
+                #[inline]
+                fn from(x: $signed) -> Self {
+                    VarintBytes::from_zigzag(i64::from(x))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_signed!(i8, i16, i32, i64);
+
+impl Deref for VarintBytes {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        &self.buf[..usize::from(self.len)]
+    }
+}
+
+impl AsRef<[u8]> for VarintBytes {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl fmt::Debug for VarintBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VarintBytes").field(&&**self).finish()
+    }
+}
+
+/// Writes the bytes of the zigzag-encoded varint of `x` to `f` as
+/// space-separated, two-digit lowercase hex.
+///
+/// This shows the varint's bytes as they appear on the wire, which is useful
+/// for debugging dumps of encoded streams.
+///
+/// # Errors
+///
+/// Returns any error from writing to `f`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::dump_zigzag_varint;
+///
+/// let mut s = String::new();
+/// dump_zigzag_varint(150, &mut s)?;
+/// assert_eq!(s, "ac 02");
+/// # Ok::<(), std::fmt::Error>(())
+/// ```
+pub fn dump_zigzag_varint(x: i64, f: &mut impl fmt::Write) -> fmt::Result {
+    let mut result = Ok(());
+    let mut separator = "";
+    encode_zigzag_varint_with(x, |byte| {
+        if result.is_ok() {
+            result = write!(f, "{}{:02x}", separator, byte);
+            separator = " ";
+        }
+    });
+    result
+}
+
+/// Feeds the bytes of the zigzag-encoded varint of `x` into `hasher`.
+///
+/// Unlike hashing `x` directly, which feeds its native-endian bytes to the
+/// hasher, this hashes a canonical form that is the same on every platform
+/// and for every width `x` was widened from, suiting content-addressed
+/// structures whose hashes must be stable.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::DefaultHasher;
+/// use std::hash::Hasher;
+/// use zigzag::varint::hash_zigzag;
+///
+/// let mut a = DefaultHasher::new();
+/// hash_zigzag(-1, &mut a);
+///
+/// let mut b = DefaultHasher::new();
+/// b.write(&[0x01]);
+/// assert_eq!(a.finish(), b.finish());
+/// ```
+#[inline]
+pub fn hash_zigzag<H: Hasher>(x: i64, hasher: &mut H) {
+    hasher.write(&VarintBytes::from_zigzag(x));
+}
+
+/// Encodes every value of `src` as a zigzag-encoded varint, one after the
+/// other.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::zigzag_encode_all;
+///
+/// assert_eq!(zigzag_encode_all(&[0, -1, 150]), [0x00, 0x01, 0xac, 0x02]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn zigzag_encode_all(src: &[i64]) -> Vec<u8> {
+    let mut out = Vec::new();
+    zigzag_encode_all_reuse(src, &mut out);
+    out
+}
+
+/// Encodes every value of `src` as a zigzag-encoded varint into `out`,
+/// reusing its allocation.
+///
+/// This calls `out.clear()` first, so afterwards `out` holds only the
+/// encoding of `src`, just like the result of [`zigzag_encode_all`]. Keeping
+/// one buffer across many calls avoids allocating a new one each time.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::zigzag_encode_all_reuse;
+///
+/// let mut out = Vec::new();
+/// zigzag_encode_all_reuse(&[0, -1, 150], &mut out);
+/// assert_eq!(out, [0x00, 0x01, 0xac, 0x02]);
+///
+/// zigzag_encode_all_reuse(&[-2], &mut out);
+/// assert_eq!(out, [0x03]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn zigzag_encode_all_reuse(src: &[i64], out: &mut Vec<u8>) {
+    out.clear();
+    out.reserve(src.len());
+    for &x in src {
+        write_zigzag_varint(x, out);
+    }
+}
+
+/// Appends `count` copies of `value` to `out`, each as a zigzag-encoded
+/// varint.
+///
+/// The varint is encoded once and its bytes are then repeated, which is much
+/// faster than encoding `value` again for every copy.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::encode_constant_block;
+///
+/// let mut out = Vec::new();
+/// encode_constant_block(150, 3, &mut out);
+/// assert_eq!(out, [0xac, 0x02, 0xac, 0x02, 0xac, 0x02]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_constant_block(value: i64, count: usize, out: &mut Vec<u8>) {
+    let bytes = VarintBytes::from_zigzag(value);
+    out.reserve(bytes.len() * count);
+    for _ in 0..count {
+        out.extend_from_slice(&bytes);
+    }
+}
+
+/// Appends every `Ok` value of `iter` to `out` as a zigzag-encoded varint,
+/// stopping at the first `Err`.
+///
+/// This fits pipelines of fallible values without collecting them into a
+/// `Result<Vec<_>, _>` first.
+///
+/// # Errors
+///
+/// Returns the first error yielded by `iter`. The values before it have
+/// already been appended to `out` and remain there.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::try_encode_iter;
+///
+/// let mut out = Vec::new();
+/// let values = ["-1", "150", "x", "2"].iter().map(|s| s.parse::<i32>());
+/// assert!(try_encode_iter(values, &mut out).is_err());
+/// assert_eq!(out, [0x01, 0xac, 0x02]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn try_encode_iter<T, E, I>(iter: I, out: &mut Vec<u8>) -> Result<(), E>
+where
+    T: ZigZag,
+    T::Unsigned: Into<u64>,
+    I: IntoIterator<Item = Result<T, E>>,
+{
+    for value in iter {
+        write_uvarint(value?.zigzag_encode().into(), out);
+    }
+    Ok(())
+}
+
+/// Appends the native-endian `i32`s stored in `bytes` to `out` as
+/// zigzag-encoded varints.
+///
+/// This bridges from buffers filled by C code, such as an `int32_t` array
+/// read as raw bytes. The values are read in the byte order of the target
+/// platform, so `bytes` must come from the same machine, or from one with
+/// the same byte order.
+///
+/// # Errors
+///
+/// Returns [`LengthError`] if the length of `bytes` isn't a multiple of
+/// four, in which case nothing is appended to `out`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::transcode_raw_i32_to_varint;
+///
+/// let mut bytes = Vec::new();
+/// for x in [-1i32, 150] {
+///     bytes.extend_from_slice(&x.to_ne_bytes());
+/// }
+///
+/// let mut out = Vec::new();
+/// transcode_raw_i32_to_varint(&bytes, &mut out)?;
+/// assert_eq!(out, [0x01, 0xac, 0x02]);
+/// assert!(transcode_raw_i32_to_varint(&bytes[1..], &mut out).is_err());
+/// # Ok::<(), zigzag::LengthError>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn transcode_raw_i32_to_varint(bytes: &[u8], out: &mut Vec<u8>) -> Result<(), LengthError> {
+    const VALUE_SIZE: usize = core::mem::size_of::<i32>();
+    if !bytes.len().is_multiple_of(VALUE_SIZE) {
+        return Err(LengthError {
+            len: bytes.len(),
+            value_size: VALUE_SIZE,
+        });
+    }
+
+    for chunk in bytes.chunks_exact(VALUE_SIZE) {
+        let mut raw = [0; VALUE_SIZE];
+        raw.copy_from_slice(chunk);
+        let x: u32 = i32::from_ne_bytes(raw).zigzag_encode();
+        write_uvarint(u64::from(x), out);
+    }
+    Ok(())
+}
+
+/// Migrates fixed-width zigzag-encoded `i32`s, stored as little-endian
+/// `u32`s, to zigzag-encoded varints appended to `out`.
+///
+/// Since the zigzag encoding of a value doesn't depend on the width it is
+/// computed at, each stored `u32` is already the encoding of its value at
+/// 64-bit width and is written as a varint unchanged. Decoding the output
+/// with [`read_zigzag_varint`] therefore yields exactly the values decoded
+/// from the input.
+///
+/// # Errors
+///
+/// Returns [`LengthError`] if the length of `input` isn't a multiple of
+/// four, in which case nothing is appended to `out`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::migrate_fixed_to_varint_i32;
+///
+/// let input = [0x01, 0, 0, 0, 0x2c, 0x01, 0, 0];
+/// let mut out = Vec::new();
+/// migrate_fixed_to_varint_i32(&input, &mut out)?;
+/// assert_eq!(out, [0x01, 0xac, 0x02]);
+/// # Ok::<(), zigzag::LengthError>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn migrate_fixed_to_varint_i32(input: &[u8], out: &mut Vec<u8>) -> Result<(), LengthError> {
+    const VALUE_SIZE: usize = core::mem::size_of::<u32>();
+    if !input.len().is_multiple_of(VALUE_SIZE) {
+        return Err(LengthError {
+            len: input.len(),
+            value_size: VALUE_SIZE,
+        });
+    }
+
+    for chunk in input.chunks_exact(VALUE_SIZE) {
+        let mut raw = [0; VALUE_SIZE];
+        raw.copy_from_slice(chunk);
+        write_uvarint(u64::from(u32::from_le_bytes(raw)), out);
+    }
+    Ok(())
+}
+
+/// A reusable buffer for writing zigzag-encoded varints.
+///
+/// This owns its `Vec<u8>`, so a single `VarintBuf` can be kept around and
+/// [`clear`](Self::clear)ed between messages instead of threading a `&mut
+/// Vec<u8>` through every call.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::VarintBuf;
+///
+/// let mut buf = VarintBuf::new();
+/// buf.push_zigzag(-1);
+/// buf.push_zigzag(150);
+/// assert_eq!(buf.as_bytes(), [0x01, 0xac, 0x02]);
+///
+/// buf.clear();
+/// buf.push_zigzag(-2);
+/// assert_eq!(buf.take(), [0x03]);
+/// assert!(buf.as_bytes().is_empty());
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct VarintBuf {
+    buf: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl VarintBuf {
+    /// Creates an empty buffer.
+    #[inline]
+    pub const fn new() -> Self {
+        VarintBuf { buf: Vec::new() }
+    }
+
+    /// Creates an empty buffer with room for at least `capacity` bytes.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        VarintBuf {
+            buf: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Appends `x` as a zigzag-encoded varint.
+    ///
+    /// Whenever the buffer runs out of room, it grows by enough for several
+    /// more values at once rather than by the bytes needed for `x` alone.
+    #[inline]
+    pub fn push_zigzag(&mut self, x: i64) {
+        if self.buf.capacity() - self.buf.len() < MAX_VARINT_LEN {
+            self.buf.reserve(MAX_VARINT_LEN * 8);
+        }
+        write_zigzag_varint(x, &mut self.buf);
+    }
+
+    /// Removes every byte from the buffer, keeping its allocation.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+
+    /// Returns the bytes written so far.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Takes the bytes written so far, leaving the buffer empty.
+    ///
+    /// The buffer's allocation goes along with the bytes, so the next write
+    /// allocates anew.
+    #[inline]
+    pub fn take(&mut self) -> Vec<u8> {
+        core::mem::take(&mut self.buf)
+    }
+}
+
+/// Information about the layout of a varint, as returned by
+/// [`read_zigzag_varint_verbose`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VarintInfo {
+    /// The number of bytes the varint occupies, including the final byte.
+    pub len: usize,
+    /// Whether the varint uses the fewest bytes possible for its value.
+    ///
+    /// A varint is not minimal if it ends in one or more redundant `0x00`
+    /// groups, such as `[0x80, 0x00]` for zero.
+    pub minimal: bool,
+    /// The unsigned value of the varint before zigzag decoding.
+    pub raw: u64,
+}
+
+/// Reads an unsigned varint from the start of `input`.
+///
+/// On success, returns the value and the number of bytes it occupied. Any
+/// bytes after the varint are ignored.
+///
+/// # Errors
+///
+/// Returns [`VarintError::Truncated`] if `input` ends before the final byte of
+/// the varint, and [`VarintError::Overflow`] if the varint encodes a value
+/// that doesn't fit in a `u64`.
+///
+/// Nine bytes hold 63 bits of payload, so the tenth byte of a varint may only
+/// be `0x00` or `0x01`: anything else either sets bits beyond bit 63 or
+/// continues the varint past [`MAX_VARINT_LEN`] bytes, and is an overflow.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::read_uvarint;
+/// use zigzag::VarintError;
+///
+/// assert_eq!(read_uvarint(&[0x96, 0x01]), Ok((150, 2)));
+///
+/// let mut max = [0xff; 10];
+/// max[9] = 0x01;
+/// assert_eq!(read_uvarint(&max), Ok((u64::MAX, 10)));
+/// max[9] = 0x02;
+/// assert_eq!(read_uvarint(&max), Err(VarintError::Overflow));
+/// ```
+pub fn read_uvarint(input: &[u8]) -> Result<(u64, usize), VarintError> {
+    let mut value = 0u64;
+    for (i, &byte) in input.iter().take(MAX_VARINT_LEN).enumerate() {
+        let payload = u64::from(byte & PAYLOAD_BITS);
+        // The tenth byte only has room for the single remaining bit of a
+        // 64-bit value.
+        if i == MAX_VARINT_LEN - 1 && byte > 1 {
+            return Err(VarintError::Overflow);
+        }
+        value |= payload << (7 * i);
+        if byte & CONTINUATION_BIT == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(VarintError::Truncated)
+}
+
+/// Reads a zigzag-encoded varint from the start of `input`.
+///
+/// On success, returns the decoded value and the number of bytes it occupied.
+/// Any bytes after the varint are ignored.
+///
+/// # Errors
+///
+/// See [`read_uvarint`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::read_zigzag_varint;
+///
+/// assert_eq!(read_zigzag_varint(&[0x01]), Ok((-1, 1)));
+/// assert_eq!(read_zigzag_varint(&[0xac, 0x02, 0xff]), Ok((150, 2)));
+/// ```
+#[inline]
+pub fn read_zigzag_varint(input: &[u8]) -> Result<(i64, usize), VarintError> {
+    read_uvarint(input).map(|(raw, len)| (raw.zigzag_decode(), len))
+}
+
+/// Decodes a zigzag-encoded varint which makes up the whole of `input`.
+///
+/// This suits fields whose length is stored separately, such as a value in
+/// a key-value store.
+///
+/// # Errors
+///
+/// Returns [`DecodeError::TrailingGarbage`] if `input` continues past the end
+/// of the varint, and otherwise the error [`read_zigzag_varint`] returns,
+/// converted into a [`DecodeError`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::decode_varint_from_slice;
+/// use zigzag::DecodeError;
+///
+/// assert_eq!(decode_varint_from_slice(&[0xac, 0x02]), Ok(150));
+/// assert_eq!(decode_varint_from_slice(&[0xac]), Err(DecodeError::Truncated { needed: 1 }));
+/// assert_eq!(decode_varint_from_slice(&[0xac, 0x02, 0x00]), Err(DecodeError::TrailingGarbage));
+/// ```
+pub fn decode_varint_from_slice(input: &[u8]) -> Result<i64, DecodeError> {
+    let (value, len) = read_zigzag_varint(input)?;
+    if len != input.len() {
+        return Err(DecodeError::TrailingGarbage);
+    }
+    Ok(value)
+}
+
+/// Reads a zigzag-encoded varint, as written by [`encode_varint`], from the
+/// start of `input`.
+///
+/// This accepts protobuf's `sint32` and `sint64` and Avro's `int` and `long`
+/// encodings. On success, returns the decoded value and the number of bytes
+/// it occupied.
+///
+/// # Errors
+///
+/// See [`read_uvarint`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::decode_varint;
+///
+/// assert_eq!(decode_varint(&[0xfe, 0xff, 0xff, 0xff, 0x0f]), Ok((i64::from(i32::MAX), 5)));
+/// assert_eq!(decode_varint(&[0x7f, 0x80, 0x01]), Ok((-64, 1)));
+/// ```
+#[inline]
+pub fn decode_varint(input: &[u8]) -> Result<(i64, usize), VarintError> {
+    read_zigzag_varint(input)
+}
+
+/// Reads a zigzag-encoded varint from the start of `input`, rejecting values
+/// outside `min..=max`.
+///
+/// This enforces a protocol's range constraints while parsing untrusted
+/// input, instead of in a separate pass afterwards.
+///
+/// # Errors
+///
+/// See [`read_uvarint`]. Additionally returns [`VarintError::OutOfRange`] if
+/// the decoded value is less than `min` or greater than `max`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::read_zigzag_varint_clamped;
+/// use zigzag::VarintError;
+///
+/// assert_eq!(read_zigzag_varint_clamped(&[0x01], -10, 10), Ok((-1, 1)));
+/// assert_eq!(read_zigzag_varint_clamped(&[0x2a], -10, 10), Err(VarintError::OutOfRange));
+/// ```
+#[inline]
+pub fn read_zigzag_varint_clamped(
+    input: &[u8],
+    min: i64,
+    max: i64,
+) -> Result<(i64, usize), VarintError> {
+    let (value, len) = read_zigzag_varint(input)?;
+    if value < min || value > max {
+        return Err(VarintError::OutOfRange);
+    }
+    Ok((value, len))
+}
+
+/// Reads a zigzag-encoded varint from the start of `input`, if there is one.
+///
+/// Returns `(None, 0)` if `input` is empty, and otherwise behaves like
+/// [`read_zigzag_varint`]. This lets formats with optional fields tell an
+/// absent value apart from one that is present and zero, which is encoded as
+/// `0x00`.
+///
+/// # Errors
+///
+/// See [`read_uvarint`]. Empty input is not an error.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::read_optional_zigzag_varint;
+///
+/// assert_eq!(read_optional_zigzag_varint(&[]), Ok((None, 0)));
+/// assert_eq!(read_optional_zigzag_varint(&[0x00]), Ok((Some(0), 1)));
+/// assert_eq!(read_optional_zigzag_varint(&[0x03, 0x00]), Ok((Some(-2), 1)));
+/// assert!(read_optional_zigzag_varint(&[0x80]).is_err());
+/// ```
+#[inline]
+pub fn read_optional_zigzag_varint(input: &[u8]) -> Result<(Option<i64>, usize), VarintError> {
+    if input.is_empty() {
+        return Ok((None, 0));
+    }
+    read_zigzag_varint(input).map(|(value, len)| (Some(value), len))
+}
+
+/// Reads a zigzag-encoded varint from the start of `input`, returning the
+/// decoded value and the rest of the input.
+///
+/// This is the same as [`read_zigzag_varint`], but shaped for parser
+/// combinators such as `nom`: successive calls can be chained by passing the
+/// returned tail to the next one.
+///
+/// # Errors
+///
+/// See [`read_uvarint`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::parse_zigzag_varint;
+///
+/// let input = [0x03, 0xac, 0x02];
+/// let (a, rest) = parse_zigzag_varint(&input)?;
+/// let (b, rest) = parse_zigzag_varint(rest)?;
+/// assert_eq!((a, b), (-2, 150));
+/// assert!(rest.is_empty());
+/// # Ok::<(), zigzag::VarintError>(())
+/// ```
+#[inline]
+pub fn parse_zigzag_varint(input: &[u8]) -> Result<(i64, &[u8]), VarintError> {
+    read_zigzag_varint(input).map(|(value, len)| (value, &input[len..]))
+}
+
+/// Decodes the zigzag-encoded varint at the start of `input` without
+/// consuming it.
+///
+/// Since `input` is borrowed, no cursor moves; this is a convenience for
+/// lookahead in parsers built on [`parse_zigzag_varint`], which can look at
+/// the next value before deciding whether to consume it.
+///
+/// # Errors
+///
+/// See [`read_uvarint`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::{parse_zigzag_varint, peek_len, peek_zigzag_varint};
+///
+/// let input = [0xac, 0x02, 0x01];
+/// assert_eq!(peek_zigzag_varint(&input), Ok(150));
+/// assert_eq!(peek_len(&input), Ok(2));
+///
+/// let (value, rest) = parse_zigzag_varint(&input)?;
+/// assert_eq!((value, rest.len()), (150, 1));
+/// # Ok::<(), zigzag::VarintError>(())
+/// ```
+#[inline]
+pub fn peek_zigzag_varint(input: &[u8]) -> Result<i64, VarintError> {
+    read_zigzag_varint(input).map(|(value, _)| value)
+}
+
+/// Returns the number of bytes the varint at the start of `input` occupies,
+/// which is how many bytes reading it would consume.
+///
+/// # Errors
+///
+/// See [`read_uvarint`].
+#[inline]
+pub fn peek_len(input: &[u8]) -> Result<usize, VarintError> {
+    read_uvarint(input).map(|(_, len)| len)
+}
+
+const CONTINUE_TAG: u8 = 0;
+const BREAK_TAG: u8 = 1;
+
+/// Appends `cf` to `out` as a tag byte—`0` for [`Continue`] and `1` for
+/// [`Break`]—followed by the inner value as a zigzag-encoded varint.
+///
+/// [`Continue`]: ControlFlow::Continue
+/// [`Break`]: ControlFlow::Break
+///
+/// # Examples
+///
+/// ```
+/// use std::ops::ControlFlow;
+/// use zigzag::varint::encode_control_flow;
+///
+/// let mut out = Vec::new();
+/// encode_control_flow(ControlFlow::Continue(-1), &mut out);
+/// encode_control_flow(ControlFlow::Break(150), &mut out);
+/// assert_eq!(out, [0x00, 0x01, 0x01, 0xac, 0x02]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_control_flow(cf: ControlFlow<i64, i64>, out: &mut Vec<u8>) {
+    let (tag, value) = match cf {
+        ControlFlow::Continue(value) => (CONTINUE_TAG, value),
+        ControlFlow::Break(value) => (BREAK_TAG, value),
+    };
+    out.push(tag);
+    write_zigzag_varint(value, out);
+}
+
+/// Reads a value written by [`encode_control_flow`] from the start of
+/// `input`.
+///
+/// On success, returns the value and the number of bytes it occupied,
+/// including its tag byte.
+///
+/// # Errors
+///
+/// Returns [`VarintError::Truncated`] if `input` is empty, and
+/// [`VarintError::OutOfRange`] if the tag byte is neither `0` nor `1`.
+/// Otherwise, see [`read_uvarint`].
+///
+/// # Examples
+///
+/// ```
+/// use std::ops::ControlFlow;
+/// use zigzag::varint::decode_control_flow;
+///
+/// assert_eq!(decode_control_flow(&[0x01, 0x03]), Ok((ControlFlow::Break(-2), 2)));
+/// assert!(decode_control_flow(&[0x02, 0x03]).is_err());
+/// ```
+pub fn decode_control_flow(input: &[u8]) -> Result<(ControlFlow<i64, i64>, usize), VarintError> {
+    let (&tag, rest) = input.split_first().ok_or(VarintError::Truncated)?;
+    let (value, len) = read_zigzag_varint(rest)?;
+    let cf = match tag {
+        CONTINUE_TAG => ControlFlow::Continue(value),
+        BREAK_TAG => ControlFlow::Break(value),
+        _ => return Err(VarintError::OutOfRange),
+    };
+    Ok((cf, 1 + len))
+}
+
+/// Reads a zigzag-encoded varint from the start of `input` without checking
+/// that it is well formed.
+///
+/// This skips the bounds and overflow checks of [`read_zigzag_varint`], for
+/// hot loops over input that has already been validated. It returns the same
+/// result as `read_zigzag_varint(input).unwrap()` whenever the safety
+/// requirements are met.
+///
+/// # Safety
+///
+/// `input` must start with a complete varint that
+/// [`read_zigzag_varint`] would accept: a byte without the continuation bit
+/// must occur within the first [`MAX_VARINT_LEN`] bytes of `input`. Reading
+/// a truncated varint reads out of bounds, which is undefined behavior.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::read_zigzag_varint_unchecked;
+///
+/// let input = [0xac, 0x02, 0x01];
+/// // SAFETY: `input` starts with a complete, valid varint.
+/// assert_eq!(unsafe { read_zigzag_varint_unchecked(&input) }, (150, 2));
+/// ```
+#[inline]
+pub unsafe fn read_zigzag_varint_unchecked(input: &[u8]) -> (i64, usize) {
+    let mut value = 0u64;
+    macro_rules! step {
+        ($($i:literal)*) => {$(
+            // SAFETY: the caller guarantees that the varint ends within
+            // `input`, and each step returns at its final byte.
+            let byte = *input.get_unchecked($i);
+            value |= u64::from(byte & PAYLOAD_BITS) << (7 * $i);
+            if byte & CONTINUATION_BIT == 0 {
+                return (value.zigzag_decode(), $i + 1);
+            }
+        )*};
+    }
+    step!(0 1 2 3 4 5 6 7 8 9);
+    // SAFETY: the caller guarantees that the varint ends within its first
+    // `MAX_VARINT_LEN` bytes.
+    core::hint::unreachable_unchecked()
+}
+
+/// Reads a zigzag-encoded varint from the start of `input`, decoding varints
+/// of up to eight bytes without a branch per byte.
+///
+/// The first eight bytes are loaded as a single little-endian word. The end
+/// of the varint is the lowest byte whose continuation bit is clear, which a
+/// trailing zero count finds at once, and the payload groups are then packed
+/// together with a few masked shifts. Varints longer than eight bytes fall
+/// back to [`read_zigzag_varint`], whose result this always matches.
+///
+/// This is only available on little-endian targets, where loading the word
+/// needs no byte swap. Whether it is faster than the byte loop depends on the
+/// target and on the mix of varint lengths, since each decode's length is
+/// needed before the next can start, whereas a well-predicted byte loop can
+/// run ahead speculatively; the `varint` benchmark compares the two.
+///
+/// # Errors
+///
+/// See [`read_uvarint`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::read_zigzag_varint_swar;
+///
+/// assert_eq!(read_zigzag_varint_swar(&[0xac, 0x02, 0xff]), Ok((150, 2)));
+/// assert_eq!(read_zigzag_varint_swar(&[0xff; 9]), Err(zigzag::VarintError::Truncated));
+/// ```
+#[cfg(target_endian = "little")]
+#[inline]
+pub fn read_zigzag_varint_swar(input: &[u8]) -> Result<(i64, usize), VarintError> {
+    use core::convert::TryInto;
+
+    const CONTINUATION_BITS: u64 = 0x8080_8080_8080_8080;
+
+    let word = match input.get(..8) {
+        Some(bytes) => u64::from_le_bytes(bytes.try_into().unwrap()),
+        // The word can't be loaded in one go, which only happens at the very
+        // end of the input.
+        None => return read_zigzag_varint(input),
+    };
+
+    let ends = !word & CONTINUATION_BITS;
+    let len = ends.trailing_zeros() as usize / 8 + 1;
+
+    // Keep only the payload bits of the varint's own bytes, which are the
+    // bits up to the lowest set bit of `ends`, then pack the seven-bit groups
+    // together pairwise, then in fours, then in eights.
+    let mask = ends ^ ends.wrapping_sub(1);
+    let mut x = word & mask & !CONTINUATION_BITS;
+    x = (x & 0x007f_007f_007f_007f) | ((x & 0x7f00_7f00_7f00_7f00) >> 1);
+    x = (x & 0x0000_3fff_0000_3fff) | ((x & 0x3fff_0000_3fff_0000) >> 2);
+    x = (x & 0x0000_0000_0fff_ffff) | ((x & 0x0fff_ffff_0000_0000) >> 4);
+    if len <= 8 {
+        return Ok((x.zigzag_decode(), len));
+    }
+
+    // The varint continues past the first word, into its last two bytes.
+    match input[8..] {
+        [byte, ..] if byte & CONTINUATION_BIT == 0 => {
+            Ok(((x | u64::from(byte) << 56).zigzag_decode(), 9))
+        }
+        [byte, last, ..] if last <= 1 => {
+            let x = x | u64::from(byte & PAYLOAD_BITS) << 56 | u64::from(last) << 63;
+            Ok((x.zigzag_decode(), 10))
+        }
+        [_, _, ..] => Err(VarintError::Overflow),
+        _ => Err(VarintError::Truncated),
+    }
+}
+
+/// Reads a zigzag-encoded varint from the start of `input`, along with
+/// details about how it was laid out.
+///
+/// This is intended for tooling that inspects varint streams; prefer
+/// [`read_zigzag_varint`] when only the value is needed.
+///
+/// # Errors
+///
+/// See [`read_uvarint`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::{read_zigzag_varint_verbose, VarintInfo};
+///
+/// let (value, info) = read_zigzag_varint_verbose(&[0x80, 0x00]).unwrap();
+/// assert_eq!(value, 0);
+/// assert_eq!(info, VarintInfo { len: 2, minimal: false, raw: 0 });
+///
+/// let (value, info) = read_zigzag_varint_verbose(&[0x03]).unwrap();
+/// assert_eq!(value, -2);
+/// assert_eq!(info, VarintInfo { len: 1, minimal: true, raw: 3 });
+/// ```
+pub fn read_zigzag_varint_verbose(input: &[u8]) -> Result<(i64, VarintInfo), VarintError> {
+    let (raw, len) = read_uvarint(input)?;
+    let info = VarintInfo {
+        len,
+        minimal: len == 1 || input[len - 1] != 0,
+        raw,
+    };
+    Ok((raw.zigzag_decode(), info))
+}
+
+/// Decodes exactly `N` zigzag-encoded varints from the start of `input` into
+/// an array, without allocating.
+///
+/// On success, returns the values and the number of bytes they occupied. Any
+/// bytes past the `N`th varint are left unread.
+///
+/// # Errors
+///
+/// See [`read_uvarint`]. In particular, returns [`VarintError::Truncated`] if
+/// `input` holds fewer than `N` varints.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::decode_zigzag_varints_array;
+///
+/// let input = [0x01, 0xac, 0x02, 0x04, 0xff];
+/// assert_eq!(decode_zigzag_varints_array::<2>(&input), Ok(([-1, 150], 3)));
+/// assert!(decode_zigzag_varints_array::<5>(&input).is_err());
+/// ```
+pub fn decode_zigzag_varints_array<const N: usize>(
+    input: &[u8],
+) -> Result<([i64; N], usize), VarintError> {
+    let mut values = [0; N];
+    let mut offset = 0;
+    for value in values.iter_mut() {
+        let (x, len) = read_zigzag_varint(&input[offset..])?;
+        *value = x;
+        offset += len;
+    }
+    Ok((values, offset))
+}
+
+/// Decodes a stream of zigzag-encoded varints into any collection.
+///
+/// Decoding stops at the first malformed varint, whose error is returned
+/// instead of the collection.
+///
+/// # Errors
+///
+/// Returns an error if `input` doesn't consist entirely of valid varints.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeSet;
+/// use zigzag::varint::decode_varint_collect;
+///
+/// let input = [0x04, 0x01, 0x04, 0x02];
+///
+/// let values: Vec<i64> = decode_varint_collect(&input)?;
+/// assert_eq!(values, [2, -1, 2, 1]);
+///
+/// let set: BTreeSet<i64> = decode_varint_collect(&input)?;
+/// assert_eq!(set.into_iter().collect::<Vec<_>>(), [-1, 1, 2]);
+///
+/// assert!(decode_varint_collect::<Vec<i64>>(&[0x01, 0x80]).is_err());
+/// # Ok::<(), zigzag::VarintError>(())
+/// ```
+pub fn decode_varint_collect<C>(input: &[u8]) -> Result<C, VarintError>
+where
+    C: FromIterator<i64>,
+{
+    ZigZagVarintIter::new(input).collect()
+}
+
+/// Decodes a stream of zigzag-encoded varints, appending the values to an
+/// existing collection.
+///
+/// # Errors
+///
+/// Returns an error if `input` doesn't consist entirely of valid varints.
+/// Decoding stops at the first malformed varint, but the values before it
+/// have already been added to `sink` and remain there.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::VecDeque;
+/// use zigzag::varint::decode_varint_extend;
+///
+/// let mut values = VecDeque::from(vec![7]);
+/// decode_varint_extend(&[0x01, 0x04], &mut values)?;
+/// assert_eq!(values, [7, -1, 2]);
+///
+/// assert!(decode_varint_extend(&[0x02, 0x80], &mut values).is_err());
+/// assert_eq!(values, [7, -1, 2, 1]);
+/// # Ok::<(), zigzag::VarintError>(())
+/// ```
+pub fn decode_varint_extend<C>(input: &[u8], sink: &mut C) -> Result<(), VarintError>
+where
+    C: Extend<i64>,
+{
+    let mut error = None;
+    sink.extend(
+        ZigZagVarintIter::new(input).map_while(|item| item.map_err(|err| error = Some(err)).ok()),
+    );
+    error.map_or(Ok(()), Err)
+}
+
+/// Decodes zigzag-encoded varints from `input` into `ring` until it holds
+/// `cap` values or `input` runs out, and returns the number of bytes
+/// consumed.
+///
+/// This bounds the memory a streaming decoder uses when its consumer is
+/// slower than it: once `ring` is full, the caller drains some values and
+/// calls this again with the input past the returned offset.
+///
+/// # Errors
+///
+/// Returns an error if a varint before the point where decoding stops is
+/// malformed. The values before it have already been pushed to `ring` and
+/// remain there.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::VecDeque;
+/// use zigzag::varint::decode_into_ringbuf;
+///
+/// let input = [0x01, 0xac, 0x02, 0x04];
+/// let mut ring = VecDeque::new();
+/// let consumed = decode_into_ringbuf(&input, &mut ring, 2)?;
+/// assert_eq!((consumed, ring.len()), (3, 2));
+///
+/// ring.pop_front();
+/// let rest = decode_into_ringbuf(&input[consumed..], &mut ring, 2)?;
+/// assert_eq!(rest, 1);
+/// assert_eq!(ring, [150, 2]);
+/// # Ok::<(), zigzag::VarintError>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_into_ringbuf(
+    input: &[u8],
+    ring: &mut VecDeque<i64>,
+    cap: usize,
+) -> Result<usize, VarintError> {
+    let mut offset = 0;
+    while ring.len() < cap && offset < input.len() {
+        let (value, len) = read_zigzag_varint(&input[offset..])?;
+        ring.push_back(value);
+        offset += len;
+    }
+    Ok(offset)
+}
+
+/// An iterator that lazily decodes a stream of zigzag-encoded varints.
+///
+/// Each item is the result of reading the next varint. If a varint is
+/// malformed, its error is yielded and the iterator ends, since the position
+/// of any following varint is unknown.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::ZigZagVarintIter;
+/// use zigzag::VarintError;
+///
+/// let mut iter = ZigZagVarintIter::new(&[0x03, 0xac, 0x02, 0x80]);
+/// assert_eq!(iter.next(), Some(Ok(-2)));
+/// assert_eq!(iter.next(), Some(Ok(150)));
+/// assert_eq!(iter.next(), Some(Err(VarintError::Truncated)));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ZigZagVarintIter<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> ZigZagVarintIter<'a> {
+    /// Creates an iterator over the varints in `input`.
+    #[inline]
+    pub fn new(input: &'a [u8]) -> Self {
+        ZigZagVarintIter { input }
+    }
+
+    /// Returns the bytes which haven't been decoded yet, or nothing after an
+    /// error.
+    #[inline]
+    pub fn remaining(&self) -> &'a [u8] {
+        self.input
+    }
+}
+
+impl Iterator for ZigZagVarintIter<'_> {
+    type Item = Result<i64, VarintError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_empty() {
+            return None;
+        }
+        match read_zigzag_varint(self.input) {
+            Ok((value, len)) => {
+                self.input = &self.input[len..];
+                Some(Ok(value))
+            }
+            Err(err) => {
+                self.input = &[];
+                Some(Err(err))
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.input.len();
+        (len.div_ceil(MAX_VARINT_LEN), Some(len))
+    }
+}
+
+impl FusedIterator for ZigZagVarintIter<'_> {}
+
+/// A cursor that lazily decodes a stream of zigzag-encoded varints while
+/// tracking its offset into the stream.
+///
+/// Unlike [`ZigZagVarintIter`], the cursor doesn't discard the rest of the
+/// stream when a varint is malformed. It stays at the start of that varint,
+/// so [`position`](Self::position) gives the offset of the bad bytes, and
+/// yields nothing more until it's moved with
+/// [`set_position`](Self::set_position) or [`resync`](Self::resync).
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::VarintCursor;
+/// use zigzag::DecodeError;
+///
+/// // -2, then 11 bytes with their continuation bits set, then 150.
+/// let mut input = vec![0x03];
+/// input.extend_from_slice(&[0xff; 11]);
+/// input.extend_from_slice(&[0x7f, 0xac, 0x02]);
+///
+/// let mut cursor = VarintCursor::new(&input);
+/// assert_eq!(cursor.next(), Some(Ok(-2)));
+/// assert_eq!(cursor.next(), Some(Err(DecodeError::Overflow)));
+/// assert_eq!(cursor.position(), 1);
+/// assert_eq!(cursor.next(), None);
+///
+/// cursor.resync();
+/// assert_eq!(cursor.position(), 13);
+/// assert_eq!(cursor.remaining(), [0xac, 0x02]);
+/// assert_eq!(cursor.next(), Some(Ok(150)));
+/// assert_eq!(cursor.next(), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct VarintCursor<'a> {
+    input: &'a [u8],
+    pos: usize,
+    stalled: bool,
+}
+
+impl<'a> VarintCursor<'a> {
+    /// Creates a cursor at the start of `input`.
+    #[inline]
+    pub const fn new(input: &'a [u8]) -> Self {
+        VarintCursor {
+            input,
+            pos: 0,
+            stalled: false,
+        }
+    }
+
+    /// Returns the offset of the next varint to decode, which after an error
+    /// is the offset of the malformed varint.
+    #[inline]
+    pub const fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Moves the cursor to offset `pos`, resuming decoding after an error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos` is past the end of the input.
+    #[inline]
+    pub fn set_position(&mut self, pos: usize) {
+        assert!(pos <= self.input.len(), "position out of bounds");
+        self.pos = pos;
+        self.stalled = false;
+    }
+
+    /// Moves the cursor past the end of the varint at its position, whether
+    /// or not it's well-formed.
+    ///
+    /// The end of a varint is the first byte without its continuation bit
+    /// set. After an overlong varint, the next varint is therefore assumed
+    /// to start after the next such byte.
+    pub fn resync(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest
+            .iter()
+            .position(|&byte| byte & CONTINUATION_BIT == 0)
+            .map_or(rest.len(), |i| i + 1);
+        self.stalled = false;
+    }
+
+    /// Returns the bytes from the cursor's position to the end of the input.
+    #[inline]
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.input[self.pos..]
+    }
+}
+
+impl Iterator for VarintCursor<'_> {
+    type Item = Result<i64, DecodeError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.stalled || self.pos == self.input.len() {
+            return None;
+        }
+        match read_zigzag_varint(&self.input[self.pos..]) {
+            Ok((value, len)) => {
+                self.pos += len;
+                Some(Ok(value))
+            }
+            Err(err) => {
+                self.stalled = true;
+                Some(Err(err.into()))
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.stalled {
+            return (0, Some(0));
+        }
+        let len = self.input.len() - self.pos;
+        (len.div_ceil(MAX_VARINT_LEN), Some(len))
+    }
+}
+
+/// Returns an iterator over the zigzag-encoded varints in `bytes`, yielding
+/// each value along with the offset of its first byte.
+///
+/// This allows building an index of a stream in the same pass that decodes
+/// it. Like [`ZigZagVarintIter`], the iterator yields the first error it
+/// encounters and then stops.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::decode_varint_with_offsets;
+///
+/// let values: Result<Vec<_>, _> = decode_varint_with_offsets(&[0x01, 0xac, 0x02, 0x04]).collect();
+/// assert_eq!(values, Ok(vec![(0, -1), (1, 150), (3, 2)]));
+/// ```
+pub fn decode_varint_with_offsets(
+    bytes: &[u8],
+) -> impl Iterator<Item = Result<(usize, i64), VarintError>> + '_ {
+    let mut iter = ZigZagVarintIter::new(bytes);
+    core::iter::from_fn(move || {
+        let offset = bytes.len() - iter.input.len();
+        iter.next()
+            .map(|result| result.map(|value| (offset, value)))
+    })
+}
+
+/// Decodes a stream of zigzag-encoded varints into a `Vec`.
+///
+/// This is the inverse of [`zigzag_encode_all`].
+///
+/// # Errors
+///
+/// Returns an error if `input` doesn't consist entirely of valid varints.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::{zigzag_decode_all, zigzag_encode_all};
+///
+/// let values = [0, -1, 150, i64::MIN];
+/// assert_eq!(zigzag_decode_all(&zigzag_encode_all(&values)), Ok(values.to_vec()));
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn zigzag_decode_all(input: &[u8]) -> Result<Vec<i64>, VarintError> {
+    decode_varint_collect(input)
+}
+
+/// Decodes a stream of zigzag-encoded varints, appending the values to
+/// `out`, and returns how many were decoded.
+///
+/// This computes the same values as [`zigzag_decode_all`], several times
+/// faster than decoding one varint at a time when most varints are one or two
+/// bytes long. The input is inspected a word at a time: eight one-byte
+/// varints, or four two-byte ones, are decoded together with a few masked
+/// shifts, and any other varint is decoded without a branch per byte, as by
+/// [`read_zigzag_varint_swar`]. With the `simd` feature, runs of sixteen
+/// one-byte varints are decoded with a single vector operation. The last
+/// fifteen bytes of `input` are decoded one varint at a time.
+///
+/// The `varint` benchmark compares this with the other decoders.
+///
+/// # Errors
+///
+/// Returns an error if `input` doesn't consist entirely of valid varints.
+/// The values before the malformed varint have already been appended to
+/// `out` and remain there.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::{decode_varints_into, zigzag_encode_all};
+/// use zigzag::DecodeError;
+///
+/// let values = [0, -1, 150, -64, 8191, i64::MIN, 3, 2, 1];
+/// let mut out = vec![7];
+/// assert_eq!(decode_varints_into(&zigzag_encode_all(&values), &mut out), Ok(9));
+/// assert_eq!(out[1..], values);
+///
+/// out.clear();
+/// assert_eq!(decode_varints_into(&[0x04, 0x80], &mut out), Err(DecodeError::Truncated { needed: 1 }));
+/// assert_eq!(out, [2]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_varints_into(input: &[u8], out: &mut Vec<i64>) -> Result<usize, DecodeError> {
+    use core::convert::TryInto;
+
+    const CONTINUATION_BITS: u64 = 0x8080_8080_8080_8080;
+
+    let start = out.len();
+    let mut pos = 0;
+    // Sixteen bytes hold a word to inspect and any varint starting in it.
+    while let Some(chunk) = input.get(pos..pos + 16) {
+        let word = u64::from_le_bytes(chunk[..8].try_into().unwrap());
+        let continuations = word & CONTINUATION_BITS;
+        if continuations == 0 {
+            #[cfg(feature = "simd")]
+            if let Some(decoded) = crate::simd::decode_one_byte_varints(chunk.try_into().unwrap()) {
+                out.extend_from_slice(&decoded);
+                pos += 16;
+                continue;
+            }
+            // Each byte decodes to a value in `-64..64`, which is the byte
+            // itself read as an `i8`.
+            let half = (word >> 1) & 0x7f7f_7f7f_7f7f_7f7f;
+            let signs = (word & 0x0101_0101_0101_0101) * 0xff;
+            let decoded = (half ^ signs).to_le_bytes();
+            out.extend_from_slice(&decoded.map(|byte| i64::from(byte as i8)));
+            pos += 8;
+        } else if continuations == 0x0080_0080_0080_0080 {
+            // Four two-byte varints, whose payloads are packed into 16-bit
+            // lanes at once.
+            let x = (word & 0x007f_007f_007f_007f) | ((word & 0x7f00_7f00_7f00_7f00) >> 1);
+            out.extend((0..4).map(|i| ((x >> (16 * i)) & 0xffff).zigzag_decode()));
+            pos += 8;
+        } else {
+            // The varint ends at the lowest byte whose continuation bit is
+            // clear, and its payload groups are packed together as in
+            // `read_zigzag_varint_swar`.
+            let ends = !word & CONTINUATION_BITS;
+            let mask = ends ^ ends.wrapping_sub(1);
+            let mut x = word & mask & !CONTINUATION_BITS;
+            x = (x & 0x007f_007f_007f_007f) | ((x & 0x7f00_7f00_7f00_7f00) >> 1);
+            x = (x & 0x0000_3fff_0000_3fff) | ((x & 0x3fff_0000_3fff_0000) >> 2);
+            x = (x & 0x0000_0000_0fff_ffff) | ((x & 0x0fff_ffff_0000_0000) >> 4);
+            if ends != 0 {
+                out.push(x.zigzag_decode());
+                pos += ends.trailing_zeros() as usize / 8 + 1;
+                continue;
+            }
+            // The varint continues past the word, into its last two bytes.
+            match chunk[8..] {
+                [byte, ..] if byte & CONTINUATION_BIT == 0 => {
+                    out.push((x | (u64::from(byte) << 56)).zigzag_decode());
+                    pos += 9;
+                }
+                [byte, last, ..] if last <= 1 => {
+                    let x = x | (u64::from(byte & PAYLOAD_BITS) << 56) | (u64::from(last) << 63);
+                    out.push(x.zigzag_decode());
+                    pos += 10;
+                }
+                _ => return Err(DecodeError::Overflow),
+            }
+        }
+    }
+    let mut rest = &input[pos..];
+    while !rest.is_empty() {
+        let (value, len) = read_zigzag_varint(rest)?;
+        out.push(value);
+        rest = &rest[len..];
+    }
+    Ok(out.len() - start)
+}
+
+/// Rewrites a stream of varints so that every varint is minimally encoded.
+///
+/// Most streams are already minimal, so `input` is checked first and
+/// returned as [`Cow::Borrowed`] if no varint needs to change. Otherwise, a
+/// canonical copy is returned as [`Cow::Owned`]. The values in the stream are
+/// unaffected either way.
+///
+/// # Errors
+///
+/// Returns an error if `input` doesn't consist entirely of valid varints.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use zigzag::varint::canonicalize_varints;
+///
+/// let minimal = [0x01, 0xac, 0x02];
+/// assert!(matches!(canonicalize_varints(&minimal), Ok(Cow::Borrowed(_))));
+///
+/// let padded = [0x01, 0x80, 0x00, 0xac, 0x82, 0x00];
+/// let canonical = canonicalize_varints(&padded).unwrap();
+/// assert!(matches!(canonical, Cow::Owned(_)));
+/// assert_eq!(*canonical, [0x01, 0x00, 0xac, 0x02]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn canonicalize_varints(input: &[u8]) -> Result<Cow<'_, [u8]>, VarintError> {
+    let mut offset = 0;
+    while offset < input.len() {
+        let (_, info) = read_zigzag_varint_verbose(&input[offset..])?;
+        if !info.minimal {
+            break;
+        }
+        offset += info.len;
+    }
+    if offset == input.len() {
+        return Ok(Cow::Borrowed(input));
+    }
+
+    let mut out = input[..offset].to_vec();
+    while offset < input.len() {
+        let (raw, len) = read_uvarint(&input[offset..])?;
+        write_uvarint(raw, &mut out);
+        offset += len;
+    }
+    Ok(Cow::Owned(out))
+}
+
+/// Splits a stream of varints into at most `approx_chunks` sub-slices of
+/// roughly equal length, each holding only whole varints.
+///
+/// Every byte with its continuation bit clear ends a varint, so each chunk
+/// is cut just after the first such byte at or past its target length. The
+/// chunks can then be decoded independently, for example by separate worker
+/// threads, with the same results as decoding `input` in one go. An
+/// `approx_chunks` of zero is treated as one, and empty input yields no
+/// chunks.
+///
+/// Chunks may be shorter or fewer than requested when varints straddle the
+/// target split points. Trailing bytes of a truncated varint end up in the
+/// last chunk, so decoding it fails just like decoding `input` would.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::{split_zigzag_varint_chunks, zigzag_decode_all, zigzag_encode_all};
+///
+/// let input = zigzag_encode_all(&[1, -1000, 2, 3, 1_000_000, -4]);
+/// let chunks = split_zigzag_varint_chunks(&input, 3);
+/// assert_eq!(chunks, [&input[..3], &input[3..8], &input[8..]]);
+/// assert_eq!(zigzag_decode_all(chunks[1]), Ok(vec![2, 3, 1_000_000]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn split_zigzag_varint_chunks(input: &[u8], approx_chunks: usize) -> Vec<&[u8]> {
+    let target = input.len().div_ceil(approx_chunks.max(1));
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < input.len() {
+        let min_end = (start + target).min(input.len());
+        let end = input[min_end - 1..]
+            .iter()
+            .position(|&byte| byte & CONTINUATION_BIT == 0)
+            .map_or(input.len(), |i| min_end + i);
+        chunks.push(&input[start..end]);
+        start = end;
+    }
+    chunks
+}
+
+/// A resumable decoder for zigzag-encoded varints which is fed one byte at a
+/// time.
+///
+/// This allows decoding a varint that is split across several buffers, such
+/// as network packets, without first copying it into a contiguous buffer. The
+/// decoder resets itself after every completed value or error, so it can be
+/// fed an entire stream.
+///
+/// The same goes for asynchronous readers: with the `tokio` feature, the
+/// methods of `zigzag::async_io::AsyncZigZagReadExt` keep one of these
+/// across polls, so a read which returns in the middle of a varint picks up
+/// where it left off.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::VarintDecoderState;
+///
+/// let mut decoder = VarintDecoderState::new();
+/// let bytes = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+///
+/// for &byte in &bytes[..9] {
+///     assert_eq!(decoder.feed(byte), None);
+/// }
+/// assert_eq!(decoder.feed(bytes[9]), Some(Ok(i64::MIN)));
+/// assert!(!decoder.is_mid_value());
+/// ```
+///
+/// Since it yields `Option`s, the decoder composes with iterator combinators:
+///
+/// ```
+/// use zigzag::varint::VarintDecoderState;
+///
+/// let mut decoder = VarintDecoderState::new();
+/// let values: Result<Vec<i64>, _> = [0x01, 0xac, 0x02, 0x04]
+///     .iter()
+///     .filter_map(|&byte| decoder.feed(byte))
+///     .collect();
+/// assert_eq!(values.unwrap(), [-1, 150, 2]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VarintDecoderState {
+    value: u64,
+    len: usize,
+}
+
+impl VarintDecoderState {
+    /// Creates a decoder which is waiting for the first byte of a varint.
+    #[inline]
+    pub const fn new() -> Self {
+        VarintDecoderState { value: 0, len: 0 }
+    }
+
+    /// Feeds the next byte of the stream into the decoder.
+    ///
+    /// Returns `None` if the varint continues past `byte`, or `Some` once the
+    /// varint is complete.
+    ///
+    /// # Errors
+    ///
+    /// Yields [`VarintError::Overflow`] if the varint being decoded doesn't
+    /// fit in 64 bits.
+    pub fn feed(&mut self, byte: u8) -> Option<Result<i64, VarintError>> {
+        if self.len == MAX_VARINT_LEN - 1 && byte > 1 {
+            *self = VarintDecoderState::new();
+            return Some(Err(VarintError::Overflow));
+        }
+        self.value |= u64::from(byte & PAYLOAD_BITS) << (7 * self.len);
+        self.len += 1;
+        if byte & CONTINUATION_BIT != 0 {
+            return None;
+        }
+        let value = self.value.zigzag_decode();
+        *self = VarintDecoderState::new();
+        Some(Ok(value))
+    }
+
+    /// Returns `true` if the decoder has been fed part of a varint.
+    ///
+    /// A stream which ends while this returns `true` is truncated.
+    #[inline]
+    pub fn is_mid_value(&self) -> bool {
+        self.len != 0
+    }
+}
+
+/// A varint decoder with a precomputed table for single-byte varints.
+///
+/// Single-byte varints encode the values `-64..=63`, which dominate many
+/// streams of small deltas. Their decodings are computed once, when the
+/// decoder is constructed, and looked up from then on. Longer varints fall
+/// back to [`read_zigzag_varint`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::CachedDecoder;
+///
+/// let decoder = CachedDecoder::new();
+/// assert_eq!(decoder.lookup(0x7f), Some(-64));
+/// assert_eq!(decoder.lookup(0xac), None);
+///
+/// assert_eq!(decoder.read_zigzag_varint(&[0x7e]), Ok((63, 1)));
+/// assert_eq!(decoder.read_zigzag_varint(&[0xac, 0x02]), Ok((150, 2)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct CachedDecoder {
+    table: [i64; 128],
+}
+
+impl CachedDecoder {
+    /// Creates a decoder, filling in its table.
+    pub const fn new() -> Self {
+        let mut table = [0; 128];
+        let mut i = 0;
+        while i < table.len() {
+            table[i] = (i as i64 >> 1) ^ -(i as i64 & 1);
+            i += 1;
+        }
+        CachedDecoder { table }
+    }
+
+    /// Returns the decoding of `byte` as a single-byte varint, or `None` if
+    /// its continuation bit is set.
+    #[inline]
+    pub fn lookup(&self, byte: u8) -> Option<i64> {
+        self.table.get(usize::from(byte)).copied()
+    }
+
+    /// Reads a zigzag-encoded varint from the start of `input`.
+    ///
+    /// This returns the same result as [`read_zigzag_varint`].
+    ///
+    /// # Errors
+    ///
+    /// See [`read_uvarint`].
+    #[inline]
+    pub fn read_zigzag_varint(&self, input: &[u8]) -> Result<(i64, usize), VarintError> {
+        match input.first().and_then(|&byte| self.lookup(byte)) {
+            Some(value) => Ok((value, 1)),
+            None => read_zigzag_varint(input),
+        }
+    }
+}
+
+impl Default for CachedDecoder {
+    #[inline]
+    fn default() -> Self {
+        CachedDecoder::new()
+    }
+}
+
+/// A varint decoder which rejects varints longer than a configured length.
+///
+/// Varint decoding needs no precomputed state, so the only state a context
+/// saves is its maximum length, which would otherwise have to be passed to,
+/// and checked by, every call site. A protocol whose values fit in 32 bits,
+/// for example, can cap varints at five bytes and reject longer ones before
+/// decoding them in full.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::DecodeContext;
+/// use zigzag::VarintError;
+///
+/// let context = DecodeContext::with_max_len(2);
+/// assert_eq!(context.decode(&[0xac, 0x02]), Ok((150, 2)));
+/// assert_eq!(context.decode(&[0x80, 0x80, 0x01]), Err(VarintError::Overflow));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DecodeContext {
+    max_len: usize,
+}
+
+impl DecodeContext {
+    /// Creates a context accepting varints of up to [`MAX_VARINT_LEN`] bytes,
+    /// which decodes exactly like [`read_zigzag_varint`].
+    #[inline]
+    pub const fn new() -> Self {
+        DecodeContext {
+            max_len: MAX_VARINT_LEN,
+        }
+    }
+
+    /// Creates a context accepting varints of up to `max_len` bytes.
+    ///
+    /// Lengths greater than [`MAX_VARINT_LEN`] are clamped to it, since no
+    /// longer varint can be decoded.
+    #[inline]
+    pub const fn with_max_len(max_len: usize) -> Self {
+        let max_len = if max_len < MAX_VARINT_LEN {
+            max_len
+        } else {
+            MAX_VARINT_LEN
+        };
+        DecodeContext { max_len }
+    }
+
+    /// Returns the maximum length of a varint the context accepts.
+    #[inline]
+    pub const fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// Reads a zigzag-encoded varint from the start of `input`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VarintError::Overflow`] if the varint is longer than
+    /// [`max_len`](Self::max_len) bytes. Otherwise, see [`read_uvarint`].
+    #[inline]
+    pub fn decode(&self, input: &[u8]) -> Result<(i64, usize), VarintError> {
+        let limited = &input[..input.len().min(self.max_len)];
+        match read_zigzag_varint(limited) {
+            Err(VarintError::Truncated) if input.len() > self.max_len => Err(VarintError::Overflow),
+            result => result,
+        }
+    }
+}
+
+impl Default for DecodeContext {
+    #[inline]
+    fn default() -> Self {
+        DecodeContext::new()
+    }
+}
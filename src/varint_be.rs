@@ -0,0 +1,147 @@
+//! Varints with their seven-bit groups in big-endian order.
+//!
+//! These are laid out like the LEB128 varints of the [`varint`](crate::varint)
+//! module, with the continuation bit set on every byte but the last, except
+//! that the most significant group comes first. This is the layout of MIDI's
+//! variable-length quantities, among others.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::varint::{uvarint_len, write_uvarint};
+use crate::varint::{CONTINUATION_BIT, MAX_VARINT_LEN, PAYLOAD_BITS};
+#[cfg(feature = "alloc")]
+use crate::ZigZagEncode;
+use crate::{VarintError, ZigZagDecode};
+
+/// Appends `x` to `out` as a big-endian unsigned varint and returns the
+/// number of bytes appended.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint_be::write_uvarint_be;
+///
+/// let mut out = Vec::new();
+/// assert_eq!(write_uvarint_be(150, &mut out), 2);
+/// assert_eq!(out, [0x81, 0x16]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn write_uvarint_be(x: u64, out: &mut Vec<u8>) -> usize {
+    let len = uvarint_len(x);
+    for i in (1..len).rev() {
+        out.push((x >> (7 * i)) as u8 & PAYLOAD_BITS | CONTINUATION_BIT);
+    }
+    out.push(x as u8 & PAYLOAD_BITS);
+    len
+}
+
+/// Appends `x` to `out` as a zigzag-encoded big-endian varint and returns
+/// the number of bytes appended.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint_be::write_zigzag_varint_be;
+///
+/// let mut out = Vec::new();
+/// write_zigzag_varint_be(-1, &mut out);
+/// write_zigzag_varint_be(150, &mut out);
+/// assert_eq!(out, [0x01, 0x82, 0x2c]);
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn write_zigzag_varint_be(x: i64, out: &mut Vec<u8>) -> usize {
+    write_uvarint_be(x.zigzag_encode(), out)
+}
+
+/// Reads a big-endian unsigned varint from the start of `input`.
+///
+/// On success, returns the decoded value and the number of bytes it occupied.
+///
+/// # Errors
+///
+/// Returns [`VarintError::Truncated`] if `input` ends before the last byte
+/// of the varint, and [`VarintError::Overflow`] if the varint encodes a
+/// value which doesn't fit in 64 bits or is longer than
+/// [`MAX_VARINT_LEN`] bytes.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint_be::read_uvarint_be;
+///
+/// assert_eq!(read_uvarint_be(&[0x81, 0x16, 0xff]), Ok((150, 2)));
+/// ```
+pub fn read_uvarint_be(input: &[u8]) -> Result<(u64, usize), VarintError> {
+    let mut value = 0u64;
+    for (i, &byte) in input.iter().enumerate() {
+        if i == MAX_VARINT_LEN || value > u64::MAX >> 7 {
+            return Err(VarintError::Overflow);
+        }
+        value = value << 7 | u64::from(byte & PAYLOAD_BITS);
+        if byte & CONTINUATION_BIT == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(VarintError::Truncated)
+}
+
+/// Reads a zigzag-encoded big-endian varint from the start of `input`.
+///
+/// # Errors
+///
+/// See [`read_uvarint_be`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint_be::read_zigzag_varint_be;
+///
+/// assert_eq!(read_zigzag_varint_be(&[0x82, 0x2c]), Ok((150, 2)));
+/// ```
+#[inline]
+pub fn read_zigzag_varint_be(input: &[u8]) -> Result<(i64, usize), VarintError> {
+    read_uvarint_be(input).map(|(raw, len)| (raw.zigzag_decode(), len))
+}
+
+/// Rewrites a stream of zigzag-encoded big-endian varints as the LEB128
+/// varints of the [`varint`](crate::varint) module, appending them to `out`.
+///
+/// Every varint is re-emitted in minimal form, so the output is exactly what
+/// [`write_zigzag_varint`](crate::varint::write_zigzag_varint) would produce
+/// for the same values.
+///
+/// # Errors
+///
+/// Returns an error if `input` doesn't consist entirely of valid big-endian
+/// varints. The varints before the malformed one have already been appended
+/// to `out`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::varint::zigzag_encode_all;
+/// use zigzag::varint_be::{transcode_be_to_le, write_zigzag_varint_be};
+///
+/// let values = [-1, 150, i64::MIN];
+/// let mut be = Vec::new();
+/// for &x in &values {
+///     write_zigzag_varint_be(x, &mut be);
+/// }
+///
+/// let mut le = Vec::new();
+/// transcode_be_to_le(&be, &mut le)?;
+/// assert_eq!(le, zigzag_encode_all(&values));
+/// # Ok::<(), zigzag::VarintError>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn transcode_be_to_le(mut input: &[u8], out: &mut Vec<u8>) -> Result<(), VarintError> {
+    while !input.is_empty() {
+        let (raw, len) = read_uvarint_be(input)?;
+        write_uvarint(raw, out);
+        input = &input[len..];
+    }
+    Ok(())
+}
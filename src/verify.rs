@@ -0,0 +1,149 @@
+//! Runtime checks that the encodings behave as specified on the current
+//! target.
+
+use crate::varint::{read_zigzag_varint, write_zigzag_varint_slice, MAX_VARINT_LEN};
+use crate::{ZigZagDecode, ZigZagEncode};
+
+macro_rules! check_width {
+    ($signed:ty, $unsigned:ty) => {{
+        let cases: [($signed, $unsigned); 7] = [
+            (0, 0),
+            (-1, 1),
+            (1, 2),
+            (-2, 3),
+            (<$signed>::MAX, <$unsigned>::MAX - 1),
+            (<$signed>::MIN + 1, <$unsigned>::MAX - 2),
+            (<$signed>::MIN, <$unsigned>::MAX),
+        ];
+        for &(signed, unsigned) in &cases {
+            if ZigZagEncode::zigzag_encode(signed) != unsigned {
+                return Err(concat!(
+                    "encoding an `",
+                    stringify!($signed),
+                    "` is incorrect"
+                ));
+            }
+            if ZigZagDecode::zigzag_decode(unsigned) != signed {
+                return Err(concat!(
+                    "decoding a `",
+                    stringify!($unsigned),
+                    "` is incorrect"
+                ));
+            }
+        }
+    }};
+}
+
+/// Known varint encodings, including the longest one.
+const VARINT_CASES: [(i64, &[u8]); 6] = [
+    (0, &[0x00]),
+    (-1, &[0x01]),
+    (1, &[0x02]),
+    (-64, &[0x7f]),
+    (150, &[0xac, 0x02]),
+    (
+        i64::MIN,
+        &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+    ),
+];
+
+/// Runs a fixed battery of encode and decode round-trips across every
+/// integer width and the varint format.
+///
+/// This lets applications that depend on this crate for persistent storage
+/// check, for instance at startup, that it behaves correctly on the target
+/// they were built for. It doesn't allocate.
+///
+/// # Errors
+///
+/// Returns a description of the first check that failed.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(zigzag::self_test(), Ok(()));
+/// ```
+pub fn self_test() -> Result<(), &'static str> {
+    check_width!(i8, u8);
+    check_width!(i16, u16);
+    check_width!(i32, u32);
+    check_width!(i64, u64);
+    check_width!(i128, u128);
+    check_width!(isize, usize);
+
+    let mut buf = [0; MAX_VARINT_LEN];
+    for &(value, bytes) in &VARINT_CASES {
+        match write_zigzag_varint_slice(value, &mut buf) {
+            Some(len) if &buf[..len] == bytes => {}
+            _ => return Err("writing a varint is incorrect"),
+        }
+        if read_zigzag_varint(bytes) != Ok((value, bytes.len())) {
+            return Err("reading a varint is incorrect");
+        }
+    }
+    Ok(())
+}
+
+/// Encodes and decodes every value of `values` with the scalar codec, and
+/// returns the first value that doesn't round-trip.
+///
+/// The result is the index of the value, the value itself and what it
+/// decoded to, or `None` if every value round-trips. The scalar codec is
+/// correct, so this always returns `None`; it serves as the reference for
+/// [`find_first_roundtrip_failure_with`].
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(zigzag::find_first_roundtrip_failure(&[0, -1, i64::MIN]), None);
+/// ```
+pub fn find_first_roundtrip_failure(values: &[i64]) -> Option<(usize, i64, i64)> {
+    find_first_roundtrip_failure_with(
+        values,
+        ZigZagEncode::zigzag_encode,
+        ZigZagDecode::zigzag_decode,
+    )
+}
+
+/// Encodes every value of `values` with `encode`, decodes the result with
+/// `decode`, and returns the first value that doesn't round-trip.
+///
+/// This checks another code path, such as one using a lookup table or SIMD,
+/// against a downstream dataset to pinpoint the value it gets wrong. The
+/// result is the index of the value, the value itself and what it decoded
+/// to, or `None` if every value round-trips.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::{find_first_roundtrip_failure_with, ZigZagDecode, ZigZagEncode};
+///
+/// // A faulty decoder which loses the sign of odd encodings above 100.
+/// fn faulty_decode(x: u64) -> i64 {
+///     if x > 100 && x % 2 == 1 {
+///         (x >> 1) as i64
+///     } else {
+///         x.zigzag_decode()
+///     }
+/// }
+///
+/// let values = [3, -7, 60, -60, 1];
+/// assert_eq!(
+///     find_first_roundtrip_failure_with(&values, ZigZagEncode::zigzag_encode, faulty_decode),
+///     Some((3, -60, 59))
+/// );
+/// ```
+pub fn find_first_roundtrip_failure_with(
+    values: &[i64],
+    encode: fn(i64) -> u64,
+    decode: fn(u64) -> i64,
+) -> Option<(usize, i64, i64)> {
+    values.iter().enumerate().find_map(|(i, &value)| {
+        let decoded = decode(encode(value));
+        if decoded == value {
+            None
+        } else {
+            Some((i, value, decoded))
+        }
+    })
+}
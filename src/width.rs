@@ -0,0 +1,266 @@
+//! Conversions of zigzag-encoded values between integer widths.
+//!
+//! Zigzag encoding maps `x` to `2x` when `x` is non-negative and to
+//! `-2x - 1` otherwise, regardless of the width it is computed at. An encoded
+//! value therefore keeps its numeric value when the signed value it stands
+//! for is moved to a wider or narrower type: promoting an encoding is a plain
+//! zero extension, and demoting one only needs a range check. Decoding and
+//! re-encoding, with the sign extension that would involve, is never
+//! necessary.
+
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::hash::{Hash, Hasher};
+
+use crate::{OutOfRange, ZigZagDecode, ZigZagEncode};
+
+macro_rules! impl_width {
+    ($narrow:ty, $wide:ty, $promote:ident, $demote:ident) => {
+        #[doc = concat!(
+                    "Converts the zigzag encoding of an `", stringify!($narrow), "`'s signed ",
+                    "counterpart into the encoding of the same value at the width of `",
+                    stringify!($wide), "`."
+                )]
+        #[inline]
+        pub fn $promote(x: $narrow) -> $wide {
+            <$wide>::from(x)
+        }
+
+        #[doc = concat!(
+                    "Converts a zigzag-encoded `", stringify!($wide), "` into the encoding of ",
+                    "the same value at the width of `", stringify!($narrow), "`.\n\n",
+                    "# Errors\n\n",
+                    "Returns [`OutOfRange`] if the decoded value doesn't fit in the signed ",
+                    "counterpart of `", stringify!($narrow), "`."
+                )]
+        #[inline]
+        pub fn $demote(x: $wide) -> Result<$narrow, OutOfRange> {
+            <$narrow>::try_from(x).map_err(|_| OutOfRange)
+        }
+    };
+}
+
+impl_width!(u8, u16, promote_encoded_u8_to_u16, demote_encoded_u16_to_u8);
+impl_width!(
+    u16,
+    u32,
+    promote_encoded_u16_to_u32,
+    demote_encoded_u32_to_u16
+);
+impl_width!(
+    u32,
+    u64,
+    promote_encoded_u32_to_u64,
+    demote_encoded_u64_to_u32
+);
+impl_width!(
+    u64,
+    u128,
+    promote_encoded_u64_to_u128,
+    demote_encoded_u128_to_u64
+);
+
+/// An unsigned value stored in the narrowest type that can hold it.
+///
+/// This is returned by [`zigzag_encode_narrowest`]. Values compare by the
+/// signed value they decode to, whatever their variants, so a column of
+/// values of mixed widths sorts by its decoded values. For consistency,
+/// equality and hashing only depend on the value, not on its variant.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::width::NarrowUnsigned;
+///
+/// // -1 is less than 5.
+/// assert!(NarrowUnsigned::U8(1) < NarrowUnsigned::U64(10));
+/// // 1 is greater than -2.
+/// assert!(NarrowUnsigned::U8(2) > NarrowUnsigned::U32(3));
+/// assert_eq!(NarrowUnsigned::U8(7), NarrowUnsigned::U16(7));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum NarrowUnsigned {
+    /// A value that fits in a `u8`.
+    U8(u8),
+    /// A value that fits in a `u16`, but not a `u8`.
+    U16(u16),
+    /// A value that fits in a `u32`, but not a `u16`.
+    U32(u32),
+    /// A value that doesn't fit in a `u32`.
+    U64(u64),
+}
+
+impl NarrowUnsigned {
+    /// Returns the value widened to a `u64`.
+    #[inline]
+    pub fn get(self) -> u64 {
+        match self {
+            NarrowUnsigned::U8(x) => u64::from(x),
+            NarrowUnsigned::U16(x) => u64::from(x),
+            NarrowUnsigned::U32(x) => u64::from(x),
+            NarrowUnsigned::U64(x) => x,
+        }
+    }
+
+    /// Decodes the value at 64-bit width.
+    #[inline]
+    pub fn decode(self) -> i64 {
+        self.get().zigzag_decode()
+    }
+}
+
+impl PartialEq for NarrowUnsigned {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl Eq for NarrowUnsigned {}
+
+impl Hash for NarrowUnsigned {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get().hash(state);
+    }
+}
+
+impl PartialOrd for NarrowUnsigned {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NarrowUnsigned {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.decode().cmp(&other.decode())
+    }
+}
+
+/// A signed value stored in the narrowest type that can hold it.
+///
+/// This is returned by [`zigzag_decode_narrowest`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NarrowSigned {
+    /// A value that fits in an `i8`.
+    I8(i8),
+    /// A value that fits in an `i16`, but not an `i8`.
+    I16(i16),
+    /// A value that fits in an `i32`, but not an `i16`.
+    I32(i32),
+    /// A value that doesn't fit in an `i32`.
+    I64(i64),
+}
+
+impl NarrowSigned {
+    /// Returns the value widened to an `i64`.
+    #[inline]
+    pub fn get(self) -> i64 {
+        match self {
+            NarrowSigned::I8(x) => i64::from(x),
+            NarrowSigned::I16(x) => i64::from(x),
+            NarrowSigned::I32(x) => i64::from(x),
+            NarrowSigned::I64(x) => x,
+        }
+    }
+}
+
+/// Zigzag encodes `x` at 64-bit width and stores the result in the narrowest
+/// unsigned type that can hold it.
+///
+/// Because the encoding doesn't depend on the width it is computed at, the
+/// result is also the encoding of `x` at the width of the chosen type.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::width::{zigzag_encode_narrowest, NarrowUnsigned};
+///
+/// assert_eq!(zigzag_encode_narrowest(-1), NarrowUnsigned::U8(1));
+/// assert_eq!(zigzag_encode_narrowest(-129), NarrowUnsigned::U16(257));
+/// assert_eq!(zigzag_encode_narrowest(i64::MIN), NarrowUnsigned::U64(u64::MAX));
+/// ```
+pub fn zigzag_encode_narrowest(x: i64) -> NarrowUnsigned {
+    let encoded: u64 = x.zigzag_encode();
+    if let Ok(x) = u8::try_from(encoded) {
+        NarrowUnsigned::U8(x)
+    } else if let Ok(x) = u16::try_from(encoded) {
+        NarrowUnsigned::U16(x)
+    } else if let Ok(x) = u32::try_from(encoded) {
+        NarrowUnsigned::U32(x)
+    } else {
+        NarrowUnsigned::U64(encoded)
+    }
+}
+
+/// Zigzag decodes `x` at 64-bit width and stores the result in the narrowest
+/// signed type that can hold it.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::width::{zigzag_decode_narrowest, NarrowSigned};
+/// use zigzag::ZigZagEncode;
+///
+/// assert_eq!(zigzag_decode_narrowest((-1i64).zigzag_encode()), NarrowSigned::I8(-1));
+/// assert_eq!(zigzag_decode_narrowest(300), NarrowSigned::I16(150));
+/// assert_eq!(
+///     zigzag_decode_narrowest(i64::MIN.zigzag_encode()),
+///     NarrowSigned::I64(i64::MIN)
+/// );
+/// ```
+pub fn zigzag_decode_narrowest(x: u64) -> NarrowSigned {
+    let decoded: i64 = x.zigzag_decode();
+    if let Ok(x) = i8::try_from(decoded) {
+        NarrowSigned::I8(x)
+    } else if let Ok(x) = i16::try_from(decoded) {
+        NarrowSigned::I16(x)
+    } else if let Ok(x) = i32::try_from(decoded) {
+        NarrowSigned::I32(x)
+    } else {
+        NarrowSigned::I64(decoded)
+    }
+}
+
+/// Narrows `x` to an `i32` and zigzag encodes it at 32-bit width.
+///
+/// Checking the range before encoding avoids the bug where a plain `as` cast
+/// silently wraps an out-of-range value into a valid-looking encoding.
+///
+/// # Errors
+///
+/// Returns [`OutOfRange`] if `x` doesn't fit in an `i32`.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::width::try_encode_i128_as_u32;
+///
+/// assert_eq!(try_encode_i128_as_u32(-1), Ok(1));
+/// assert!(try_encode_i128_as_u32(1 << 40).is_err());
+/// ```
+#[inline]
+pub fn try_encode_i128_as_u32(x: i128) -> Result<u32, OutOfRange> {
+    i32::try_from(x)
+        .map(ZigZagEncode::zigzag_encode)
+        .map_err(|_| OutOfRange)
+}
+
+/// Zigzag decodes `x` at 32-bit width and widens the result to an `i128`.
+///
+/// This is the inverse of [`try_encode_i128_as_u32`].
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::width::decode_u32_to_i128;
+///
+/// assert_eq!(decode_u32_to_i128(1), -1);
+/// assert_eq!(decode_u32_to_i128(u32::MAX), i128::from(i32::MIN));
+/// ```
+#[inline]
+pub fn decode_u32_to_i128(x: u32) -> i128 {
+    i128::from(ZigZagDecode::zigzag_decode(x))
+}
@@ -0,0 +1,124 @@
+//! Implementations of [`ZigZagEncode`] and [`ZigZagDecode`] for types which
+//! wrap or contain integers.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::num::{Saturating, Wrapping};
+
+use crate::{private, ZigZagDecode, ZigZagEncode};
+
+impl<T> private::Sealed for Option<T> {}
+impl<T> private::Sealed for Saturating<T> {}
+impl<T> private::Sealed for Wrapping<T> {}
+impl<T, const N: usize> private::Sealed for [T; N] {}
+#[cfg(feature = "alloc")]
+impl<T> private::Sealed for Vec<T> {}
+
+/// `Some` values are encoded and `None` is passed through.
+///
+/// This maps `Option<iN>` to `Option<uN>` rather than folding `None` into the
+/// encoded value, such as by using `0` for `None` and `zigzag(x) + 1` for
+/// `Some(x)`: that would leave no room for the encoding of `iN::MIN`.
+impl<S: ZigZagEncode> ZigZagEncode for Option<S> {
+    type Output = Option<S::Output>;
+
+    #[inline]
+    fn zigzag_encode(self) -> Option<S::Output> {
+        self.map(ZigZagEncode::zigzag_encode)
+    }
+}
+
+impl<U: ZigZagDecode> ZigZagDecode for Option<U> {
+    type Output = Option<U::Output>;
+
+    #[inline]
+    fn zigzag_decode(self) -> Option<U::Output> {
+        self.map(ZigZagDecode::zigzag_decode)
+    }
+}
+
+/// The wrapped value is encoded, and stays wrapped.
+///
+/// Saturation only affects arithmetic on the value, not how it's
+/// represented, so a `Saturating<iN>` encodes to a `Saturating<uN>` holding
+/// the encoding of the value, and decodes back without leaving the wrapper.
+impl<S: ZigZagEncode> ZigZagEncode for Saturating<S> {
+    type Output = Saturating<S::Output>;
+
+    #[inline]
+    fn zigzag_encode(self) -> Saturating<S::Output> {
+        Saturating(self.0.zigzag_encode())
+    }
+}
+
+impl<U: ZigZagDecode> ZigZagDecode for Saturating<U> {
+    type Output = Saturating<U::Output>;
+
+    #[inline]
+    fn zigzag_decode(self) -> Saturating<U::Output> {
+        Saturating(self.0.zigzag_decode())
+    }
+}
+
+/// The wrapped value is encoded, and stays wrapped, like that of
+/// [`Saturating`].
+impl<S: ZigZagEncode> ZigZagEncode for Wrapping<S> {
+    type Output = Wrapping<S::Output>;
+
+    #[inline]
+    fn zigzag_encode(self) -> Wrapping<S::Output> {
+        Wrapping(self.0.zigzag_encode())
+    }
+}
+
+impl<U: ZigZagDecode> ZigZagDecode for Wrapping<U> {
+    type Output = Wrapping<U::Output>;
+
+    #[inline]
+    fn zigzag_decode(self) -> Wrapping<U::Output> {
+        Wrapping(self.0.zigzag_decode())
+    }
+}
+
+/// Every element is encoded, giving an array of the same length.
+impl<S: ZigZagEncode, const N: usize> ZigZagEncode for [S; N] {
+    type Output = [S::Output; N];
+
+    #[inline]
+    fn zigzag_encode(self) -> [S::Output; N] {
+        self.map(ZigZagEncode::zigzag_encode)
+    }
+}
+
+impl<U: ZigZagDecode, const N: usize> ZigZagDecode for [U; N] {
+    type Output = [U::Output; N];
+
+    #[inline]
+    fn zigzag_decode(self) -> [U::Output; N] {
+        self.map(ZigZagDecode::zigzag_decode)
+    }
+}
+
+/// Every element is encoded into a newly allocated vector.
+///
+/// With the `bytemuck` feature enabled, `zigzag::slice::zigzag_encode_vec`
+/// reuses the allocation instead, for the primitive integer types.
+#[cfg(feature = "alloc")]
+impl<S: ZigZagEncode> ZigZagEncode for Vec<S> {
+    type Output = Vec<S::Output>;
+
+    #[inline]
+    fn zigzag_encode(self) -> Vec<S::Output> {
+        self.into_iter().map(ZigZagEncode::zigzag_encode).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<U: ZigZagDecode> ZigZagDecode for Vec<U> {
+    type Output = Vec<U::Output>;
+
+    #[inline]
+    fn zigzag_decode(self) -> Vec<U::Output> {
+        self.into_iter().map(ZigZagDecode::zigzag_decode).collect()
+    }
+}
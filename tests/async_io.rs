@@ -0,0 +1,155 @@
+#![cfg(feature = "tokio")]
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use zigzag::async_io::{AsyncZigZagReadExt, AsyncZigZagWriteExt};
+use zigzag::varint::zigzag_encode_all;
+use zigzag::DecodeError;
+
+const VALUES: [i64; 8] = [0, -1, 1, 150, -8193, i32::MIN as i64, i64::MAX, i64::MIN];
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+// A stream which returns `Pending` before every byte, and then hands over a
+// single one, so every multi-byte varint is split across polls.
+#[derive(Default)]
+struct Trickle {
+    bytes: Vec<u8>,
+    pos: usize,
+    ready: bool,
+    polls: usize,
+}
+
+impl Trickle {
+    fn new(bytes: Vec<u8>) -> Self {
+        Trickle {
+            bytes,
+            ..Trickle::default()
+        }
+    }
+
+    fn step(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.polls += 1;
+        self.ready = !self.ready;
+        if self.ready {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+}
+
+impl AsyncRead for Trickle {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if self.step(cx).is_pending() {
+            return Poll::Pending;
+        }
+        if let Some(&byte) = self.bytes.get(self.pos) {
+            buf.put_slice(&[byte]);
+            self.pos += 1;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for Trickle {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.step(cx).is_pending() {
+            return Poll::Pending;
+        }
+        self.bytes.push(buf[0]);
+        Poll::Ready(Ok(1))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[test]
+fn writes_one_byte_per_poll() {
+    let mut writer = Trickle::default();
+    block_on(async {
+        for &x in &VALUES {
+            writer.write_zigzag_varint_i64(x).await.unwrap();
+        }
+    });
+    let expected = zigzag_encode_all(&VALUES);
+    assert_eq!(writer.bytes, expected);
+    assert_eq!(writer.polls, 2 * expected.len());
+}
+
+#[test]
+fn reads_one_byte_per_poll() {
+    let bytes = zigzag_encode_all(&VALUES);
+    let mut reader = Trickle::new(bytes.clone());
+    block_on(async {
+        for &x in &VALUES {
+            assert_eq!(reader.read_zigzag_varint_i64().await.unwrap(), x);
+        }
+    });
+    // Nothing was read past the last varint or polled more than needed.
+    assert_eq!(reader.pos, bytes.len());
+    assert_eq!(reader.polls, 2 * bytes.len());
+}
+
+#[test]
+fn narrow_reads_match_blocking_reads() {
+    use zigzag::io::ZigZagReadExt;
+
+    for &x in &VALUES {
+        let bytes = zigzag_encode_all(&[x]);
+        let mut reader = Trickle::new(bytes.clone());
+        let expected = ZigZagReadExt::read_zigzag_varint_i32(&mut &bytes[..]);
+        let actual = block_on(reader.read_zigzag_varint_i32());
+        match (actual, expected) {
+            (Ok(actual), Ok(expected)) => assert_eq!(actual, expected),
+            (Err(actual), Err(expected)) => assert_eq!(actual.kind(), expected.kind()),
+            (actual, expected) => panic!("{:?} != {:?}", actual, expected),
+        }
+    }
+}
+
+#[test]
+fn reports_truncation_and_overflow() {
+    let mut reader = Trickle::new(vec![0xac]);
+    let err = block_on(reader.read_zigzag_varint_i64()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    let inner = err.into_inner().unwrap();
+    assert_eq!(
+        inner.downcast_ref(),
+        Some(&DecodeError::Truncated { needed: 1 })
+    );
+
+    let mut overflow = vec![0xff; 9];
+    overflow.push(0x02);
+    let err = block_on(Trickle::new(overflow).read_zigzag_varint_i64()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    // 2^31 encodes to a value which fits in an `i64` but not an `i32`.
+    let bytes = zigzag_encode_all(&[1 << 31]);
+    let err = block_on(Trickle::new(bytes).read_zigzag_varint_i32()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::bigint::{zigzag_decode_le, zigzag_encode_le};
+use zigzag::ZigZagEncode;
+
+mod common;
+
+fn trimmed_le(x: u128) -> Vec<u8> {
+    let bytes = x.to_le_bytes();
+    let len = bytes.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    bytes[..len].to_vec()
+}
+
+#[test]
+fn agrees_with_i128() {
+    let mut random = common::random_u64s();
+    let mut values = vec![0, -1, 1, -128, 128, -256, 255, i128::MIN, i128::MAX];
+    for shift in [0, 8, 40, 64, 100] {
+        for x in random.by_ref().take(100) {
+            values.push((i128::from(x as i64) << shift) >> (shift / 2));
+        }
+    }
+    for x in values {
+        let magnitude = trimmed_le(x.unsigned_abs());
+        let encoded = zigzag_encode_le(x < 0, &magnitude);
+        assert_eq!(encoded, trimmed_le(x.zigzag_encode()), "{}", x);
+        assert_eq!(zigzag_decode_le(&encoded), (x < 0, magnitude), "{}", x);
+    }
+}
+
+#[test]
+fn accepts_untrimmed_input() {
+    assert_eq!(zigzag_encode_le(true, &[1, 0, 0]), [1]);
+    assert_eq!(zigzag_encode_le(true, &[0, 0]), []);
+    assert_eq!(zigzag_encode_le(false, &[0]), []);
+    assert_eq!(zigzag_decode_le(&[2, 0, 0]), (false, vec![1]));
+    assert_eq!(zigzag_decode_le(&[0]), (false, vec![]));
+}
+
+#[test]
+fn carries_across_bytes() {
+    // -(2^200) encodes to 2^201 - 1.
+    let mut magnitude = vec![0; 25];
+    magnitude.push(1);
+    let encoded = zigzag_encode_le(true, &magnitude);
+    let mut expected = vec![0xff; 25];
+    expected.push(0x01);
+    assert_eq!(encoded, expected);
+    assert_eq!(zigzag_decode_le(&encoded), (true, magnitude));
+}
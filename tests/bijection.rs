@@ -0,0 +1,38 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use zigzag::ZigZagEncode;
+
+mod common;
+
+fn random_u64s(len: usize) -> impl Iterator<Item = u64> {
+    common::random_u64s().take(len)
+}
+
+#[test]
+fn i8_encoding_is_a_bijection_onto_u8() {
+    let encoded: BTreeSet<u8> = (i8::MIN..=i8::MAX).map(|x| x.zigzag_encode()).collect();
+    assert_eq!(encoded.len(), 256);
+    assert!(encoded.iter().copied().eq(0..=u8::MAX));
+}
+
+#[test]
+fn i16_encoding_has_no_collisions() {
+    let encoded: HashSet<u16> = (i16::MIN..=i16::MAX).map(|x| x.zigzag_encode()).collect();
+    assert_eq!(encoded.len(), 1 << 16);
+}
+
+#[test]
+fn sampled_wide_encodings_have_no_collisions() {
+    let mut seen_32 = HashMap::new();
+    let mut seen_64 = HashMap::new();
+    let mut seen_128 = HashMap::new();
+    let boundaries = [0, 1, u64::MAX, u64::MAX - 1, 1 << 63, (1 << 63) - 1];
+    for raw in random_u64s(100_000).chain(boundaries.iter().copied()) {
+        let x32 = raw as i32;
+        assert_eq!(*seen_32.entry(x32.zigzag_encode()).or_insert(x32), x32);
+        let x64 = raw as i64;
+        assert_eq!(*seen_64.entry(x64.zigzag_encode()).or_insert(x64), x64);
+        let x128 = (raw as i128) << 64 | raw.rotate_left(17) as i128;
+        assert_eq!(*seen_128.entry(x128.zigzag_encode()).or_insert(x128), x128);
+    }
+}
@@ -0,0 +1,125 @@
+use std::num::{NonZeroI32, NonZeroIsize};
+
+use zigzag::bits::{decode_i24_sample, encode_i24_sample};
+use zigzag::{OutOfRange, ZigZag};
+
+#[test]
+fn bits_match_std() {
+    assert_eq!(<i8 as ZigZag>::BITS, i8::BITS);
+    assert_eq!(<i16 as ZigZag>::BITS, i16::BITS);
+    assert_eq!(<i32 as ZigZag>::BITS, i32::BITS);
+    assert_eq!(<i64 as ZigZag>::BITS, i64::BITS);
+    assert_eq!(<i128 as ZigZag>::BITS, i128::BITS);
+    assert_eq!(<isize as ZigZag>::BITS, isize::BITS);
+    assert_eq!(<NonZeroI32 as ZigZag>::BITS, 32);
+    assert_eq!(<NonZeroIsize as ZigZag>::BITS, isize::BITS);
+}
+
+const I32_BITS: u32 = <i32 as ZigZag>::BITS;
+
+fn encoded_bytes<T: ZigZag>() -> usize {
+    T::BITS as usize / 8
+}
+
+#[test]
+fn bits_usable_in_const_positions() {
+    let bytes = [0u8; <i128 as ZigZag>::BITS as usize / 8];
+    assert_eq!(bytes.len(), 16);
+    assert_eq!(I32_BITS, 32);
+    assert_eq!(encoded_bytes::<i16>(), 2);
+}
+
+#[test]
+fn i24_samples_round_trip_at_boundaries() {
+    let min = -(1 << 23);
+    let max = (1 << 23) - 1;
+    for &x in &[min, min + 1, -1, 0, 1, max - 1, max] {
+        let encoded = encode_i24_sample(x).unwrap();
+        assert!(encoded < 1 << 24);
+        assert_eq!(decode_i24_sample(encoded), Ok(x));
+    }
+    assert_eq!(encode_i24_sample(max), Ok(0xff_fffe));
+    assert_eq!(encode_i24_sample(min), Ok(0xff_ffff));
+    assert_eq!(encode_i24_sample(min - 1), Err(OutOfRange));
+    assert_eq!(encode_i24_sample(max + 1), Err(OutOfRange));
+    assert_eq!(encode_i24_sample(i32::MIN), Err(OutOfRange));
+    assert_eq!(decode_i24_sample(1 << 24), Err(OutOfRange));
+}
+
+#[test]
+fn packs_fields_into_u128() {
+    use zigzag::bits::{pack_zigzag_fields, unpack_zigzag_fields};
+
+    let values = [-1, 0, (1 << 19) - 1, -(1 << 19)];
+    let packed = pack_zigzag_fields(values, 20).unwrap();
+    assert!(packed < 1 << 80);
+    assert_eq!(packed & 0xf_ffff, 1);
+    assert_eq!(unpack_zigzag_fields::<4>(packed, 20), Ok(values));
+
+    assert_eq!(pack_zigzag_fields([1 << 19, 0, 0, 0], 20), Err(OutOfRange));
+    assert_eq!(pack_zigzag_fields([0; 7], 20), Err(OutOfRange));
+    assert_eq!(unpack_zigzag_fields::<7>(0, 20), Err(OutOfRange));
+
+    let extremes = [i64::MIN, i64::MAX];
+    let packed = pack_zigzag_fields(extremes, 64).unwrap();
+    assert_eq!(packed, u128::MAX - (1 << 64));
+    assert_eq!(unpack_zigzag_fields::<2>(packed, 64), Ok(extremes));
+
+    assert_eq!(
+        pack_zigzag_fields([i64::MIN], 128),
+        Ok(u128::from(u64::MAX))
+    );
+    assert_eq!(unpack_zigzag_fields::<1>(1 << 64, 128), Err(OutOfRange));
+    assert_eq!(pack_zigzag_fields([0; 5], 0), Ok(0));
+    assert_eq!(pack_zigzag_fields([-1], 0), Err(OutOfRange));
+    assert_eq!(pack_zigzag_fields([], 200), Err(OutOfRange));
+}
+
+#[test]
+fn arbitrary_widths_round_trip() {
+    use zigzag::bits::{decode_bits, encode_bits, try_decode_bits, try_encode_bits};
+
+    for x in -32..32 {
+        let encoded = encode_bits::<6>(x);
+        assert!(encoded < 64);
+        assert_eq!(encoded, zigzag::encode(x));
+        assert_eq!(decode_bits::<6>(encoded), x);
+        assert_eq!(try_encode_bits::<6>(x), Ok(encoded));
+        assert_eq!(try_decode_bits::<6>(encoded), Ok(x));
+    }
+    assert_eq!(try_encode_bits::<6>(-33), Err(OutOfRange));
+    assert_eq!(try_encode_bits::<6>(32), Err(OutOfRange));
+    assert_eq!(try_decode_bits::<6>(64), Err(OutOfRange));
+
+    for &x in &[0, -1, 1, -(1 << 23), (1 << 23) - 1] {
+        let encoded = encode_i24_sample(x).unwrap();
+        assert_eq!(try_encode_bits::<24>(x.into()), Ok(u64::from(encoded)));
+        assert_eq!(decode_bits::<24>(encoded.into()), i64::from(x));
+    }
+
+    assert_eq!(try_encode_bits::<1>(0), Ok(0));
+    assert_eq!(try_encode_bits::<1>(-1), Ok(1));
+    assert_eq!(try_encode_bits::<1>(1), Err(OutOfRange));
+    assert_eq!(try_encode_bits::<63>(-(1 << 62)), Ok(u64::MAX >> 1));
+    assert_eq!(try_encode_bits::<63>(1 << 62), Err(OutOfRange));
+    for &x in &[0, -1, i64::MIN, i64::MAX] {
+        assert_eq!(try_encode_bits::<64>(x), Ok(zigzag::encode(x)));
+        assert_eq!(try_decode_bits::<64>(zigzag::encode(x)), Ok(x));
+    }
+}
+
+#[test]
+fn arbitrary_widths_wrap_out_of_range_values() {
+    use zigzag::bits::{decode_bits, encode_bits};
+
+    // 200 is stored as -56 in an 8-bit field.
+    assert_eq!(
+        encode_bits::<8>(200),
+        u64::from(zigzag::encode(200u8 as i8))
+    );
+    assert_eq!(decode_bits::<8>(encode_bits::<8>(200)), -56);
+    assert_eq!(decode_bits::<4>(0xf1), -1);
+
+    const PACKED: u64 = encode_bits::<12>(-2048);
+    assert_eq!(PACKED, 0xfff);
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::bits::encode_bits;
+use zigzag::bitstream::{BitReader, BitWriter};
+use zigzag::DecodeError;
+
+#[test]
+fn packs_mixed_widths_back_to_back() {
+    let fields: Vec<(i64, u32)> = (0..1000)
+        .map(|i: i64| {
+            let bits = (i % 65) as u32;
+            let value = match bits {
+                0 => 0,
+                64 => i64::MIN + i,
+                _ => (i * 7919) % (1 << (bits - 1)) * if i % 2 == 0 { 1 } else { -1 },
+            };
+            (value, bits)
+        })
+        .collect();
+
+    let mut writer = BitWriter::new();
+    for &(value, bits) in &fields {
+        writer.write_zigzag(value, bits).unwrap();
+    }
+    let total: u32 = fields.iter().map(|&(_, bits)| bits).sum();
+    assert_eq!(writer.bit_len(), total as usize);
+    let bytes = writer.into_bytes();
+    assert_eq!(bytes.len(), (total as usize).div_ceil(8));
+
+    let mut reader = BitReader::new(&bytes);
+    for &(value, bits) in &fields {
+        assert_eq!(reader.read_zigzag(bits), Ok(value));
+    }
+    assert!(reader.remaining_bits() < 8);
+}
+
+#[test]
+fn matches_the_fixed_width_encoding() {
+    let mut writer = BitWriter::new();
+    for x in -16..16 {
+        writer.write_zigzag(x, 5).unwrap();
+    }
+    let bytes = writer.into_bytes();
+    let mut reader = BitReader::new(&bytes);
+    for x in -16..16 {
+        assert_eq!(reader.read_bits(5), Ok(encode_bits::<5>(x)));
+    }
+}
+
+#[test]
+fn rejects_values_too_wide_for_the_field() {
+    let mut writer = BitWriter::new();
+    writer.write_bits(0b101, 3);
+    assert!(writer.write_zigzag(-17, 5).is_err());
+    assert!(writer.write_zigzag(16, 5).is_err());
+    assert!(writer.write_zigzag(1, 0).is_err());
+    assert_eq!(writer.write_zigzag(0, 0), Ok(()));
+    assert_eq!(writer.write_zigzag(i64::MIN, 64), Ok(()));
+    // Rejected fields leave the writer untouched.
+    assert_eq!(writer.bit_len(), 67);
+}
+
+#[test]
+fn reports_how_many_bytes_are_missing() {
+    let bytes = [0xffu8; 3];
+    let mut reader = BitReader::new(&bytes);
+    assert_eq!(reader.read_bits(5), Ok(0b11111));
+    assert_eq!(
+        reader.read_bits(20),
+        Err(DecodeError::Truncated { needed: 1 })
+    );
+    assert_eq!(
+        reader.read_bits(64),
+        Err(DecodeError::Truncated { needed: 6 })
+    );
+    assert_eq!(reader.bit_position(), 5);
+    assert_eq!(reader.read_bits(19), Ok((1 << 19) - 1));
+    assert_eq!(reader.read_bit(), Err(DecodeError::Truncated { needed: 1 }));
+}
+
+#[test]
+#[should_panic(expected = "at most 64 bits")]
+fn rejects_fields_wider_than_64_bits() {
+    BitWriter::new().write_bits(0, 65);
+}
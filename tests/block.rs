@@ -0,0 +1,54 @@
+#![cfg(feature = "alloc")]
+
+use std::num::Saturating;
+
+use zigzag::block::write_zigzag_block;
+
+fn decode_saturating(x: i64) -> Saturating<i64> {
+    Saturating(x)
+}
+
+#[test]
+fn saturating_block_round_trips() {
+    let values: Vec<_> = (0..200)
+        .map(|i| Saturating(i64::MAX - 100) + Saturating(i * i) - Saturating(i64::MAX / 2))
+        .chain([Saturating(i64::MIN), Saturating(i64::MAX)])
+        .collect();
+    let block = write_zigzag_block(values.iter().map(|value| value.0));
+    let reader = block.reader();
+
+    for (i, &value) in values.iter().enumerate() {
+        assert_eq!(reader.get(i).map(decode_saturating), Ok(value));
+    }
+    assert!(reader.get(values.len()).is_err());
+}
+
+#[test]
+fn integer_literals_infer_i64() {
+    let block = write_zigzag_block(0..1000);
+    assert_eq!(block.reader().get(999), Ok(999));
+}
+
+#[test]
+fn decoded_block_iterates_every_way() {
+    let values: Vec<i64> = (-300..300).map(|x| x * 31).collect();
+    let mut block = write_zigzag_block(values.iter().copied()).decode().unwrap();
+
+    let by_ref: Vec<i64> = (&block).into_iter().copied().collect();
+    assert_eq!(by_ref, values);
+
+    for value in &mut block {
+        *value = -*value;
+    }
+    let mut negated = Vec::new();
+    for value in &block {
+        negated.push(-value);
+    }
+    assert_eq!(negated, values);
+
+    for value in &mut block {
+        *value = -*value;
+    }
+    let by_value: Vec<i64> = block.into_iter().collect();
+    assert_eq!(by_value, values);
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "bnum")]
+
+use bnum::types::{I512, U512};
+use bnum::{BIntD8, BUintD8};
+use zigzag::{ZigZag, ZigZagDecode, ZigZagEncode};
+
+#[test]
+fn boundaries_encode_like_primitives() {
+    assert_eq!(I512::MIN.zigzag_encode(), U512::MAX);
+    assert_eq!(I512::MAX.zigzag_encode(), U512::MAX - U512::ONE);
+    assert_eq!(U512::MAX.zigzag_decode(), I512::MIN);
+    assert_eq!(I512::NEG_ONE.zigzag_encode(), U512::ONE);
+    assert_eq!(<I512 as ZigZag>::BITS, 512);
+
+    // A three-byte integer has no primitive equivalent.
+    type I24 = BIntD8<3>;
+    type U24 = BUintD8<3>;
+    assert_eq!(I24::MIN.zigzag_encode(), U24::MAX);
+    assert_eq!(U24::from(3u8).zigzag_decode(), I24::from(-2i8));
+}
+
+#[test]
+fn mid_range_values_round_trip() {
+    let x = I512::from(-1_234_567_890_123_456_789i64) * I512::from(i64::MAX);
+    let encoded: U512 = x.zigzag_encode();
+    assert_eq!(encoded, (-x).cast_unsigned() * U512::TWO - U512::ONE);
+    assert_eq!(encoded.zigzag_decode(), x);
+
+    for &small in &[0i64, 1, -1, i64::MIN, i64::MAX] {
+        let wide = I512::from(small);
+        assert_eq!(
+            wide.zigzag_encode(),
+            U512::from(ZigZagEncode::zigzag_encode(small))
+        );
+    }
+}
+
+fn encode_all<T: ZigZag>(values: &[T]) -> Vec<T::Unsigned> {
+    values.iter().map(|&x| zigzag::encode(x)).collect()
+}
+
+#[test]
+fn generic_code_spans_8_to_256_bits() {
+    use bnum::types::{I256, U256};
+
+    assert_eq!(encode_all(&[-1i8, i8::MIN]), [1, u8::MAX]);
+    assert_eq!(encode_all(&[-1i128, i128::MIN]), [1, u128::MAX]);
+    assert_eq!(
+        encode_all(&[I256::NEG_ONE, I256::MIN, I256::MAX]),
+        [U256::ONE, U256::MAX, U256::MAX - U256::ONE]
+    );
+
+    let balance = -(I256::from(u128::MAX) << 64u32);
+    let encoded: U256 = balance.zigzag_encode();
+    assert_eq!(encoded, (-balance).cast_unsigned() * U256::TWO - U256::ONE);
+    assert_eq!(encoded.zigzag_decode(), balance);
+}
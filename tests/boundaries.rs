@@ -0,0 +1,55 @@
+use zigzag::{ZigZag, ZigZagDecode, ZigZagEncode, ZigZagZero};
+
+// Checks the four canonical mappings of a width pair. Every supported width
+// gets exactly one invocation, so a new width only needs one more line.
+macro_rules! boundaries {
+    ($name:ident, $signed:ty, $unsigned:ty) => {
+        #[test]
+        fn $name() {
+            let zero = <$signed>::from(0i8);
+            let minus_one = <$signed>::from(-1i8);
+            let one = <$unsigned>::from(1u8);
+
+            let cases = [
+                (<$signed>::MIN, <$unsigned>::MAX),
+                (minus_one, one),
+                (zero, <$unsigned>::from(0u8)),
+                (<$signed>::MAX, <$unsigned>::MAX - one),
+            ];
+            assert_eq!(<$signed as ZigZag>::ENCODED_NEG_ONE, one);
+            assert_eq!(<$signed as ZigZagZero>::ENCODED_ZERO, zero.zigzag_encode());
+            for &(signed, unsigned) in &cases {
+                assert_eq!(ZigZagEncode::zigzag_encode(signed), unsigned);
+                assert_eq!(ZigZagDecode::zigzag_decode(unsigned), signed);
+            }
+        }
+    };
+}
+
+boundaries!(i8_u8, i8, u8);
+boundaries!(i16_u16, i16, u16);
+boundaries!(i32_u32, i32, u32);
+boundaries!(i64_u64, i64, u64);
+boundaries!(i128_u128, i128, u128);
+boundaries!(isize_usize, isize, usize);
+
+#[cfg(feature = "bnum")]
+boundaries!(i256_u256, bnum::types::I256, bnum::types::U256);
+#[cfg(feature = "bnum")]
+boundaries!(i24_u24, bnum::BIntD8<3>, bnum::BUintD8<3>);
+
+#[test]
+fn encoded_neg_one_is_usable_in_const_context() {
+    const SENTINEL: u32 = <i32 as ZigZag>::ENCODED_NEG_ONE;
+    const SENTINELS: [u64; 2] = [<i64 as ZigZag>::ENCODED_NEG_ONE; 2];
+    assert_eq!(SENTINEL, 1);
+    assert_eq!(SENTINELS, [1, 1]);
+}
+
+#[test]
+fn encoded_zero_is_usable_in_const_context() {
+    const EMPTY: u32 = <i32 as ZigZagZero>::ENCODED_ZERO;
+    const EMPTIES: [u16; 2] = [<i16 as ZigZagZero>::ENCODED_ZERO; 2];
+    assert_eq!(EMPTY, 0);
+    assert_eq!(EMPTIES, [0, 0]);
+}
@@ -0,0 +1,112 @@
+#![cfg(all(feature = "bytes", feature = "alloc"))]
+
+use bytes::{Buf, Bytes, BytesMut};
+use zigzag::buf::{ZigZagBufExt, ZigZagBufMutExt};
+use zigzag::varint::{write_zigzag_varint, zigzag_encode_all};
+use zigzag::DecodeError;
+
+const VALUES: [i64; 9] = [
+    0,
+    -1,
+    1,
+    150,
+    -8193,
+    1 << 40,
+    i32::MIN as i64,
+    i64::MAX,
+    i64::MIN,
+];
+
+// A buffer whose chunks are a single byte each, so that every varint of more
+// than one byte is split across chunks.
+struct OneByteChunks<'a>(&'a [u8]);
+
+impl Buf for OneByteChunks<'_> {
+    fn remaining(&self) -> usize {
+        self.0.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        &self.0[..self.0.len().min(1)]
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.0 = &self.0[cnt..];
+    }
+}
+
+#[test]
+fn puts_the_same_bytes_as_write_zigzag_varint() {
+    let mut out = BytesMut::new();
+    let mut expected = Vec::new();
+    for &x in &VALUES {
+        assert_eq!(
+            out.put_zigzag_varint_i64(x),
+            write_zigzag_varint(x, &mut expected)
+        );
+    }
+    assert_eq!(out[..], expected[..]);
+
+    let mut out = Vec::new();
+    out.put_zigzag_varint_i32(-2);
+    out.put_zigzag_varint_i16(i16::MIN);
+    assert_eq!(out, zigzag_encode_all(&[-2, i64::from(i16::MIN)]));
+}
+
+#[test]
+fn gets_varints_split_at_every_chunk_boundary() {
+    let bytes = Bytes::from(zigzag_encode_all(&VALUES));
+    for split in 0..=bytes.len() {
+        let mut input = bytes.slice(..split).chain(bytes.slice(split..));
+        for &x in &VALUES {
+            assert_eq!(input.get_zigzag_varint_i64(), Ok(x));
+        }
+        assert!(!input.has_remaining());
+    }
+
+    let mut input = OneByteChunks(&bytes);
+    for &x in &VALUES {
+        assert_eq!(input.get_zigzag_varint_i64(), Ok(x));
+    }
+    assert!(!input.has_remaining());
+}
+
+#[test]
+fn narrow_reads_reject_values_out_of_range() {
+    let bytes = zigzag_encode_all(&[i64::from(i32::MAX), i64::from(i32::MAX) + 1, -129]);
+    let mut input = &bytes[..];
+    assert_eq!(input.get_zigzag_varint_i32(), Ok(i32::MAX));
+    assert_eq!(input.get_zigzag_varint_i32(), Err(DecodeError::Overflow));
+    assert_eq!(input.get_zigzag_varint_i16(), Ok(-129));
+}
+
+#[test]
+fn truncated_varints() {
+    // A contiguous buffer keeps the truncated varint...
+    let mut input = Bytes::from_static(&[0x01, 0xac]);
+    assert_eq!(input.get_zigzag_varint_i64(), Ok(-1));
+    assert_eq!(
+        input.get_zigzag_varint_i64(),
+        Err(DecodeError::Truncated { needed: 1 })
+    );
+    assert_eq!(input[..], [0xac]);
+
+    // ...while one split across chunks is consumed.
+    let mut input = OneByteChunks(&[0xac, 0x80]);
+    assert_eq!(
+        input.get_zigzag_varint_i64(),
+        Err(DecodeError::Truncated { needed: 1 })
+    );
+    assert!(!input.has_remaining());
+
+    let mut overflow = [0xff; 10];
+    overflow[9] = 0x02;
+    assert_eq!(
+        OneByteChunks(&overflow).get_zigzag_varint_i64(),
+        Err(DecodeError::Overflow)
+    );
+    assert_eq!(
+        (&overflow[..]).get_zigzag_varint_i64(),
+        Err(DecodeError::Overflow)
+    );
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn zigzag(args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_zigzag"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn encodes_text_to_every_format() {
+    let cases: &[(&str, &[u8])] = &[
+        ("varint", &[0x00, 0x01, 0xac, 0x02]),
+        ("prefix", &[0x00, 0x01, 0xac, 0x04]),
+        (
+            "fixed",
+            &[
+                0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 44, 1, 0, 0, 0, 0, 0, 0,
+            ],
+        ),
+    ];
+    for &(format, expected) in cases {
+        let output = zigzag(&["encode", "--format", format], b"0 -1\n150\n");
+        assert!(output.status.success(), "{}", format);
+        assert_eq!(output.stdout, expected, "{}", format);
+    }
+}
+
+#[test]
+fn decodes_back_to_text_or_raw() {
+    let values = [0i64, -1, 150, i64::MIN, i64::MAX];
+    let text: String = values.iter().map(|x| format!("{}\n", x)).collect();
+    let raw: Vec<u8> = values.iter().flat_map(|x| x.to_le_bytes()).collect();
+    for format in ["varint", "prefix", "fixed"] {
+        let encoded = zigzag(&["encode", "-f", format], text.as_bytes()).stdout;
+        assert_eq!(
+            zigzag(&["decode", "-f", format], &encoded).stdout,
+            text.as_bytes()
+        );
+        assert_eq!(
+            zigzag(&["decode", "-f", format, "--raw"], &encoded).stdout,
+            raw
+        );
+        assert_eq!(
+            zigzag(&["encode", "-f", format, "--raw"], &raw).stdout,
+            encoded
+        );
+    }
+}
+
+#[test]
+fn reports_bad_input() {
+    let output = zigzag(&["decode"], &[0x02, 0xac]);
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "zigzag: at byte 1: input is truncated: 1 more bytes needed\n"
+    );
+
+    let output = zigzag(&["encode"], b"1 two");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+
+    let output = zigzag(&["encode", "--raw"], &[0; 9]);
+    assert!(!output.status.success());
+}
@@ -0,0 +1,25 @@
+// Helpers shared by the integration tests and the benchmarks. Each of them
+// only uses some of these.
+#![allow(dead_code)]
+
+/// Returns an endless stream of pseudorandom `u64`s from a xorshift
+/// generator with a fixed seed, so that every run sees the same values.
+pub fn random_u64s() -> impl Iterator<Item = u64> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    core::iter::repeat_with(move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    })
+}
+
+/// Returns `len` pseudorandom values, each shifted right by a varying
+/// amount so that their varints have every length from one to ten bytes.
+pub fn mixed_i64s(len: usize) -> Vec<i64> {
+    random_u64s()
+        .take(len)
+        .enumerate()
+        .map(|(i, x)| (x as i64) >> (i % 64))
+        .collect()
+}
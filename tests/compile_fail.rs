@@ -0,0 +1,15 @@
+// The expected errors list the sealed types, which the num-bigint impls add
+// to.
+#[cfg(not(feature = "num-bigint"))]
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derive_ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/derive/*.rs");
+}
@@ -0,0 +1,47 @@
+use zigzag::const_fn::*;
+use zigzag::{ZigZagDecode, ZigZagEncode};
+
+#[test]
+fn matches_traits_for_every_narrow_value() {
+    for x in i8::MIN..=i8::MAX {
+        assert_eq!(encode_i8(x), x.zigzag_encode());
+    }
+    for x in u8::MIN..=u8::MAX {
+        assert_eq!(decode_u8(x), x.zigzag_decode());
+    }
+    for x in i16::MIN..=i16::MAX {
+        assert_eq!(encode_i16(x), x.zigzag_encode());
+    }
+    for x in u16::MIN..=u16::MAX {
+        assert_eq!(decode_u16(x), x.zigzag_decode());
+    }
+}
+
+#[test]
+fn matches_traits_at_the_boundaries() {
+    macro_rules! check {
+        ($signed:ty, $encode:ident, $decode:ident) => {
+            for &x in &[0, -1, 1, <$signed>::MIN, <$signed>::MAX, <$signed>::MIN + 1] {
+                assert_eq!($encode(x), x.zigzag_encode());
+                assert_eq!($decode($encode(x)), x);
+            }
+        };
+    }
+    check!(i32, encode_i32, decode_u32);
+    check!(i64, encode_i64, decode_u64);
+    check!(i128, encode_i128, decode_u128);
+    check!(isize, encode_isize, decode_usize);
+}
+
+#[test]
+fn arrays_are_built_at_compile_time() {
+    const SIGNED: [i64; 5] = [0, -1, 1, i64::MIN, i64::MAX];
+    const ENCODED: [u64; 5] = encode_array_i64(SIGNED);
+    const DECODED: [i64; 5] = decode_array_u64(ENCODED);
+    assert_eq!(ENCODED, [0, 1, 2, u64::MAX, u64::MAX - 1]);
+    assert_eq!(DECODED, SIGNED);
+
+    const EMPTY: [u16; 0] = encode_array_i16([]);
+    assert_eq!(EMPTY, []);
+    assert_eq!(decode_array_u8(encode_array_i8([-128, 127])), [-128, 127]);
+}
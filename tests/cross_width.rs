@@ -0,0 +1,45 @@
+use std::convert::TryFrom;
+
+use zigzag::{OutOfRange, ZigZag, ZigZagEncode, ZigZagUnsigned};
+
+#[test]
+fn narrowing_encode_matches_narrowing_first() {
+    for x in (-70_000i64..70_000).chain([i64::MIN, i64::MAX]) {
+        let expected = i16::try_from(x)
+            .map(ZigZagEncode::zigzag_encode)
+            .map_err(|_| OutOfRange);
+        assert_eq!(x.zigzag_encode_into::<u16>(), expected);
+    }
+}
+
+#[test]
+fn narrowing_decode_matches_decoding_then_narrowing() {
+    for x in (0u64..140_000).chain([u64::MAX - 1, u64::MAX]) {
+        let expected = i16::try_from(zigzag::decode(x)).map_err(|_| OutOfRange);
+        assert_eq!(x.zigzag_decode_into::<i16>(), expected);
+        if let Ok(narrowed) = expected {
+            assert_eq!(narrowed.zigzag_encode_as::<u64>(), x);
+        }
+    }
+}
+
+#[test]
+fn widening_round_trips_every_i16() {
+    for x in i16::MIN..=i16::MAX {
+        let wide = x.zigzag_encode_as::<u64>();
+        assert_eq!(wide, zigzag::encode(i64::from(x)));
+        assert_eq!(wide.zigzag_decode_as::<i64>(), i64::from(x));
+        assert_eq!(wide.zigzag_decode_into::<i16>(), Ok(x));
+        assert_eq!(
+            i64::from(x).zigzag_encode_into::<u16>(),
+            Ok(x.zigzag_encode())
+        );
+    }
+}
+
+#[test]
+fn same_width_never_fails() {
+    assert_eq!(i32::MIN.zigzag_encode_into::<u32>(), Ok(u32::MAX));
+    assert_eq!(u32::MAX.zigzag_decode_into::<i32>(), Ok(i32::MIN));
+    assert_eq!(i8::MIN.zigzag_encode_into::<u128>(), Ok(255));
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::decimal::{decode_zigzag_decimal, encode_zigzag_decimal};
+
+#[test]
+fn round_trips_through_decimal() {
+    assert_eq!(encode_zigzag_decimal(-1), "1");
+    assert_eq!(encode_zigzag_decimal(1), "2");
+    assert_eq!(encode_zigzag_decimal(0), "0");
+
+    for &x in &[0, -1, 1, -64, 1000, i64::MIN, i64::MAX] {
+        let s = encode_zigzag_decimal(x);
+        assert!(s.bytes().all(|b| b.is_ascii_digit()), "{}", s);
+        assert_eq!(decode_zigzag_decimal(&s), Ok(x));
+    }
+}
+
+#[test]
+fn rejects_invalid_tokens() {
+    assert!(decode_zigzag_decimal("").is_err());
+    assert!(decode_zigzag_decimal("-2").is_err());
+    assert!(decode_zigzag_decimal("1x").is_err());
+    assert!(decode_zigzag_decimal("18446744073709551616").is_err());
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::delta::{delta_zigzag_decode, delta_zigzag_encode, rebase_delta_block};
+
+#[test]
+fn rebased_block_is_shifted() {
+    let values = [-5, 1000, 999, i64::MAX, i64::MIN, 0];
+    let mut block = Vec::new();
+    delta_zigzag_encode(&values, &mut block);
+
+    for &base in &[0, -5, 123_456_789, i64::MIN] {
+        let mut out = vec![0xff];
+        rebase_delta_block(&block, base, &mut out).unwrap();
+        let shift = base.wrapping_sub(values[0]);
+        let expected: Vec<i64> = values.iter().map(|x| x.wrapping_add(shift)).collect();
+        assert_eq!(delta_zigzag_decode(&out[1..]), Ok(expected));
+    }
+}
+
+#[test]
+fn rebasing_concatenates_blocks() {
+    let mut out = Vec::new();
+    delta_zigzag_encode(&[1, 2, 3], &mut out);
+    let mut second = Vec::new();
+    delta_zigzag_encode(&[50, 51], &mut second);
+
+    // Appended after the first block, the second block's base is read as a
+    // delta from that block's last value.
+    rebase_delta_block(&second, 50 - 3, &mut out).unwrap();
+    assert_eq!(delta_zigzag_decode(&out), Ok(vec![1, 2, 3, 50, 51]));
+}
+
+#[test]
+fn rebasing_rejects_malformed_input() {
+    let mut out = Vec::new();
+    assert!(rebase_delta_block(&[0x02, 0x80], 7, &mut out).is_err());
+    assert!(out.is_empty());
+    rebase_delta_block(&[], 7, &mut out).unwrap();
+    assert!(out.is_empty());
+}
+
+#[test]
+fn prefix_sums_accumulate_and_check_overflow() {
+    use zigzag::delta::{PrefixSumDecoder, PrefixSumEncoder};
+    use zigzag::{OutOfRange, VarintError};
+
+    let mut encoder = PrefixSumEncoder::new();
+    let mut out = Vec::new();
+    for &increment in &[10, -3, 5] {
+        encoder.push(increment, &mut out).unwrap();
+    }
+    let totals: Vec<i64> = PrefixSumDecoder::new(&out).map(Result::unwrap).collect();
+    assert_eq!(totals, [10, 7, 12]);
+    assert_eq!(delta_zigzag_decode(&out), Ok(totals));
+
+    let len = out.len();
+    assert_eq!(encoder.push(i64::MAX, &mut out), Err(OutOfRange));
+    assert_eq!((encoder.total(), out.len()), (12, len));
+
+    // Increments written without the check overflow on decode.
+    let mut wrapped = Vec::new();
+    delta_zigzag_encode(&[i64::MAX, i64::MIN], &mut wrapped);
+    let mut decoder = PrefixSumDecoder::new(&wrapped);
+    assert_eq!(decoder.next(), Some(Ok(i64::MAX)));
+    assert_eq!(decoder.next(), Some(Err(VarintError::Overflow)));
+    assert_eq!(decoder.next(), None);
+    assert_eq!(decoder.total(), i64::MAX);
+}
+
+#[test]
+fn stateful_codec_matches_one_shot_functions_across_splits() {
+    use zigzag::delta::{delta_zigzag_decode, delta_zigzag_encode};
+    use zigzag::delta::{DeltaZigZagDecoder, DeltaZigZagEncoder};
+
+    let values = [5, 7, 6, i64::MIN, i64::MAX, 0, -3];
+    let mut expected = Vec::new();
+    delta_zigzag_encode(&values, &mut expected);
+
+    for split in 0..=values.len() {
+        let mut encoder = DeltaZigZagEncoder::new();
+        let mut first = Vec::new();
+        encoder.encode_slice(&values[..split], &mut first);
+        let mut second = Vec::new();
+        encoder.encode_slice(&values[split..], &mut second);
+        assert_eq!([&first[..], &second[..]].concat(), expected);
+
+        let mut decoder = DeltaZigZagDecoder::new();
+        let mut decoded = Vec::new();
+        decoder.decode_slice(&first, &mut decoded).unwrap();
+        decoder.decode_slice(&second, &mut decoded).unwrap();
+        assert_eq!(decoded, values);
+        assert_eq!(decoder.prev(), -3);
+    }
+    assert_eq!(delta_zigzag_decode(&expected).unwrap(), values);
+}
+
+#[test]
+fn stateful_decoder_is_unchanged_by_errors() {
+    use zigzag::delta::DeltaZigZagDecoder;
+    use zigzag::VarintError;
+
+    let mut decoder = DeltaZigZagDecoder::new();
+    assert_eq!(decoder.decode(&[0x14]), Ok((10, 1)));
+
+    let mut out = vec![10];
+    assert_eq!(
+        decoder.decode_slice(&[0x02, 0x80], &mut out),
+        Err(VarintError::Truncated)
+    );
+    assert_eq!(out, [10]);
+    assert_eq!(decoder.prev(), 10);
+    assert_eq!(decoder.decode(&[0x80]), Err(VarintError::Truncated));
+    assert_eq!(decoder.prev(), 10);
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "derive")]
+
+use std::num::{NonZeroI32, NonZeroU32};
+
+use zigzag::{ZigZagDecode, ZigZagEncode};
+
+#[derive(ZigZagEncode, ZigZagDecode, Clone, Debug, PartialEq)]
+#[zigzag(derive(Clone, Debug, PartialEq, Eq))]
+pub struct Sample {
+    /// The change in time since the previous sample.
+    pub dt: i64,
+    pub dv: i16,
+    id: Option<NonZeroI32>,
+    #[zigzag(skip)]
+    label: String,
+    #[zigzag(skip)]
+    flags: u8,
+}
+
+#[derive(ZigZagEncode, ZigZagDecode, Debug, PartialEq)]
+#[zigzag(name = "Wire", derive(Debug, PartialEq))]
+struct Pair(i128, isize);
+
+#[derive(ZigZagEncode, ZigZagDecode, Debug, PartialEq)]
+struct Unit;
+
+#[test]
+fn named_fields_round_trip() {
+    let sample = Sample {
+        dt: i64::MIN,
+        dv: -2,
+        id: NonZeroI32::new(-1),
+        label: "probe".to_owned(),
+        flags: 0xff,
+    };
+    let encoded = sample.clone().zigzag_encode();
+    assert_eq!(encoded.dt, u64::MAX);
+    assert_eq!(encoded.dv, 3u16);
+    assert_eq!(encoded.id, NonZeroU32::new(1));
+    assert_eq!(encoded.label, "probe");
+    assert_eq!(encoded.flags, 0xff);
+    assert_eq!(encoded.clone().zigzag_decode(), sample);
+}
+
+#[test]
+fn tuple_and_unit_structs_round_trip() {
+    let wire: Wire = Pair(-1, isize::MAX).zigzag_encode();
+    assert_eq!(wire, Wire(1, usize::MAX - 1));
+    assert_eq!(wire.zigzag_decode(), Pair(-1, isize::MAX));
+
+    let EncodedUnit = Unit.zigzag_encode();
+    assert_eq!(EncodedUnit.zigzag_decode(), Unit);
+}
+
+#[test]
+fn the_traits_are_still_usable() {
+    assert_eq!(ZigZagEncode::zigzag_encode(-1i8), 1);
+    assert_eq!(ZigZagDecode::zigzag_decode(1u8), -1);
+}
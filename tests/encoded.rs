@@ -0,0 +1,96 @@
+use std::mem::{align_of, size_of};
+
+use zigzag::encoded::{Zigzag128, Zigzag16, Zigzag32, Zigzag64, Zigzag8};
+
+#[repr(C)]
+struct Header {
+    tag: u8,
+    delta: Zigzag32,
+    offset: Zigzag64,
+}
+
+#[test]
+fn layout_matches_unsigned() {
+    assert_eq!(size_of::<Zigzag8>(), 1);
+    assert_eq!(size_of::<Zigzag16>(), 2);
+    assert_eq!(size_of::<Zigzag32>(), 4);
+    assert_eq!(size_of::<Zigzag64>(), 8);
+    assert_eq!(size_of::<Zigzag128>(), 16);
+    assert_eq!(align_of::<Zigzag32>(), align_of::<u32>());
+
+    #[repr(C)]
+    struct RawHeader {
+        tag: u8,
+        delta: u32,
+        offset: u64,
+    }
+    assert_eq!(size_of::<Header>(), size_of::<RawHeader>());
+}
+
+#[test]
+fn conversions_round_trip() {
+    let header = Header {
+        tag: 1,
+        delta: Zigzag32::from_signed(-1),
+        offset: Zigzag64::from_signed(i64::MIN),
+    };
+    assert_eq!(header.tag, 1);
+    assert_eq!(header.delta, Zigzag32(1));
+    assert_eq!(header.delta.to_signed(), -1);
+    assert_eq!(header.offset, Zigzag64(u64::MAX));
+    assert_eq!(header.offset.to_signed(), i64::MIN);
+
+    for x in i16::MIN..=i16::MAX {
+        assert_eq!(Zigzag16::from_signed(x).to_signed(), x);
+    }
+}
+
+#[test]
+fn formats_encoded_bits_and_decoded_value() {
+    let minus_one = Zigzag32::from_signed(-1i32);
+    assert_eq!(format!("{:032b}", minus_one), format!("{:0>32}", "1"));
+    assert_eq!(
+        format!("{:b}", Zigzag32::from_signed(i32::MIN)),
+        "1".repeat(32)
+    );
+    assert_eq!(format!("{}", minus_one), "-1");
+
+    let x = Zigzag64::from_signed(-0x80);
+    assert_eq!(format!("{:x}", x), "ff");
+    assert_eq!(format!("{:#X}", x), "0xFF");
+    assert_eq!(format!("{:>6}", x), "  -128");
+}
+
+#[test]
+fn debug_shows_encoded_and_decoded() {
+    use zigzag::encoded::{EncodedU32, OrderPreservingEncoded, ZigZagKey, ZigzagEncoded};
+
+    assert_eq!(
+        format!("{:?}", Zigzag32::from_signed(-1)),
+        "Zigzag32 { encoded: 1, decoded: -1 }"
+    );
+    assert_eq!(
+        format!("{:?}", Zigzag8::from_signed(i8::MIN)),
+        "Zigzag8 { encoded: 255, decoded: -128 }"
+    );
+    assert_eq!(
+        format!("{:?}", EncodedU32(3)),
+        "EncodedU32 { encoded: 3, decoded: -2 }"
+    );
+    assert_eq!(
+        format!("{:?}", ZigzagEncoded::from_signed(2)),
+        "ZigzagEncoded { encoded: 4, decoded: 2 }"
+    );
+    assert_eq!(
+        format!("{:?}", OrderPreservingEncoded::from_signed(0)),
+        "OrderPreservingEncoded { encoded: 2147483648, decoded: 0 }"
+    );
+    assert_eq!(
+        format!("{:?}", ZigZagKey::from(-1i32)),
+        "ZigZagKey { encoded: 1, decoded: -1 }"
+    );
+    assert_eq!(
+        format!("{:?}", ZigZagKey::from(i16::MIN)),
+        "ZigZagKey { encoded: 65535, decoded: -32768 }"
+    );
+}
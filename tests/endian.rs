@@ -0,0 +1,48 @@
+use zigzag::endian::{
+    zigzag_decode_bytes_endian, zigzag_encode_bytes_endian, BigEndian, LittleEndian, ZigZagBytes,
+};
+use zigzag::ZigZagEncode;
+
+#[test]
+fn matches_std_bytes_of_the_encoding() {
+    for x in (-300i64..300).chain([i64::MIN, i64::MAX]) {
+        let encoded: u64 = x.zigzag_encode();
+        assert_eq!(x.to_zigzag_le_bytes(), encoded.to_le_bytes());
+        assert_eq!(x.to_zigzag_be_bytes(), encoded.to_be_bytes());
+        assert_eq!(i64::from_zigzag_le_bytes(encoded.to_le_bytes()), x);
+        assert_eq!(i64::from_zigzag_be_bytes(encoded.to_be_bytes()), x);
+    }
+}
+
+#[test]
+fn round_trips_every_i16() {
+    for x in i16::MIN..=i16::MAX {
+        assert_eq!(i16::from_zigzag_le_bytes(x.to_zigzag_le_bytes()), x);
+        assert_eq!(i16::from_zigzag_be_bytes(x.to_zigzag_be_bytes()), x);
+    }
+}
+
+#[test]
+fn small_negatives_have_zero_high_bytes() {
+    assert_eq!((-1i128).to_zigzag_be_bytes()[..15], [0; 15]);
+    assert!((-64isize).to_zigzag_le_bytes()[1..].iter().all(|&b| b == 0));
+    assert_eq!(i8::MIN.to_zigzag_le_bytes(), [0xff]);
+}
+
+#[test]
+fn agrees_with_byte_order_helpers() {
+    for x in [0i32, -1, 1, i32::MIN, i32::MAX, 123_456_789] {
+        assert_eq!(
+            zigzag_encode_bytes_endian::<LittleEndian>(x),
+            x.to_zigzag_le_bytes()
+        );
+        assert_eq!(
+            zigzag_encode_bytes_endian::<BigEndian>(x),
+            x.to_zigzag_be_bytes()
+        );
+        assert_eq!(
+            zigzag_decode_bytes_endian::<BigEndian>(x.to_zigzag_be_bytes()),
+            i32::from_zigzag_be_bytes(x.to_zigzag_be_bytes())
+        );
+    }
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "ethnum")]
+
+use ethnum::{I256, U256};
+use zigzag::{ZigZag, ZigZagDecode, ZigZagEncode, ZigZagZero};
+
+#[test]
+fn boundaries_encode_like_primitives() {
+    assert_eq!(I256::MIN.zigzag_encode(), U256::MAX);
+    assert_eq!(I256::MAX.zigzag_encode(), U256::MAX - 1);
+    assert_eq!(U256::MAX.zigzag_decode(), I256::MIN);
+    assert_eq!(I256::MINUS_ONE.zigzag_encode(), U256::ONE);
+    assert_eq!(<I256 as ZigZag>::BITS, 256);
+    assert_eq!(<I256 as ZigZag>::ENCODED_NEG_ONE, U256::ONE);
+    assert_eq!(<I256 as ZigZagZero>::ENCODED_ZERO, U256::ZERO);
+}
+
+#[test]
+fn values_which_fit_in_i128_encode_like_i128() {
+    for &small in &[0i128, 1, -1, i128::MIN, i128::MAX, -150] {
+        let wide = I256::new(small);
+        assert_eq!(
+            wide.zigzag_encode(),
+            U256::new(ZigZagEncode::zigzag_encode(small))
+        );
+        assert_eq!(wide.zigzag_encode().zigzag_decode(), wide);
+    }
+}
+
+fn encode_all<T: ZigZag>(values: &[T]) -> Vec<T::Unsigned> {
+    values.iter().map(|&x| zigzag::encode(x)).collect()
+}
+
+#[test]
+fn generic_code_spans_8_to_256_bits() {
+    assert_eq!(encode_all(&[-1i8, i8::MIN]), [1, u8::MAX]);
+    assert_eq!(encode_all(&[-1i128, i128::MIN]), [1, u128::MAX]);
+    assert_eq!(
+        encode_all(&[I256::MINUS_ONE, I256::MIN, I256::MAX]),
+        [U256::ONE, U256::MAX, U256::MAX - 1]
+    );
+
+    let balance = -(I256::new(i128::MAX) << 64u32);
+    let encoded: U256 = balance.zigzag_encode();
+    assert_eq!(encoded, (-balance).as_u256() * 2 - 1);
+    assert_eq!(encoded.zigzag_decode(), balance);
+}
@@ -0,0 +1,146 @@
+#![cfg(feature = "ffi")]
+
+use std::ptr;
+
+use zigzag::ffi::{
+    zigzag_decode_u32_buf, zigzag_decode_u64_buf, zigzag_encode_i32_buf, zigzag_encode_i64_buf,
+    zigzag_varint_decode_i64_buf, zigzag_varint_encode_i64_buf, ZigZagStatus,
+};
+use zigzag::varint::zigzag_encode_all;
+
+#[test]
+fn fixed_width_round_trips() {
+    let src = [0i64, -1, 1, i64::MIN, i64::MAX];
+    let mut encoded = [0u64; 5];
+    let mut decoded = [0i64; 5];
+    unsafe {
+        assert_eq!(
+            zigzag_encode_i64_buf(src.as_ptr(), encoded.as_mut_ptr(), 5),
+            ZigZagStatus::Ok
+        );
+        assert_eq!(
+            zigzag_decode_u64_buf(encoded.as_ptr(), decoded.as_mut_ptr(), 5),
+            ZigZagStatus::Ok
+        );
+    }
+    assert_eq!(encoded, [0, 1, 2, u64::MAX, u64::MAX - 1]);
+    assert_eq!(decoded, src);
+}
+
+#[test]
+fn fixed_width_transforms_in_place() {
+    let mut buf = [-2i32, 3, i32::MIN];
+    let ptr = buf.as_mut_ptr();
+    unsafe {
+        assert_eq!(
+            zigzag_encode_i32_buf(ptr, ptr as *mut u32, 3),
+            ZigZagStatus::Ok
+        );
+        assert_eq!(buf.map(|x| x as u32), [3, 6, u32::MAX]);
+        assert_eq!(
+            zigzag_decode_u32_buf(ptr as *const u32, ptr, 3),
+            ZigZagStatus::Ok
+        );
+    }
+    assert_eq!(buf, [-2, 3, i32::MIN]);
+}
+
+#[test]
+fn null_pointers_are_only_accepted_when_empty() {
+    let mut dst = [0u64; 1];
+    unsafe {
+        assert_eq!(
+            zigzag_encode_i64_buf(ptr::null(), ptr::null_mut(), 0),
+            ZigZagStatus::Ok
+        );
+        assert_eq!(
+            zigzag_encode_i64_buf(ptr::null(), dst.as_mut_ptr(), 1),
+            ZigZagStatus::NullPointer
+        );
+        assert_eq!(
+            zigzag_varint_encode_i64_buf(ptr::null(), 0, ptr::null_mut(), 0, ptr::null_mut()),
+            ZigZagStatus::NullPointer
+        );
+    }
+}
+
+#[test]
+fn varints_round_trip() {
+    let src = [0i64, -1, 150, i64::MIN, 63, -64];
+    let expected = zigzag_encode_all(&src);
+    let mut buf = [0u8; 32];
+    let mut written = 0;
+    let mut decoded = [0i64; 6];
+    let mut count = 0;
+    unsafe {
+        assert_eq!(
+            zigzag_varint_encode_i64_buf(src.as_ptr(), 6, buf.as_mut_ptr(), 32, &mut written),
+            ZigZagStatus::Ok
+        );
+        assert_eq!(buf[..written], expected[..]);
+        assert_eq!(
+            zigzag_varint_decode_i64_buf(
+                buf.as_ptr(),
+                written,
+                decoded.as_mut_ptr(),
+                6,
+                &mut count
+            ),
+            ZigZagStatus::Ok
+        );
+    }
+    assert_eq!((decoded, count), (src, 6));
+}
+
+#[test]
+fn varint_errors_are_reported() {
+    let mut buf = [0xaau8; 2];
+    let mut written = 0;
+    let mut decoded = [0i64; 1];
+    let mut count = 0;
+    unsafe {
+        assert_eq!(
+            zigzag_varint_encode_i64_buf(
+                [150i64, 1].as_ptr(),
+                2,
+                buf.as_mut_ptr(),
+                2,
+                &mut written
+            ),
+            ZigZagStatus::BufferTooSmall
+        );
+        assert_eq!((buf, written), ([0xaa; 2], 3));
+
+        let two = [0x02u8, 0x04];
+        assert_eq!(
+            zigzag_varint_decode_i64_buf(two.as_ptr(), 2, decoded.as_mut_ptr(), 1, &mut count),
+            ZigZagStatus::BufferTooSmall
+        );
+        assert_eq!((decoded, count), ([1], 1));
+
+        let truncated = [0x02u8, 0x80];
+        assert_eq!(
+            zigzag_varint_decode_i64_buf(
+                truncated.as_ptr(),
+                2,
+                decoded.as_mut_ptr(),
+                1,
+                &mut count
+            ),
+            ZigZagStatus::BufferTooSmall
+        );
+        let mut pair = [0i64; 2];
+        assert_eq!(
+            zigzag_varint_decode_i64_buf(truncated.as_ptr(), 2, pair.as_mut_ptr(), 2, &mut count),
+            ZigZagStatus::Truncated
+        );
+        assert_eq!(count, 1);
+
+        let overflow = [0xffu8; 11];
+        assert_eq!(
+            zigzag_varint_decode_i64_buf(overflow.as_ptr(), 11, pair.as_mut_ptr(), 2, &mut count),
+            ZigZagStatus::Overflow
+        );
+        assert_eq!(count, 0);
+    }
+}
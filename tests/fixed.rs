@@ -0,0 +1,15 @@
+use zigzag::fixed::{decode_point_i16, encode_point_i16};
+
+#[test]
+fn packs_points() {
+    assert_eq!(encode_point_i16(-1, 1), 1 << 16 | 2);
+    assert_eq!(decode_point_i16(encode_point_i16(-1, 1)), (-1, 1));
+
+    let boundaries = [i16::MIN, -1, 0, 1, i16::MAX];
+    for &x in &boundaries {
+        for &y in &boundaries {
+            assert_eq!(decode_point_i16(encode_point_i16(x, y)), (x, y));
+        }
+    }
+    assert_eq!(encode_point_i16(i16::MIN, i16::MAX), 0xffff_fffe);
+}
@@ -0,0 +1,94 @@
+use std::cmp::Ordering;
+
+use zigzag::float::{
+    from_total_order_f32, from_total_order_f64, total_order_f32, total_order_f64, xor_decode_f32,
+    xor_decode_f64, xor_delta_f64, xor_encode_f32, xor_encode_f64, xor_undelta_f64,
+};
+
+fn interesting_f64s() -> Vec<f64> {
+    let mut values = vec![
+        f64::NEG_INFINITY,
+        f64::MIN,
+        -1.0,
+        -f64::MIN_POSITIVE,
+        -f64::from_bits(1),
+        -0.0,
+        0.0,
+        f64::from_bits(1),
+        f64::MIN_POSITIVE,
+        1.0,
+        f64::MAX,
+        f64::INFINITY,
+        f64::NAN,
+        -f64::NAN,
+        f64::from_bits(0x7ff0_0000_0000_0001),
+    ];
+    values.extend((0..200).map(|i| (f64::from(i) - 100.0) * 1.37e10));
+    values
+}
+
+#[test]
+fn total_order_matches_total_cmp() {
+    let values = interesting_f64s();
+    for &a in &values {
+        assert_eq!(
+            from_total_order_f64(total_order_f64(a)).to_bits(),
+            a.to_bits()
+        );
+        for &b in &values {
+            assert_eq!(total_order_f64(a).cmp(&total_order_f64(b)), a.total_cmp(&b));
+        }
+    }
+    assert_eq!(total_order_f64(-f64::NAN), 0x0007_ffff_ffff_ffff);
+    assert_eq!(total_order_f64(0.0), 1 << 63);
+    assert_eq!(total_order_f64(-0.0), (1 << 63) - 1);
+}
+
+#[test]
+fn total_order_f32_matches_total_cmp() {
+    let values: Vec<f32> = interesting_f64s().iter().map(|&x| x as f32).collect();
+    for &a in &values {
+        assert_eq!(
+            from_total_order_f32(total_order_f32(a)).to_bits(),
+            a.to_bits()
+        );
+        for &b in &values {
+            let order = total_order_f32(a).cmp(&total_order_f32(b));
+            assert_eq!(order, a.total_cmp(&b));
+            if order == Ordering::Equal {
+                assert_eq!(a.to_bits(), b.to_bits());
+            }
+        }
+    }
+}
+
+#[test]
+fn xor_round_trips_every_bit_pattern() {
+    let values = interesting_f64s();
+    let mut xored = vec![0u64; values.len()];
+    xor_encode_f64(&values, &mut xored);
+    assert_eq!(xored[0], values[0].to_bits());
+    assert_eq!(xored[1], xor_delta_f64(values[0], values[1]));
+    assert_eq!(xor_undelta_f64(values[0], xored[1]), values[1]);
+
+    let mut decoded = vec![0.0; values.len()];
+    xor_decode_f64(&xored, &mut decoded);
+    let bits = |v: &[f64]| v.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+    assert_eq!(bits(&decoded), bits(&values));
+
+    let narrow: Vec<f32> = values.iter().map(|&x| x as f32).collect();
+    let mut xored = vec![0u32; narrow.len()];
+    xor_encode_f32(&narrow, &mut xored);
+    let mut decoded = vec![0.0f32; narrow.len()];
+    xor_decode_f32(&xored, &mut decoded);
+    assert!(decoded
+        .iter()
+        .zip(&narrow)
+        .all(|(a, b)| a.to_bits() == b.to_bits()));
+}
+
+#[test]
+#[should_panic]
+fn xor_encode_rejects_mismatched_lengths() {
+    xor_encode_f64(&[1.0, 2.0], &mut [0; 1]);
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::frame::{read_frame, write_frame};
+use zigzag::VarintError;
+
+#[test]
+fn reads_back_consecutive_frames() {
+    let long = vec![0xab; 300];
+    let mut out = Vec::new();
+    write_frame(b"hello", &mut out);
+    write_frame(&long, &mut out);
+    assert_eq!(out.len(), 1 + 5 + 2 + 300);
+
+    let (first, rest) = read_frame(&out).unwrap();
+    assert_eq!(first, b"hello");
+    let (second, rest) = read_frame(rest).unwrap();
+    assert_eq!(second, &long[..]);
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn rejects_truncated_frames() {
+    assert_eq!(read_frame(&[]), Err(VarintError::Truncated));
+    assert_eq!(read_frame(&[0x80]), Err(VarintError::Truncated));
+    assert_eq!(read_frame(&[0x03, 1, 2]), Err(VarintError::Truncated));
+    assert_eq!(read_frame(&[0x02, 1, 2, 3]), Ok((&[1, 2][..], &[3][..])));
+}
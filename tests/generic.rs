@@ -0,0 +1,45 @@
+#![cfg(feature = "num-traits")]
+
+use zigzag::generic::{decode_generic, encode_generic};
+use zigzag::{ZigZagDecode, ZigZagEncode};
+
+mod common;
+
+fn random_u64s(len: usize) -> Vec<u64> {
+    common::random_u64s().take(len).collect()
+}
+
+#[test]
+fn matches_the_traits_exhaustively() {
+    for x in i8::MIN..=i8::MAX {
+        assert_eq!(encode_generic::<i8, u8>(x), x.zigzag_encode());
+    }
+    for x in u16::MIN..=u16::MAX {
+        assert_eq!(decode_generic::<u16, i16>(x), x.zigzag_decode());
+        assert_eq!(encode_generic::<i16, u16>(x.zigzag_decode()), x);
+    }
+}
+
+#[test]
+fn matches_the_traits_at_every_width() {
+    for bits in random_u64s(1000) {
+        let x = bits as i32;
+        assert_eq!(encode_generic::<i32, u32>(x), x.zigzag_encode());
+        let x = bits as i64;
+        assert_eq!(encode_generic::<i64, u64>(x), x.zigzag_encode());
+        assert_eq!(decode_generic::<u64, i64>(bits), bits.zigzag_decode());
+        let x = (i128::from(bits as i64) << 64) | i128::from(bits);
+        assert_eq!(encode_generic::<i128, u128>(x), x.zigzag_encode());
+        let x = bits as isize;
+        assert_eq!(encode_generic::<isize, usize>(x), x.zigzag_encode());
+    }
+    for x in [i128::MIN, i128::MAX, -1, 0, 1] {
+        assert_eq!(decode_generic::<u128, i128>(encode_generic(x)), x);
+    }
+}
+
+#[test]
+#[should_panic(expected = "different widths")]
+fn rejects_mismatched_widths() {
+    encode_generic::<i32, u64>(-1);
+}
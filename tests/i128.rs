@@ -0,0 +1,33 @@
+use zigzag::{ZigZagDecode, ZigZagEncode};
+
+const BOUNDARIES: [(i128, u128); 4] = [
+    (i128::MIN, u128::MAX),
+    (i128::MIN + 1, u128::MAX - 2),
+    (i128::MAX, u128::MAX - 1),
+    (-1, 1),
+];
+
+#[test]
+fn encodes_boundaries() {
+    for &(signed, unsigned) in &BOUNDARIES {
+        assert_eq!(signed.zigzag_encode(), unsigned, "encoding {}", signed);
+    }
+}
+
+#[test]
+fn decodes_boundaries() {
+    for &(signed, unsigned) in &BOUNDARIES {
+        assert_eq!(unsigned.zigzag_decode(), signed, "decoding {}", unsigned);
+    }
+}
+
+#[test]
+fn high_bits_shift_out_without_panicking() {
+    // These are built with overflow checks in the test profile, so any
+    // overflowing shift would panic here.
+    for shift in 0..128 {
+        let x = i128::MIN >> shift;
+        assert_eq!(x.zigzag_encode().zigzag_decode(), x);
+        assert_eq!((!x).zigzag_encode().zigzag_decode(), !x);
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt::Debug;
+use std::num::{NonZeroI32, NonZeroU32, Saturating};
+
+use zigzag::{decode, encode, ZigZag, ZigZagDecode, ZigZagEncode, ZigZagUnsigned};
+
+// With only a `ZigZag` bound, the encoding's own decoding is reachable
+// without naming any other type.
+fn round_trip<T: ZigZag + PartialEq + Debug>(x: T) -> T::Unsigned {
+    let encoded = encode(x);
+    assert_eq!(decode(encoded), x);
+    encoded
+}
+
+fn signed_of<U: ZigZagUnsigned>(x: U) -> U::Signed {
+    decode(x)
+}
+
+// The associated types name the result without a second type parameter,
+// which couldn't be inferred from the arguments.
+fn encode_all<T: ZigZagEncode + Copy>(values: &[T]) -> Vec<T::Output> {
+    values.iter().map(|&x| x.zigzag_encode()).collect()
+}
+
+fn decode_u32<T: ZigZagDecode<Output = i32>>(x: T) -> i32 {
+    x.zigzag_decode()
+}
+
+#[test]
+fn encode_and_decode_name_their_output() {
+    assert_eq!(encode_all(&[-1i32, 1]), [1u32, 2]);
+    assert_eq!(encode_all(&[Saturating(-2i8)]), [Saturating(3u8)]);
+    assert_eq!(encode([-1i16, i16::MIN]), [1u16, u16::MAX]);
+    assert_eq!(decode([1u64, 2]), [-1i64, 1]);
+    assert_eq!(decode_u32(5u32), -3);
+}
+
+#[test]
+fn free_functions_infer_the_other_type() {
+    assert_eq!(round_trip(-1i8), 1u8);
+    assert_eq!(round_trip(i16::MIN), u16::MAX);
+    assert_eq!(round_trip(i32::MAX), u32::MAX - 1);
+    assert_eq!(round_trip(-3i64), 5u64);
+    assert_eq!(round_trip(i128::MIN), u128::MAX);
+    assert_eq!(round_trip(isize::MIN), usize::MAX);
+
+    assert_eq!(signed_of(2u8), 1i8);
+    assert_eq!(signed_of(u64::MAX), i64::MIN);
+}
+
+#[test]
+fn free_functions_cover_non_zero_and_option() {
+    let x = NonZeroI32::new(-1).unwrap();
+    assert_eq!(round_trip(x), NonZeroU32::new(1).unwrap());
+    assert_eq!(encode(Some(-2i16)), Some(3u16));
+    assert_eq!(decode(Some(3u16)), Some(-2i16));
+    assert_eq!(encode(None::<i64>), None::<u64>);
+    assert_eq!(
+        decode(Some(NonZeroU32::new(3).unwrap())),
+        NonZeroI32::new(-2)
+    );
+}
+
+#[cfg(feature = "bnum")]
+#[test]
+fn free_functions_cover_bnum() {
+    use bnum::types::I256;
+
+    assert_eq!(round_trip(I256::MIN), bnum::types::U256::MAX);
+    assert_eq!(signed_of(bnum::types::U256::ONE), -I256::ONE);
+}
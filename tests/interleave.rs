@@ -0,0 +1,32 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::interleave::{decode_interleaved, encode_interleaved};
+use zigzag::VarintError;
+
+#[test]
+fn interleaves_and_deinterleaves() {
+    let xs = [0, -300, i32::MAX];
+    let ys = [i32::MIN, 7, -1];
+    let mut out = Vec::new();
+    encode_interleaved(&xs, &ys, &mut out);
+    assert_eq!(out[..2], [0x00, 0xff]);
+
+    let (dxs, dys) = decode_interleaved(&out).unwrap();
+    assert_eq!(dxs, xs);
+    assert_eq!(dys, ys);
+}
+
+#[test]
+fn rejects_unpaired_and_oversized_values() {
+    assert_eq!(decode_interleaved(&[0x02]), Err(VarintError::Truncated));
+    assert_eq!(
+        decode_interleaved(&[0x80, 0x80, 0x80, 0x80, 0x10, 0x00]),
+        Err(VarintError::Overflow)
+    );
+}
+
+#[test]
+#[should_panic]
+fn panics_on_length_mismatch() {
+    encode_interleaved(&[1, 2], &[1], &mut Vec::new());
+}
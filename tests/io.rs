@@ -0,0 +1,75 @@
+#![cfg(feature = "std")]
+
+use std::io::{self, Read};
+
+use zigzag::io::{ZigZagReadExt, ZigZagWriteExt};
+use zigzag::varint::write_zigzag_varint;
+use zigzag::DecodeError;
+
+fn inner_decode_error(err: io::Error) -> Option<DecodeError> {
+    err.into_inner()?.downcast_ref().copied()
+}
+
+#[test]
+fn stream_round_trip_matches_slice_encoding() {
+    let values = [0, -1, 1, 150, -150, i64::MIN, i64::MAX];
+    let mut stream = Vec::new();
+    let mut expected = Vec::new();
+    for &x in &values {
+        let len = stream.write_zigzag_varint_i64(x).unwrap();
+        assert_eq!(len, write_zigzag_varint(x, &mut expected));
+    }
+    stream.write_zigzag_varint_i32(i32::MIN).unwrap();
+    stream.write_zigzag_varint_i16(i16::MAX).unwrap();
+    write_zigzag_varint(i32::MIN.into(), &mut expected);
+    write_zigzag_varint(i16::MAX.into(), &mut expected);
+    assert_eq!(stream, expected);
+
+    let mut input = &stream[..];
+    for &x in &values {
+        assert_eq!(input.read_zigzag_varint_i64().unwrap(), x);
+    }
+    assert_eq!(input.read_zigzag_varint_i32().unwrap(), i32::MIN);
+    assert_eq!(input.read_zigzag_varint_i16().unwrap(), i16::MAX);
+    assert!(input.is_empty());
+}
+
+#[test]
+fn reads_stop_at_the_end_of_the_varint() {
+    let mut input = &[0xac, 0x02, 0xff][..];
+    assert_eq!(input.read_zigzag_varint_i64().unwrap(), 150);
+    let mut rest = Vec::new();
+    input.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, [0xff]);
+}
+
+#[test]
+fn truncated_input_is_unexpected_eof() {
+    for input in [&[][..], &[0x80], &[0xff, 0xff]] {
+        let err = { input }.read_zigzag_varint_i64().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(
+            inner_decode_error(err),
+            Some(DecodeError::Truncated { needed: 1 })
+        );
+    }
+}
+
+#[test]
+fn overflowing_varints_are_invalid_data() {
+    fn assert_overflow<T: std::fmt::Debug>(result: io::Result<T>) {
+        let err = result.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(inner_decode_error(err), Some(DecodeError::Overflow));
+    }
+
+    let mut too_long = [0xff; 10];
+    too_long[9] = 0x02;
+    assert_overflow((&too_long[..]).read_zigzag_varint_i64());
+    assert_overflow((&[0x80, 0x80, 0x80, 0x80, 0x10][..]).read_zigzag_varint_i32());
+    assert_overflow((&[0x80, 0x80, 0x04][..]).read_zigzag_varint_i16());
+
+    // The widest values of each type still fit.
+    let mut input = &[0xff, 0xff, 0xff, 0xff, 0x0f][..];
+    assert_eq!(input.read_zigzag_varint_i32().unwrap(), i32::MIN);
+}
@@ -0,0 +1,73 @@
+use std::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
+
+use zigzag::{ZigZagDecodeIterExt, ZigZagEncodeIterExt};
+
+#[test]
+fn encodes_nonzero_items() {
+    let encoded: Vec<NonZeroU32> = vec![NonZeroI32::new(-1).unwrap()]
+        .into_iter()
+        .zigzag_encoded()
+        .collect();
+    assert_eq!(encoded, [NonZeroU32::new(1).unwrap()]);
+}
+
+#[test]
+fn round_trips_nonzero_items() {
+    let values: Vec<NonZeroI64> = [1, -1, 2, -2, i64::MAX, i64::MIN]
+        .iter()
+        .map(|&x| NonZeroI64::new(x).unwrap())
+        .collect();
+
+    let encoded: Vec<NonZeroU64> = values.iter().copied().zigzag_encoded().collect();
+    let expected: Vec<u64> = vec![2, 1, 4, 3, u64::MAX - 1, u64::MAX];
+    assert_eq!(
+        encoded.iter().map(|x| x.get()).collect::<Vec<_>>(),
+        expected
+    );
+
+    let decoded: Vec<NonZeroI64> = encoded.into_iter().zigzag_decoded().collect();
+    assert_eq!(decoded, values);
+}
+
+#[test]
+fn round_trips_option_items() {
+    let values = vec![Some(-1i32), None, Some(i32::MIN), Some(0), None];
+
+    let encoded: Vec<Option<u32>> = values.clone().into_iter().zigzag_encoded().collect();
+    assert_eq!(encoded, [Some(1), None, Some(u32::MAX), Some(0), None]);
+
+    let decoded: Vec<Option<i32>> = encoded.into_iter().zigzag_decoded().collect();
+    assert_eq!(decoded, values);
+}
+
+#[test]
+fn adapters_forward_iterator_traits() {
+    use std::iter::FusedIterator;
+
+    fn assert_fused<I: FusedIterator>(_: &I) {}
+
+    let values = [0i16, -1, 1, i16::MIN, i16::MAX];
+    let mut encoded = values.iter().copied().zigzag_encoded::<u16>();
+    assert_fused(&encoded);
+    assert_eq!(encoded.len(), 5);
+    assert_eq!(encoded.next_back(), Some(u16::MAX - 1));
+    assert_eq!(encoded.next(), Some(0));
+    assert_eq!(encoded.len(), 3);
+    assert_eq!(encoded.nth(1), Some(2));
+    assert_eq!(encoded.collect::<Vec<_>>(), [u16::MAX]);
+
+    let reversed: Vec<u16> = values.iter().copied().zigzag_encoded().rev().collect();
+    assert_eq!(reversed, [u16::MAX - 1, u16::MAX, 2, 1, 0]);
+    let sum = values
+        .iter()
+        .copied()
+        .zigzag_encoded::<u16>()
+        .fold(0u32, |acc, x| acc + u32::from(x));
+    assert_eq!(sum, 1 + 2 + 2 * u32::from(u16::MAX) - 1);
+
+    let mut decoded = reversed.into_iter().zigzag_decoded::<i16>();
+    assert_eq!(decoded.len(), 5);
+    assert_eq!(decoded.next_back(), Some(0));
+    let rest: Vec<i16> = decoded.rev().collect();
+    assert_eq!(rest, [-1, 1, i16::MIN, i16::MAX]);
+}
@@ -0,0 +1,31 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use zigzag::encoded::ZigZagKey;
+
+fn hash_of<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn equal_keys_hash_equal() {
+    let a = ZigZagKey::from(-1i32);
+    let b = ZigZagKey::from(-1i32);
+    assert!(a == b);
+    assert_eq!(hash_of(&a), hash_of(&b));
+}
+
+#[test]
+fn keys_collide_like_signed_values() {
+    let mut map = HashMap::new();
+    map.insert(ZigZagKey::from(i128::MIN), 1);
+    map.insert(ZigZagKey::from(-1i128), 2);
+    map.insert(ZigZagKey::from(-1i128), 3);
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[&ZigZagKey::from(-1i128)], 3);
+    assert_eq!(map[&ZigZagKey::from(i128::MIN)], 1);
+}
@@ -0,0 +1,38 @@
+use zigzag::lut::{ZIGZAG_DECODE_U8, ZIGZAG_ENCODE_I8};
+use zigzag::{ZigZagDecode, ZigZagEncode};
+
+fn encode_formula(x: i8) -> u8 {
+    ((x >> 7) ^ (x << 1)) as u8
+}
+
+fn decode_formula(x: u8) -> i8 {
+    (x >> 1) as i8 ^ -((x & 1) as i8)
+}
+
+#[test]
+fn encode_table_matches_formula() {
+    for x in i8::MIN..=i8::MAX {
+        assert_eq!(ZIGZAG_ENCODE_I8[x as u8 as usize], encode_formula(x));
+        assert_eq!(x.zigzag_encode(), encode_formula(x));
+    }
+}
+
+#[test]
+fn decode_table_matches_formula() {
+    for x in u8::MIN..=u8::MAX {
+        assert_eq!(ZIGZAG_DECODE_U8[x as usize], decode_formula(x));
+        assert_eq!(x.zigzag_decode(), decode_formula(x));
+    }
+}
+
+#[cfg(feature = "lut16")]
+#[test]
+fn decode_table_u16_matches_formula() {
+    use zigzag::lut::ZIGZAG_DECODE_U16;
+
+    for x in u16::MIN..=u16::MAX {
+        let expected = (x >> 1) as i16 ^ -((x & 1) as i16);
+        assert_eq!(ZIGZAG_DECODE_U16[x as usize], expected);
+        assert_eq!(x.zigzag_decode(), expected);
+    }
+}
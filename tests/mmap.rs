@@ -0,0 +1,52 @@
+#![cfg(feature = "memmap2")]
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use zigzag::mmap::MappedZigZagBlock;
+use zigzag::varint::zigzag_encode_all;
+use zigzag::{DecodeError, VarintError};
+
+fn temp_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("zigzag-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn mapped_block_decodes_file() {
+    let values: Vec<i64> = (-5000..5000).map(|x| x * x * x).collect();
+    let path = temp_path("whole");
+    fs::write(&path, zigzag_encode_all(&values)).unwrap();
+
+    let block = unsafe { MappedZigZagBlock::open(&path) }.unwrap();
+    assert_eq!(block.len_hint(), values.len());
+    let decoded: Result<Vec<i64>, _> = block.iter().collect();
+    assert_eq!(decoded, Ok(values));
+
+    drop(block);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn mapped_block_reports_truncated_final_varint() {
+    let mut bytes = zigzag_encode_all(&[1, -1, 150]);
+    bytes.pop();
+    let path = temp_path("truncated");
+    fs::write(&path, &bytes).unwrap();
+
+    let block = unsafe { MappedZigZagBlock::open(&path) }.unwrap();
+    assert_eq!(block.len_hint(), 3);
+    let items: Vec<_> = (&block).into_iter().collect();
+    assert_eq!(items, [Ok(1), Ok(-1), Err(VarintError::Truncated)]);
+
+    let mut cursor = block.cursor();
+    assert_eq!(cursor.by_ref().take(2).count(), 2);
+    assert_eq!(
+        cursor.next(),
+        Some(Err(DecodeError::Truncated { needed: 1 }))
+    );
+    assert_eq!(cursor.position(), 2);
+
+    drop(block);
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,23 @@
+use zigzag::{impl_zigzag_newtype, ZigZagExt};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Meters(i32);
+
+impl_zigzag_newtype!(Meters, i32, u32);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Offset(i64);
+
+impl_zigzag_newtype!(Offset, i64, u64);
+
+#[test]
+fn newtype_round_trips() {
+    for &x in &[0, 1, -1, 1234, -1234, i32::MIN, i32::MAX] {
+        let encoded = Meters(x).zigzag_encode();
+        assert_eq!(encoded, zigzag::ZigZagEncode::zigzag_encode(x));
+        assert_eq!(Meters::zigzag_decode(encoded), Meters(x));
+    }
+    for &x in &[0, -1, i64::MIN, i64::MAX] {
+        assert_eq!(Offset::zigzag_decode(Offset(x).zigzag_encode()), Offset(x));
+    }
+}
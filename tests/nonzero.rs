@@ -0,0 +1,96 @@
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+use zigzag::{ZigZag, ZigZagDecode, ZigZagEncode};
+
+fn nonzero_isize(x: isize) -> NonZeroIsize {
+    NonZeroIsize::new(x).unwrap()
+}
+
+fn nonzero_usize(x: usize) -> NonZeroUsize {
+    NonZeroUsize::new(x).unwrap()
+}
+
+#[test]
+fn pointer_sized_boundaries() {
+    assert_eq!(
+        <NonZeroIsize as ZigZag>::ENCODED_NEG_ONE,
+        nonzero_isize(-1).zigzag_encode()
+    );
+    assert_eq!(NonZeroIsize::MIN.zigzag_encode(), NonZeroUsize::MAX);
+    assert_eq!(NonZeroUsize::MAX.zigzag_decode(), NonZeroIsize::MIN);
+    assert_eq!(
+        NonZeroIsize::MAX.zigzag_encode(),
+        nonzero_usize(usize::MAX - 1)
+    );
+    assert_eq!(nonzero_isize(-1).zigzag_encode(), nonzero_usize(1));
+    assert_eq!(nonzero_isize(1).zigzag_encode(), nonzero_usize(2));
+}
+
+#[test]
+#[cfg(target_pointer_width = "16")]
+fn pointer_width_16() {
+    assert_eq!(isize::BITS, 16);
+    assert_eq!(NonZeroIsize::MIN.zigzag_encode().get(), 0xffff);
+    assert_eq!(nonzero_isize(-0x4000).zigzag_encode().get(), 0x7fff);
+}
+
+#[test]
+#[cfg(target_pointer_width = "32")]
+fn pointer_width_32() {
+    assert_eq!(isize::BITS, 32);
+    assert_eq!(NonZeroIsize::MIN.zigzag_encode().get(), 0xffff_ffff);
+    assert_eq!(
+        nonzero_isize(-0x4000_0000).zigzag_encode().get(),
+        0x7fff_ffff
+    );
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn pointer_width_64() {
+    assert_eq!(isize::BITS, 64);
+    assert_eq!(
+        NonZeroIsize::MIN.zigzag_encode().get(),
+        0xffff_ffff_ffff_ffff
+    );
+    assert_eq!(
+        nonzero_isize(-0x4000_0000_0000_0000).zigzag_encode().get(),
+        0x7fff_ffff_ffff_ffff
+    );
+    // Values beyond 32 bits must not be truncated to a narrower width.
+    assert_eq!(nonzero_isize(1 << 40).zigzag_encode().get(), 1 << 41);
+}
+
+#[test]
+fn narrow_widths_match_primitive_encoding_exhaustively() {
+    for x in (i8::MIN..=i8::MAX).filter_map(NonZeroI8::new) {
+        let encoded: NonZeroU8 = x.zigzag_encode();
+        assert_eq!(encoded.get(), x.get().zigzag_encode());
+        assert_eq!(encoded.zigzag_decode(), x);
+    }
+    for x in (i16::MIN..=i16::MAX).filter_map(NonZeroI16::new) {
+        let encoded: NonZeroU16 = x.zigzag_encode();
+        assert_eq!(encoded.get(), x.get().zigzag_encode());
+        assert_eq!(encoded.zigzag_decode(), x);
+    }
+}
+
+macro_rules! boundaries {
+    ($name:ident, $signed:ident, $unsigned:ident) => {
+        #[test]
+        fn $name() {
+            assert_eq!($signed::MIN.zigzag_encode(), $unsigned::MAX);
+            assert_eq!($unsigned::MAX.zigzag_decode(), $signed::MIN);
+            assert_eq!($signed::MAX.zigzag_encode().get(), $unsigned::MAX.get() - 1);
+            assert_eq!($unsigned::MIN.zigzag_decode().get(), -1);
+            assert_eq!($unsigned::new(2).unwrap().zigzag_decode().get(), 1);
+        }
+    };
+}
+
+boundaries!(boundaries_32, NonZeroI32, NonZeroU32);
+boundaries!(boundaries_64, NonZeroI64, NonZeroU64);
+boundaries!(boundaries_128, NonZeroI128, NonZeroU128);
@@ -0,0 +1,69 @@
+#![cfg(feature = "num-bigint")]
+
+use num_bigint::{BigInt, BigUint, Sign};
+use zigzag::bigint::zigzag_encode_le;
+use zigzag::{ZigZagDecode, ZigZagEncode};
+
+fn pow2(bits: u32) -> BigInt {
+    BigInt::from(1) << bits
+}
+
+// 0, ±1, and the values either side of ±2^64 and ±2^128.
+fn samples() -> Vec<BigInt> {
+    let mut samples = vec![BigInt::from(0), BigInt::from(1), BigInt::from(-1)];
+    for &bits in &[64, 128] {
+        for delta in -1..=1 {
+            let x = pow2(bits) + delta;
+            samples.push(-&x);
+            samples.push(x);
+        }
+    }
+    samples
+}
+
+#[test]
+fn small_values() {
+    assert_eq!(BigInt::from(0).zigzag_encode(), BigUint::from(0u32));
+    assert_eq!(BigInt::from(-1).zigzag_encode(), BigUint::from(1u32));
+    assert_eq!(BigInt::from(1).zigzag_encode(), BigUint::from(2u32));
+    assert_eq!(BigUint::from(0u32).zigzag_decode(), BigInt::from(0));
+    assert_eq!(BigUint::from(1u32).zigzag_decode(), BigInt::from(-1));
+    assert_eq!(BigUint::from(2u32).zigzag_decode(), BigInt::from(1));
+}
+
+#[test]
+fn values_around_powers_of_two_round_trip() {
+    for x in samples() {
+        let expected: BigUint = if x.sign() == Sign::Minus {
+            x.magnitude() * 2u32 - 1u32
+        } else {
+            x.magnitude() * 2u32
+        };
+        let encoded: BigUint = (&x).zigzag_encode();
+        assert_eq!(encoded, expected, "encoding {}", x);
+        assert_eq!(x.clone().zigzag_encode(), expected);
+
+        let decoded: BigInt = (&encoded).zigzag_decode();
+        assert_eq!(decoded, x);
+        assert_eq!(encoded.zigzag_decode(), x);
+    }
+
+    // -(2^n) encodes to 2^(n + 1) - 1.
+    assert_eq!((-pow2(64)).zigzag_encode(), BigUint::from(u128::MAX >> 63));
+    let encoded: BigUint = (-pow2(128)).zigzag_encode();
+    assert_eq!(encoded + 1u32, BigUint::from(1u32) << 129u32);
+}
+
+#[test]
+fn matches_primitives_and_byte_helpers() {
+    for &x in &[0, -1, 1, i128::MIN, i128::MAX, -(1 << 64), 1 << 64] {
+        let big: BigUint = BigInt::from(x).zigzag_encode();
+        assert_eq!(big, BigUint::from(x.zigzag_encode()));
+        assert_eq!(big.zigzag_decode(), BigInt::from(x));
+    }
+    for x in samples() {
+        let (sign, magnitude) = x.to_bytes_le();
+        let bytes = zigzag_encode_le(sign == Sign::Minus, &magnitude);
+        assert_eq!(BigUint::from_bytes_le(&bytes), x.zigzag_encode());
+    }
+}
@@ -0,0 +1,50 @@
+use zigzag::offset::{OrderedDecode, OrderedEncode};
+
+#[test]
+fn preserves_order_exhaustively_for_narrow_widths() {
+    let mut prev = None;
+    for x in i8::MIN..=i8::MAX {
+        let encoded: u8 = x.ordered_encode();
+        assert!(prev < Some(encoded));
+        assert_eq!(encoded.ordered_decode(), x);
+        prev = Some(encoded);
+    }
+    let mut prev = None;
+    for x in i16::MIN..=i16::MAX {
+        let encoded: u16 = x.ordered_encode();
+        assert!(prev < Some(encoded));
+        assert_eq!(encoded.ordered_decode(), x);
+        prev = Some(encoded);
+    }
+}
+
+macro_rules! boundaries {
+    ($name:ident, $signed:ty, $unsigned:ty) => {
+        #[test]
+        fn $name() {
+            let values = [<$signed>::MIN, <$signed>::MIN + 1, -1, 0, 1, <$signed>::MAX];
+            let encoded: Vec<$unsigned> = values.iter().map(|&x| x.ordered_encode()).collect();
+            assert!(encoded.windows(2).all(|w| w[0] < w[1]));
+            assert_eq!(encoded[0], 0);
+            assert_eq!(encoded[3], 1 << (<$unsigned>::BITS - 1));
+            assert_eq!(encoded[5], <$unsigned>::MAX);
+            for (&x, &e) in values.iter().zip(&encoded) {
+                assert_eq!(OrderedDecode::<$signed>::ordered_decode(e), x);
+            }
+        }
+    };
+}
+
+boundaries!(boundaries_32, i32, u32);
+boundaries!(boundaries_64, i64, u64);
+boundaries!(boundaries_128, i128, u128);
+boundaries!(boundaries_isize, isize, usize);
+
+#[test]
+fn matches_order_preserving_newtype() {
+    use zigzag::encoded::OrderPreservingEncoded;
+
+    for &x in &[i32::MIN, -1, 0, 1, i32::MAX] {
+        assert_eq!(OrderPreservingEncoded::from_signed(x).0, x.ordered_encode());
+    }
+}
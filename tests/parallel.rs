@@ -0,0 +1,164 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::delta::{delta_zigzag_decode, delta_zigzag_encode};
+use zigzag::parallel::{
+    par_decode_slice, par_delta_zigzag_decode, par_delta_zigzag_encode, par_encode_slice,
+    par_zigzag_decode_slice, par_zigzag_encode_slice, par_zigzag_varint_encode, MIN_PARALLEL_LEN,
+};
+use zigzag::varint::zigzag_encode_all;
+use zigzag::{VarintError, ZigZagDecode, ZigZagEncode};
+
+mod common;
+
+fn random_i32s(len: usize) -> Vec<i32> {
+    common::random_u64s().take(len).map(|x| x as i32).collect()
+}
+
+#[test]
+fn parallel_encode_matches_sequential() {
+    let src = random_i32s(1 << 20);
+    let expected: Vec<u32> = src.iter().map(|&x| x.zigzag_encode()).collect();
+
+    let mut dst = vec![0u32; src.len()];
+    par_zigzag_encode_slice(&src, &mut dst);
+    assert_eq!(dst, expected);
+}
+
+#[test]
+fn parallel_decode_matches_sequential() {
+    let src: Vec<u32> = random_i32s(1 << 20).iter().map(|&x| x as u32).collect();
+    let expected: Vec<i32> = src.iter().map(|&x| x.zigzag_decode()).collect();
+
+    let mut dst = vec![0i32; src.len()];
+    par_zigzag_decode_slice(&src, &mut dst);
+    assert_eq!(dst, expected);
+}
+
+#[test]
+fn short_names_are_the_same_functions() {
+    let src = random_i32s(MIN_PARALLEL_LEN * 2);
+    let mut encoded = vec![0u32; src.len()];
+    par_encode_slice(&src, &mut encoded);
+    let mut decoded = vec![0i32; src.len()];
+    par_decode_slice(&encoded, &mut decoded);
+    assert_eq!(decoded, src);
+}
+
+#[test]
+#[should_panic]
+fn parallel_encode_rejects_mismatched_lengths() {
+    par_zigzag_encode_slice(&[1i32, 2], &mut [0u32; 3]);
+}
+
+#[test]
+fn parallel_varint_encode_matches_sequential() {
+    let src: Vec<i64> = random_i32s(1 << 20)
+        .chunks(2)
+        .enumerate()
+        .map(|(i, pair)| {
+            // Mix small and full-width values so every varint length appears.
+            let wide = (i64::from(pair[0]) << 32) | i64::from(pair[1] as u32);
+            wide >> (i % 64)
+        })
+        .collect();
+
+    assert_eq!(par_zigzag_varint_encode(&src), zigzag_encode_all(&src));
+}
+
+#[test]
+fn parallel_delta_encode_matches_sequential() {
+    // A slowly drifting series with occasional jumps across the whole range.
+    let mut value = 0i64;
+    let values: Vec<i64> = random_i32s(1 << 20)
+        .iter()
+        .map(|&step| {
+            value = if step % 1000 == 0 {
+                i64::from(step) << 32
+            } else {
+                value.wrapping_add(i64::from(step >> 20))
+            };
+            value
+        })
+        .collect();
+
+    let mut expected = vec![0xaa];
+    delta_zigzag_encode(&values, &mut expected);
+    let mut out = vec![0xaa];
+    par_delta_zigzag_encode(&values, &mut out);
+    assert_eq!(out, expected);
+    assert_eq!(delta_zigzag_decode(&out[1..]), Ok(values));
+}
+
+#[test]
+fn parallel_delta_decode_matches_sequential() {
+    // Wide jumps give varints of every length, so chunks end mid-varint.
+    let mut value = 0i64;
+    let values: Vec<i64> = random_i32s(1 << 20)
+        .iter()
+        .map(|&step| {
+            value = value.wrapping_add(i64::from(step) << (step & 31));
+            value
+        })
+        .collect();
+    let mut encoded = Vec::new();
+    delta_zigzag_encode(&values, &mut encoded);
+    assert_eq!(par_delta_zigzag_decode(&encoded), Ok(values));
+}
+
+#[test]
+fn parallel_delta_decode_reports_the_first_error() {
+    let values: Vec<i64> = random_i32s(1 << 18).iter().map(|&x| i64::from(x)).collect();
+    let mut valid = Vec::new();
+    delta_zigzag_encode(&values, &mut valid);
+
+    let mut truncated = valid.clone();
+    truncated.push(0x80);
+    assert_eq!(
+        par_delta_zigzag_decode(&truncated),
+        Err(VarintError::Truncated)
+    );
+
+    // An overflowing varint in the middle wins over the truncated end.
+    let mut overflow = valid[..valid.len() / 2].to_vec();
+    overflow.extend_from_slice(&[0xff; 12]);
+    overflow.extend_from_slice(&truncated[valid.len() / 2..]);
+    assert_eq!(delta_zigzag_decode(&overflow), Err(VarintError::Overflow));
+    assert_eq!(
+        par_delta_zigzag_decode(&overflow),
+        Err(VarintError::Overflow)
+    );
+
+    // Continuation bytes past the last varint overflow rather than truncate.
+    let mut trailing = valid;
+    trailing.extend_from_slice(&[0xff; 11]);
+    assert_eq!(
+        par_delta_zigzag_decode(&trailing),
+        delta_zigzag_decode(&trailing)
+    );
+}
+
+#[test]
+fn short_inputs_around_the_threshold_match_sequential() {
+    for len in [
+        0,
+        1,
+        MIN_PARALLEL_LEN - 1,
+        MIN_PARALLEL_LEN,
+        MIN_PARALLEL_LEN + 1,
+    ] {
+        let src: Vec<i64> = random_i32s(len).iter().map(|&x| i64::from(x) * 3).collect();
+
+        let mut encoded = vec![0u64; len];
+        par_zigzag_encode_slice(&src, &mut encoded);
+        let mut decoded = vec![0i64; len];
+        par_zigzag_decode_slice(&encoded, &mut decoded);
+        assert_eq!(decoded, src);
+
+        assert_eq!(par_zigzag_varint_encode(&src), zigzag_encode_all(&src));
+        let (mut expected, mut out) = (Vec::new(), Vec::new());
+        delta_zigzag_encode(&src, &mut expected);
+        par_delta_zigzag_encode(&src, &mut out);
+        assert_eq!(out, expected);
+        assert_eq!(par_delta_zigzag_decode(&out), Ok(src));
+    }
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::parquet_delta::{decode_i32, decode_i64, encode_i32, encode_i64, BlockLayout};
+use zigzag::varint::write_uvarint;
+use zigzag::DecodeError;
+
+mod common;
+
+fn random_i64s(len: usize) -> Vec<i64> {
+    common::random_u64s().take(len).map(|x| x as i64).collect()
+}
+
+#[test]
+fn matches_the_spec_examples() {
+    let mut out = Vec::new();
+    encode_i64(&[1, 2, 3, 4, 5], BlockLayout::default(), &mut out);
+    assert_eq!(
+        out,
+        [0x80, 0x01, 0x04, 0x05, 0x02, 0x02, 0x00, 0x00, 0x00, 0x00]
+    );
+
+    let mut out = Vec::new();
+    encode_i32(&[], BlockLayout::default(), &mut out);
+    assert_eq!(out, [0x80, 0x01, 0x04, 0x00, 0x00]);
+    assert_eq!(decode_i32(&out), Ok((Vec::new(), 5)));
+
+    let mut out = Vec::new();
+    encode_i32(&[-3], BlockLayout::default(), &mut out);
+    assert_eq!(out, [0x80, 0x01, 0x04, 0x01, 0x05]);
+}
+
+#[test]
+fn round_trips_across_blocks() {
+    let random = random_i64s(1000);
+    let slow: Vec<i64> = (0..1000).map(|i| 1_000_000 + i * 3 - i % 7).collect();
+    let extremes = [i64::MIN, i64::MAX, 0, i64::MIN, -1, i64::MAX];
+    let layouts = [
+        BlockLayout::default(),
+        BlockLayout::new(256, 8).unwrap(),
+        BlockLayout::new(128, 1).unwrap(),
+    ];
+    for values in [&random[..], &slow, &extremes, &random[..129], &random[..2]] {
+        for &layout in &layouts {
+            let mut out = vec![0xaa];
+            encode_i64(values, layout, &mut out);
+            out.extend_from_slice(b"rest");
+            let (decoded, len) = decode_i64(&out[1..]).unwrap();
+            assert_eq!(decoded, values);
+            assert_eq!(&out[1 + len..], b"rest");
+
+            let narrow: Vec<i32> = values.iter().map(|&x| x as i32).collect();
+            let mut out = Vec::new();
+            encode_i32(&narrow, layout, &mut out);
+            assert_eq!(decode_i32(&out), Ok((narrow, out.len())));
+        }
+    }
+}
+
+#[test]
+fn slow_series_pack_tightly() {
+    let values: Vec<i64> = (0..128 * 8 + 1).map(|i| (1 << 40) + i * 2).collect();
+    let mut out = Vec::new();
+    encode_i64(&values, BlockLayout::default(), &mut out);
+    // The header, then eight blocks of one byte of minimum delta and four of
+    // zero bit widths.
+    assert_eq!(out.len(), 3 + 1 + 1 + 6 + 8 * 5);
+}
+
+#[test]
+fn int32_columns_wrap_in_32_bits() {
+    let values = [i32::MIN, i32::MAX, i32::MIN, i32::MAX];
+    let mut out = Vec::new();
+    encode_i32(&values, BlockLayout::default(), &mut out);
+    // The deltas -1, 1 and -1 differ from their minimum by at most 2.
+    assert_eq!(out[10..14], [2, 0, 0, 0]);
+    assert_eq!(decode_i32(&out), Ok((values.to_vec(), out.len())));
+}
+
+#[test]
+fn rejects_malformed_input() {
+    let mut out = Vec::new();
+    encode_i64(&random_i64s(300), BlockLayout::default(), &mut out);
+    for len in 0..out.len() {
+        assert!(
+            matches!(decode_i64(&out[..len]), Err(DecodeError::Truncated { .. })),
+            "{}",
+            len
+        );
+    }
+    // Two values, the second in an 8-bit miniblock of 32 bytes.
+    assert_eq!(
+        decode_i64(&[0x80, 0x01, 0x04, 0x02, 0x00, 0x00, 8, 0, 0, 0, 0xff, 0, 0, 0]),
+        Err(DecodeError::Truncated { needed: 28 })
+    );
+
+    // A block size which isn't a multiple of 128.
+    assert_eq!(
+        decode_i64(&[0x40, 0x01, 0x00, 0x00]),
+        Err(DecodeError::OutOfRange)
+    );
+    // A first value outside the range of `i32`.
+    assert_eq!(
+        decode_i32(&[0x80, 0x01, 0x04, 0x01, 0x80, 0x80, 0x80, 0x80, 0x10]),
+        Err(DecodeError::OutOfRange)
+    );
+    // A bit width of 33 in an `INT32` column.
+    assert_eq!(
+        decode_i32(&[0x80, 0x01, 0x04, 0x02, 0x00, 0x00, 33, 0, 0, 0]),
+        Err(DecodeError::OutOfRange)
+    );
+
+    // Hostile headers, each followed by a smallest difference of zero and
+    // the miniblock widths: the output isn't allocated from the counts, and
+    // the packed lengths don't overflow.
+    let hostile = |block_size: u64, count: u64, widths: &[u8]| {
+        let mut input = Vec::new();
+        for x in [block_size, widths.len() as u64, count, 0, 0] {
+            write_uvarint(x, &mut input);
+        }
+        input.extend_from_slice(widths);
+        input
+    };
+    assert!(matches!(
+        decode_i64(&hostile(128 << 50, 1 << 50, &[64])),
+        Err(DecodeError::Truncated { .. })
+    ));
+    assert!(matches!(
+        decode_i32(&hostile(128 << 50, 1 << 50, &[32, 32, 32, 32])),
+        Err(DecodeError::Truncated { .. })
+    ));
+    assert_eq!(
+        decode_i64(&hostile(128 << 55, 2, &[64])),
+        Err(DecodeError::OutOfRange)
+    );
+    assert_eq!(
+        decode_i64(&hostile(128 << 50, 1 << 50, &[0])),
+        Err(DecodeError::OutOfRange)
+    );
+}
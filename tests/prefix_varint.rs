@@ -0,0 +1,112 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::prefix_varint::{
+    read_uvarint, read_zigzag_varint, uvarint_len, write_uvarint, write_zigzag_varint,
+    write_zigzag_varint_slice, zigzag_decode_all, zigzag_encode_all, zigzag_varint_len,
+    MAX_VARINT_LEN,
+};
+use zigzag::VarintError;
+
+fn boundaries() -> Vec<u64> {
+    let mut values = vec![0, 1, u64::MAX, u64::MAX - 1];
+    for bits in (7..64).step_by(7).chain([63]) {
+        let edge = 1u64 << bits;
+        values.extend([edge - 1, edge, edge + 1]);
+    }
+    values
+}
+
+#[test]
+fn unsigned_boundaries_round_trip_at_their_length() {
+    for x in boundaries() {
+        let mut out = Vec::new();
+        let len = write_uvarint(x, &mut out);
+        assert_eq!(len, out.len());
+        assert_eq!(len, uvarint_len(x), "{:#x}", x);
+        assert_eq!(out[0].leading_ones() as usize, len - 1);
+        assert_eq!(read_uvarint(&out), Ok((x, len)));
+    }
+    assert_eq!(uvarint_len((1 << 56) - 1), 8);
+    assert_eq!(uvarint_len(1 << 56), MAX_VARINT_LEN);
+}
+
+#[test]
+fn lengths_match_leb128_up_to_eight_bytes() {
+    for x in boundaries() {
+        let leb128 = zigzag::varint::uvarint_len(x);
+        assert_eq!(uvarint_len(x), leb128.min(MAX_VARINT_LEN));
+    }
+}
+
+#[test]
+fn signed_values_round_trip() {
+    let mut values: Vec<i64> = (-1000..1000).collect();
+    values.extend(boundaries().iter().map(|&x| x as i64));
+    values.extend(boundaries().iter().map(|&x| (x as i64).wrapping_neg()));
+
+    let encoded = zigzag_encode_all(&values);
+    assert_eq!(
+        encoded.len(),
+        values.iter().map(|&x| zigzag_varint_len(x)).sum::<usize>()
+    );
+    assert_eq!(zigzag_decode_all(&encoded), Ok(values.clone()));
+
+    let mut buf = [0u8; MAX_VARINT_LEN];
+    for &x in &values {
+        let len = write_zigzag_varint_slice(x, &mut buf).unwrap();
+        let mut out = Vec::new();
+        write_zigzag_varint(x, &mut out);
+        assert_eq!(buf[..len], out[..]);
+        assert_eq!(read_zigzag_varint(&buf), Ok((x, len)));
+        if len > 1 {
+            assert_eq!(write_zigzag_varint_slice(x, &mut buf[..len - 1]), None);
+        }
+    }
+}
+
+#[test]
+fn every_truncation_is_detected() {
+    let encoded = zigzag_encode_all(&[i64::MIN]);
+    for len in 0..encoded.len() {
+        assert_eq!(read_uvarint(&encoded[..len]), Err(VarintError::Truncated));
+    }
+    assert_eq!(
+        zigzag_decode_all(&[0x01, 0x80]),
+        Err(VarintError::Truncated)
+    );
+}
+
+#[test]
+fn non_minimal_encodings_are_accepted() {
+    assert_eq!(read_uvarint(&[0b10_000001, 0x00]), Ok((1, 2)));
+    assert_eq!(read_uvarint(&[0xff, 1, 0, 0, 0, 0, 0, 0, 0]), Ok((1, 9)));
+}
+
+#[test]
+fn decode_varint_from_slice_reports_exactly_what_is_missing() {
+    use zigzag::prefix_varint::decode_varint_from_slice;
+    use zigzag::DecodeError;
+
+    for &x in &[0, -1, 150, -10_000, i64::MAX, i64::MIN] {
+        let mut out = Vec::new();
+        write_zigzag_varint(x, &mut out);
+        assert_eq!(decode_varint_from_slice(&out), Ok(x));
+        assert_eq!(
+            decode_varint_from_slice(&[]),
+            Err(DecodeError::Truncated { needed: 1 })
+        );
+        for len in 1..out.len() {
+            assert_eq!(
+                decode_varint_from_slice(&out[..len]),
+                Err(DecodeError::Truncated {
+                    needed: out.len() - len
+                })
+            );
+        }
+        out.push(0);
+        assert_eq!(
+            decode_varint_from_slice(&out),
+            Err(DecodeError::TrailingGarbage)
+        );
+    }
+}
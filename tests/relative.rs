@@ -0,0 +1,39 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::relative::{decode_relative, encode_relative, optimal_base};
+use zigzag::VarintError;
+
+fn encoded_len(values: &[i64], base: i64) -> usize {
+    let mut out = Vec::new();
+    encode_relative(values, base, &mut out);
+    out.len()
+}
+
+#[test]
+fn round_trips_with_any_base() {
+    let values = [-5, 1000, 999, i64::MAX, i64::MIN, 0];
+    for &base in &[0, -5, 1000, i64::MIN, i64::MAX] {
+        let mut out = Vec::new();
+        encode_relative(&values, base, &mut out);
+        assert_eq!(decode_relative(&out), Ok(values.to_vec()));
+    }
+
+    let mut out = Vec::new();
+    encode_relative(&[], 7, &mut out);
+    assert_eq!(decode_relative(&out), Ok(Vec::new()));
+    assert_eq!(decode_relative(&[]), Err(VarintError::Truncated));
+}
+
+#[test]
+fn optimal_base_beats_zero_for_clustered_values() {
+    // Every value is within 50 of 1_000_000, in no particular order.
+    let values: Vec<i64> = (0..100).map(|i| 1_000_000 + (i * 37) % 101 - 50).collect();
+    let base = optimal_base(&values);
+    assert!((1_000_000 - 50..=1_000_000 + 50).contains(&base));
+    assert!(encoded_len(&values, base) <= encoded_len(&values, 0));
+    assert!(encoded_len(&values, base) <= 3 + 2 * values.len());
+
+    let centered = [-3, 0, 2, -1, 1];
+    assert_eq!(optimal_base(&centered), 0);
+    assert!(encoded_len(&centered, optimal_base(&centered)) <= encoded_len(&centered, 0));
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::separated::{
+    read_zigzag_varint_nonzero_terminal, write_zigzag_varint_nonzero_terminal,
+};
+use zigzag::VarintError;
+
+#[test]
+fn never_emits_zero_bytes() {
+    let mut values = vec![0, -1, 1, 63, -64, 64, i64::MIN, i64::MAX, i64::MIN + 1];
+    values.extend((0..64).map(|shift| 1i64 << shift));
+    values.extend((0..64).map(|shift| (1i64 << shift).wrapping_neg()));
+    values.extend((0..64).map(|shift| (1i64 << shift).wrapping_sub(1)));
+
+    for &x in &values {
+        let mut out = Vec::new();
+        write_zigzag_varint_nonzero_terminal(x, &mut out);
+        assert!(!out.contains(&0x00), "{}: {:02x?}", x, out);
+        assert!(out.len() <= 10);
+        assert_eq!(
+            read_zigzag_varint_nonzero_terminal(&out),
+            Ok((x, out.len()))
+        );
+    }
+}
+
+#[test]
+fn rejects_malformed_varints() {
+    assert_eq!(
+        read_zigzag_varint_nonzero_terminal(&[]),
+        Err(VarintError::Truncated)
+    );
+    assert_eq!(
+        read_zigzag_varint_nonzero_terminal(&[0x81]),
+        Err(VarintError::Truncated)
+    );
+    assert_eq!(
+        read_zigzag_varint_nonzero_terminal(&[0x00]),
+        Err(VarintError::OutOfRange)
+    );
+    assert_eq!(
+        read_zigzag_varint_nonzero_terminal(&[0x80; 11]),
+        Err(VarintError::Overflow)
+    );
+
+    // The largest biased value, 2^64, encodes `i64::MIN`; one more overflows.
+    let mut max = [0x80; 10];
+    max[9] = 0x02;
+    assert_eq!(
+        read_zigzag_varint_nonzero_terminal(&max),
+        Ok((i64::MIN, 10))
+    );
+    max[0] = 0x81;
+    assert_eq!(
+        read_zigzag_varint_nonzero_terminal(&max),
+        Err(VarintError::Overflow)
+    );
+}
+
+#[test]
+fn reads_records_until_separator() {
+    use zigzag::separated::read_until_separator;
+
+    let mut out = Vec::new();
+    for &x in &[0, i64::MIN, 300] {
+        write_zigzag_varint_nonzero_terminal(x, &mut out);
+    }
+    out.push(0x00);
+    let record_len = out.len();
+    out.push(0x00);
+    write_zigzag_varint_nonzero_terminal(-7, &mut out);
+
+    assert_eq!(
+        read_until_separator(&out, 0x00),
+        Ok((vec![0, i64::MIN, 300], record_len))
+    );
+    let rest = &out[record_len..];
+    assert_eq!(read_until_separator(rest, 0x00), Ok((vec![], 1)));
+    assert_eq!(
+        read_until_separator(&rest[1..], 0x00),
+        Err(VarintError::Truncated)
+    );
+    assert_eq!(read_until_separator(&[], 0x00), Err(VarintError::Truncated));
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "serde")]
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Record {
+    id: u32,
+    #[serde(with = "zigzag::serde_zigzag")]
+    offset: i64,
+    #[serde(with = "zigzag::serde_zigzag")]
+    small: i8,
+}
+
+#[test]
+fn annotated_fields_serialize_encoded() {
+    let record = Record {
+        id: 7,
+        offset: i64::MIN,
+        small: -1,
+    };
+    let json = serde_json::to_string(&record).unwrap();
+    assert_eq!(json, r#"{"id":7,"offset":18446744073709551615,"small":1}"#);
+    assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+}
+
+#[test]
+fn rejects_encodings_out_of_range() {
+    let json = r#"{"id":7,"offset":0,"small":256}"#;
+    assert!(serde_json::from_str::<Record>(json).is_err());
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Series {
+    #[serde(with = "zigzag::serde")]
+    base: i32,
+    deltas: Vec<zigzag::serde::ZigZag<i16>>,
+    last: Option<zigzag::serde::ZigZag<i64>>,
+}
+
+#[test]
+fn wrapper_serializes_encoded_wherever_it_appears() {
+    use zigzag::serde::ZigZag;
+
+    let series = Series {
+        base: -3,
+        deltas: vec![ZigZag(1), ZigZag(-1), ZigZag(i16::MIN)],
+        last: Some(ZigZag(-2)),
+    };
+    let json = serde_json::to_string(&series).unwrap();
+    assert_eq!(json, r#"{"base":5,"deltas":[2,1,65535],"last":3}"#);
+    assert_eq!(serde_json::from_str::<Series>(&json).unwrap(), series);
+
+    assert!(serde_json::from_str::<ZigZag<i16>>("65536").is_err());
+    assert_eq!(ZigZag::from(-1i8), ZigZag(-1));
+}
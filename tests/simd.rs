@@ -0,0 +1,86 @@
+#![cfg(feature = "simd")]
+#![feature(portable_simd)]
+
+use std::simd::Simd;
+
+use zigzag::simd::{
+    zigzag_decode_lanes, zigzag_decode_simd, zigzag_encode_lanes, zigzag_encode_simd,
+    zigzag_varint_len_simd,
+};
+use zigzag::varint::zigzag_varint_len;
+use zigzag::{ZigZagDecode, ZigZagEncode};
+
+mod common;
+
+fn random_u32s(len: usize) -> Vec<u32> {
+    let mut values: Vec<u32> = common::random_u64s().take(len).map(|x| x as u32).collect();
+    values.extend_from_slice(&[0, 1, 2, u32::MAX, u32::MAX - 1]);
+    values
+}
+
+#[test]
+fn simd_encode_matches_scalar() {
+    let src: Vec<i32> = random_u32s(10_003).iter().map(|&x| x as i32).collect();
+    let expected: Vec<u32> = src.iter().map(|&x| x.zigzag_encode()).collect();
+
+    let mut dst = vec![0; src.len()];
+    zigzag_encode_simd(&src, &mut dst);
+    assert_eq!(dst, expected);
+}
+
+#[test]
+fn simd_decode_matches_scalar() {
+    let src = random_u32s(10_003);
+    let expected: Vec<i32> = src.iter().map(|&x| x.zigzag_decode()).collect();
+
+    let mut dst = vec![0; src.len()];
+    zigzag_decode_simd(&src, &mut dst);
+    assert_eq!(dst, expected);
+}
+
+#[test]
+fn simd_varint_len_matches_scalar() {
+    let mut src: Vec<i64> = random_u32s(10_003)
+        .chunks_exact(2)
+        .enumerate()
+        .map(|(i, pair)| {
+            let x = (u64::from(pair[0]) << 32 | u64::from(pair[1])) as i64;
+            // Spread the values over every possible length.
+            x >> (i % 64)
+        })
+        .collect();
+    for shift in 0..64 {
+        let x = 1i64 << shift;
+        src.extend_from_slice(&[x, x.wrapping_sub(1), x.wrapping_neg(), !x]);
+    }
+    src.extend_from_slice(&[0, -1, i64::MIN, i64::MAX]);
+    let expected: Vec<u8> = src.iter().map(|&x| zigzag_varint_len(x) as u8).collect();
+
+    let mut lens = vec![0; src.len()];
+    zigzag_varint_len_simd(&src, &mut lens);
+    assert_eq!(lens, expected);
+}
+
+fn check_lanes<const N: usize>(values: &[u32]) {
+    for chunk in values.chunks_exact(N) {
+        let signed = Simd::<i32, N>::from_array(std::array::from_fn(|i| chunk[i] as i32));
+        let encoded = zigzag_encode_lanes(signed);
+        for (x, e) in signed.to_array().iter().zip(encoded.to_array().iter()) {
+            assert_eq!(x.zigzag_encode(), *e);
+        }
+        let decoded = zigzag_decode_lanes(Simd::<u32, N>::from_slice(chunk));
+        for (x, d) in chunk.iter().zip(decoded.to_array().iter()) {
+            assert_eq!(x.zigzag_decode(), *d);
+        }
+    }
+}
+
+#[test]
+fn lanes_match_scalar() {
+    let values = random_u32s(1019);
+    check_lanes::<1>(&values);
+    check_lanes::<2>(&values);
+    check_lanes::<4>(&values);
+    check_lanes::<8>(&values);
+    check_lanes::<16>(&values);
+}
@@ -0,0 +1,158 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::slice::zigzag_encode_slice_uninit;
+use zigzag::ZigZagEncode;
+
+fn encode_into_new_vec(src: &[i32]) -> Vec<u32> {
+    let mut dst = Vec::with_capacity(src.len());
+    // SAFETY: the slice of spare capacity is exactly as long as `src`.
+    let encoded =
+        unsafe { zigzag_encode_slice_uninit(src, &mut dst.spare_capacity_mut()[..src.len()]) };
+    assert_eq!(encoded.len(), src.len());
+    // SAFETY: every value up to `src.len()` was initialized above.
+    unsafe { dst.set_len(src.len()) };
+    dst
+}
+
+#[test]
+fn uninit_encode_matches_safe_encode() {
+    let src: Vec<i32> = (-1000..1000)
+        .map(|x| x * 1_000_003)
+        .chain([i32::MIN, i32::MAX, 0, -1])
+        .collect();
+    let expected: Vec<u32> = src.iter().map(|&x| x.zigzag_encode()).collect();
+    assert_eq!(encode_into_new_vec(&src), expected);
+    assert!(encode_into_new_vec(&[]).is_empty());
+}
+
+macro_rules! slice_round_trip {
+    ($name:ident, $signed:ty, $unsigned:ty) => {
+        #[test]
+        fn $name() {
+            use zigzag::slice::{
+                zigzag_decode_in_place, zigzag_decode_slice, zigzag_encode_in_place,
+                zigzag_encode_slice,
+            };
+            use zigzag::ZigZagDecode;
+
+            let src: Vec<$signed> = (0..1000u32)
+                .map(|i| (i as $signed).wrapping_mul(-0x61c8_8647i64 as $signed))
+                .chain([<$signed>::MIN, <$signed>::MAX, 0, -1])
+                .collect();
+            let expected: Vec<$unsigned> = src.iter().map(|&x| x.zigzag_encode()).collect();
+
+            let mut encoded = vec![0; src.len()];
+            zigzag_encode_slice(&src, &mut encoded);
+            assert_eq!(encoded, expected);
+            let mut decoded = vec![0; src.len()];
+            zigzag_decode_slice(&encoded, &mut decoded);
+            assert_eq!(decoded, src);
+
+            let mut buf = src.clone();
+            assert_eq!(zigzag_encode_in_place(&mut buf), &expected[..]);
+            let mut buf = expected.clone();
+            let decoded = zigzag_decode_in_place::<$signed>(&mut buf);
+            assert_eq!(decoded, &src[..]);
+            assert!(expected
+                .iter()
+                .zip(decoded.iter())
+                .all(|(&u, &s)| ZigZagDecode::zigzag_decode(u) == s));
+        }
+    };
+}
+
+slice_round_trip!(slices_i8, i8, u8);
+slice_round_trip!(slices_i16, i16, u16);
+slice_round_trip!(slices_i32, i32, u32);
+slice_round_trip!(slices_i64, i64, u64);
+slice_round_trip!(slices_i128, i128, u128);
+slice_round_trip!(slices_isize, isize, usize);
+
+#[test]
+#[should_panic(expected = "different lengths")]
+fn slice_encode_checks_lengths() {
+    zigzag::slice::zigzag_encode_slice(&[1i32, 2], &mut [0u32; 3]);
+}
+
+#[test]
+#[should_panic(expected = "different lengths")]
+fn uninit_encode_checks_lengths() {
+    let mut dst = Vec::<u32>::with_capacity(3);
+    // SAFETY: the mismatch panics before anything is returned.
+    unsafe { zigzag_encode_slice_uninit(&[1, 2], &mut dst.spare_capacity_mut()[..3]) };
+}
+
+// Lengths around the vector widths exercise both the vectorized loop and the
+// scalar remainder when the `fast` feature is enabled.
+#[test]
+fn slice_encode_matches_scalar_for_every_length() {
+    use zigzag::slice::{zigzag_decode_slice, zigzag_encode_slice};
+    use zigzag::ZigZagDecode;
+
+    let values: Vec<i64> = (0..40i64)
+        .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15u64 as i64) ^ -(i & 1))
+        .collect();
+    for len in 0..values.len() {
+        let src = &values[..len];
+        let mut encoded = vec![0u64; len];
+        zigzag_encode_slice(src, &mut encoded);
+        let expected: Vec<u64> = src.iter().map(|&x| x.zigzag_encode()).collect();
+        assert_eq!(encoded, expected);
+        let mut decoded = vec![0i64; len];
+        zigzag_decode_slice(&encoded, &mut decoded);
+        assert_eq!(decoded, src);
+
+        let src: Vec<i32> = src.iter().map(|&x| (x >> 32) as i32).collect();
+        let mut encoded = vec![0u32; len];
+        zigzag_encode_slice(&src, &mut encoded);
+        assert!(encoded
+            .iter()
+            .zip(&src)
+            .all(|(&e, &x)| e == x.zigzag_encode()));
+        let mut decoded = vec![0i32; len];
+        zigzag_decode_slice(&encoded, &mut decoded);
+        assert_eq!(decoded, src);
+
+        let src: Vec<i16> = src.iter().map(|&x| (x >> 16) as i16).collect();
+        let mut encoded = vec![0u16; len];
+        zigzag_encode_slice(&src, &mut encoded);
+        assert!(encoded
+            .iter()
+            .zip(&src)
+            .all(|(&e, &x)| e == x.zigzag_encode()));
+        let mut decoded = vec![0i16; len];
+        zigzag_decode_slice(&encoded, &mut decoded);
+        assert!(decoded
+            .iter()
+            .zip(&encoded)
+            .all(|(&d, &e)| d == e.zigzag_decode()));
+        assert_eq!(decoded, src);
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn vec_round_trip_reuses_the_allocation() {
+    use zigzag::slice::{zigzag_decode_vec, zigzag_encode_vec};
+
+    let mut values: Vec<i64> = Vec::with_capacity(100);
+    values.extend((-40..40).map(|x| x * 0x0123_4567_89ab));
+    values.extend([i64::MIN, i64::MAX]);
+    let expected: Vec<u64> = values.iter().map(|&x| x.zigzag_encode()).collect();
+    let (ptr, capacity) = (values.as_ptr() as usize, values.capacity());
+
+    let reused = zigzag_encode_vec(values);
+    assert_eq!(
+        (reused.as_ptr() as usize, reused.capacity()),
+        (ptr, capacity)
+    );
+    let decoded = zigzag_decode_vec::<i64>(reused);
+    assert_eq!(
+        (decoded.as_ptr() as usize, decoded.capacity()),
+        (ptr, capacity)
+    );
+    assert_eq!(zigzag_encode_vec(decoded), expected);
+
+    assert!(zigzag_encode_vec(Vec::<i8>::new()).is_empty());
+    assert_eq!(zigzag_decode_vec::<i128>(vec![u128::MAX]), [i128::MIN]);
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::streamvbyte::{control_len, decode_block, encode_block, max_encoded_len};
+use zigzag::DecodeError;
+
+mod common;
+
+fn sample(count: usize) -> Vec<i32> {
+    common::random_u64s()
+        .take(count)
+        // Mix lengths by shifting away a varying number of bits.
+        .map(|x| (x as i32) >> (x % 32))
+        .collect()
+}
+
+#[test]
+fn round_trips_every_length() {
+    for count in 0..100 {
+        let values = sample(count);
+        let mut block = vec![0xaa];
+        let len = encode_block(&values, &mut block);
+        assert_eq!(block.len(), 1 + len);
+        assert!(len <= max_encoded_len(count));
+
+        let mut decoded = vec![0; count];
+        assert_eq!(decode_block(&block[1..], &mut decoded), Ok(len));
+        assert_eq!(decoded, values);
+    }
+}
+
+#[test]
+fn decodes_boundaries_in_every_lane() {
+    let values: Vec<i32> = [0, -1, 63, -64, 64, 32_767, -32_768, i32::MAX, i32::MIN]
+        .iter()
+        .cycle()
+        .take(9 * 8)
+        .copied()
+        .collect();
+    let mut block = Vec::new();
+    encode_block(&values, &mut block);
+    // Trailing bytes belong to whatever follows the block.
+    block.extend_from_slice(&[0xff; 20]);
+
+    let mut decoded = vec![0; values.len()];
+    let len = decode_block(&block, &mut decoded).unwrap();
+    assert_eq!(len, block.len() - 20);
+    assert_eq!(decoded, values);
+}
+
+#[test]
+fn rejects_every_truncation() {
+    let values = sample(37);
+    let mut block = Vec::new();
+    encode_block(&values, &mut block);
+    let mut decoded = vec![0; values.len()];
+    for len in 0..block.len() {
+        match decode_block(&block[..len], &mut decoded) {
+            Err(DecodeError::Truncated { needed }) => {
+                assert!(len + needed <= block.len(), "{}", len)
+            }
+            other => panic!("{}: {:?}", len, other),
+        }
+    }
+    assert_eq!(control_len(values.len()), 10);
+}
+
+#[test]
+fn empty_block_is_empty() {
+    let mut block = Vec::new();
+    assert_eq!(encode_block(&[], &mut block), 0);
+    assert_eq!(decode_block(&[], &mut []), Ok(0));
+}
@@ -0,0 +1,28 @@
+#![cfg(feature = "std")]
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use zigzag::time::{apply_systemtime_delta, decode_systemtime_delta, encode_systemtime_delta};
+
+fn round_trip(a: SystemTime, b: SystemTime) -> i128 {
+    let nanos = decode_systemtime_delta(encode_systemtime_delta(a, b).unwrap());
+    assert_eq!(apply_systemtime_delta(b, nanos), Ok(a));
+    nanos
+}
+
+#[test]
+fn forward_and_backward_deltas_round_trip() {
+    let b = UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789);
+    let a = b + Duration::new(86_400, 1);
+    assert_eq!(round_trip(a, b), 86_400_000_000_001);
+    assert_eq!(round_trip(b, a), -86_400_000_000_001);
+    assert_eq!(round_trip(a, a), 0);
+}
+
+#[test]
+fn pre_epoch_deltas_round_trip() {
+    let a = UNIX_EPOCH - Duration::new(3600, 500);
+    let b = UNIX_EPOCH + Duration::new(60, 0);
+    assert_eq!(round_trip(a, b), -3_660_000_000_500);
+    assert_eq!(round_trip(b, a), 3_660_000_000_500);
+}
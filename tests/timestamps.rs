@@ -0,0 +1,98 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::timestamps::{
+    decode_timestamps, encode_timestamps, TimestampDecoder, TimestampEncoder,
+};
+use zigzag::DecodeError;
+
+#[test]
+fn uses_the_prefix_codes() {
+    // Deltas of 10, 10, 20, 200, 1200 and 1201 give deltas of delta of 10,
+    // 0, 10, 180, 1000 and 1.
+    let timestamps = [0, 10, 20, 40, 240, 1440, 2641];
+    let encoded = encode_timestamps(&timestamps);
+    assert_eq!(encoded[0], 7);
+    assert_eq!(encoded[1..9], [0; 8]);
+    let bits: String = encoded[9..].iter().map(|b| format!("{:08b}", b)).collect();
+    let expected = [
+        "10 0010100",        // 10
+        "0",                 // 0
+        "10 0010100",        // 10
+        "110 101101000",     // 180
+        "1110 011111010000", // 1000
+        "10 0000010",        // 1
+    ]
+    .concat()
+    .replace(' ', "");
+    assert_eq!(&bits[..expected.len()], expected);
+    assert!(bits[expected.len()..].bytes().all(|b| b == b'0'));
+    assert!(bits.len() - expected.len() < 8);
+    assert_eq!(decode_timestamps(&encoded), Ok(timestamps.to_vec()));
+}
+
+#[test]
+fn round_trips_any_series() {
+    let series: [&[i64]; 6] = [
+        &[],
+        &[i64::MIN],
+        &[i64::MIN, i64::MAX, i64::MIN, 0, i64::MAX],
+        &[5, 5, 5, 5, 5],
+        &[1 << 40, -(1 << 40), 1 << 62, 1],
+        &[100, 90, 80, 85, 90, 4000, 4000],
+    ];
+    for timestamps in series {
+        assert_eq!(
+            decode_timestamps(&encode_timestamps(timestamps)),
+            Ok(timestamps.to_vec())
+        );
+    }
+
+    let regular: Vec<i64> = (0..10_000)
+        .map(|i| 1_700_000_000_000 + i * 1000 + i % 3)
+        .collect();
+    let encoded = encode_timestamps(&regular);
+    assert!(encoded.len() < regular.len() * 2);
+    assert_eq!(decode_timestamps(&encoded), Ok(regular));
+}
+
+#[test]
+fn encodes_and_decodes_incrementally() {
+    let timestamps = [1000, 1060, 1121, 1180, 1240];
+    let mut encoder = TimestampEncoder::new();
+    assert!(encoder.is_empty());
+    encoder.encode_slice(&timestamps[..2]);
+    for &timestamp in &timestamps[2..] {
+        encoder.encode(timestamp);
+    }
+    assert_eq!(encoder.len(), 5);
+    let encoded = encoder.finish();
+    assert_eq!(encoded, encode_timestamps(&timestamps));
+
+    let mut decoder = TimestampDecoder::new(&encoded).unwrap();
+    for (i, &timestamp) in timestamps.iter().enumerate() {
+        assert_eq!(decoder.remaining(), timestamps.len() - i);
+        assert_eq!(decoder.next(), Some(Ok(timestamp)));
+    }
+    assert_eq!(decoder.next(), None);
+}
+
+#[test]
+fn reports_truncation() {
+    let encoded = encode_timestamps(&[1, 2, 1 << 50]);
+    for len in 1..encoded.len() {
+        let results: Vec<_> = TimestampDecoder::new(&encoded[..len]).unwrap().collect();
+        assert!(
+            matches!(results.last(), Some(Err(DecodeError::Truncated { .. }))),
+            "{}",
+            len
+        );
+    }
+    assert_eq!(
+        decode_timestamps(&encoded[..5]),
+        Err(DecodeError::Truncated { needed: 4 })
+    );
+    assert_eq!(
+        TimestampDecoder::new(&[]).err(),
+        Some(DecodeError::Truncated { needed: 1 })
+    );
+}
@@ -0,0 +1,7 @@
+use zigzag::encoded::{OrderPreservingEncoded, ZigzagEncoded};
+
+fn main() {
+    let zigzag = ZigzagEncoded::from_signed(-1);
+    let ordered = OrderPreservingEncoded::from_signed(-1);
+    let _ = zigzag == ordered;
+}
@@ -0,0 +1,20 @@
+use zigzag::ZigZagEncode;
+
+#[derive(ZigZagEncode)]
+enum Direction {
+    Up,
+    Down,
+}
+
+#[derive(ZigZagEncode)]
+struct Generic<T> {
+    value: T,
+}
+
+#[derive(ZigZagEncode)]
+struct Unknown {
+    #[zigzag(rename = "x")]
+    value: i32,
+}
+
+fn main() {}
@@ -0,0 +1,5 @@
+use zigzag::ZigZagEncode;
+
+fn main() {
+    let _: u32 = 5u32.zigzag_encode();
+}
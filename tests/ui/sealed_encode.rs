@@ -0,0 +1,13 @@
+use zigzag::ZigZagEncode;
+
+struct Meters(i32);
+
+impl ZigZagEncode for Meters {
+    type Output = u32;
+
+    fn zigzag_encode(self) -> u32 {
+        self.0.zigzag_encode()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,696 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::varint::{
+    decode_varint_extend, read_zigzag_varint, read_zigzag_varint_unchecked, try_encode_iter,
+    write_zigzag_varint, write_zigzag_varint_slice, zigzag_decode_all, zigzag_encode_all,
+    zigzag_encode_all_reuse, zigzag_varint_len, CachedDecoder, VarintBuf,
+};
+use zigzag::VarintError;
+
+mod common;
+
+fn random_i64s(len: usize) -> Vec<i64> {
+    let mut values = common::mixed_i64s(len);
+    values.extend([0, -1, 1, i64::MIN, i64::MAX]);
+    values
+}
+
+#[test]
+fn unchecked_reader_agrees_with_checked_reader() {
+    let bytes = zigzag_encode_all(&random_i64s(10_000));
+    let mut input = &bytes[..];
+    while !input.is_empty() {
+        let checked = read_zigzag_varint(input).unwrap();
+        // SAFETY: `read_zigzag_varint` accepted the varint at the start of
+        // `input`.
+        let unchecked = unsafe { read_zigzag_varint_unchecked(input) };
+        assert_eq!(checked, unchecked);
+        input = &input[checked.1..];
+    }
+}
+
+#[test]
+fn reused_buffer_holds_independent_results() {
+    let first: Vec<i64> = (-500..500).map(|x| x * 1_000_003).collect();
+    let second = [i64::MIN, 0, i64::MAX];
+
+    let mut out = Vec::new();
+    zigzag_encode_all_reuse(&first, &mut out);
+    assert_eq!(out, zigzag_encode_all(&first));
+    let capacity = out.capacity();
+
+    zigzag_encode_all_reuse(&second, &mut out);
+    assert_eq!(out, zigzag_encode_all(&second));
+    assert_eq!(out.capacity(), capacity);
+    assert_eq!(zigzag_decode_all(&out), Ok(second.to_vec()));
+}
+
+#[test]
+fn varint_buf_is_reusable() {
+    let values: Vec<i64> = (-100..100).map(|x| x * 12345).collect();
+    let mut buf = VarintBuf::new();
+    for &x in &values {
+        buf.push_zigzag(x);
+    }
+    assert_eq!(buf.as_bytes(), &zigzag_encode_all(&values)[..]);
+
+    buf.clear();
+    assert!(buf.as_bytes().is_empty());
+    buf.push_zigzag(i64::MIN);
+    buf.push_zigzag(0);
+    let bytes = buf.take();
+    assert_eq!(zigzag_decode_all(&bytes), Ok(vec![i64::MIN, 0]));
+    assert!(buf.as_bytes().is_empty());
+
+    buf.push_zigzag(1);
+    assert_eq!(buf.as_bytes(), [0x02]);
+}
+
+#[test]
+fn writers_return_bytes_written() {
+    let mut out = Vec::new();
+    let mut buf = [0; 10];
+    for &x in &[0, -1, 63, -64, 64, 8191, -8193, i64::MAX, i64::MIN] {
+        let before = out.len();
+        let written = write_zigzag_varint(x, &mut out);
+        assert_eq!(written, zigzag_varint_len(x));
+        assert_eq!(written, out.len() - before);
+        assert_eq!(write_zigzag_varint_slice(x, &mut buf), Some(written));
+    }
+}
+
+#[test]
+fn cached_decoder_matches_uncached() {
+    let decoder = CachedDecoder::new();
+    for byte in 0..=u8::MAX {
+        let expected = read_zigzag_varint(&[byte]);
+        assert_eq!(decoder.lookup(byte).is_some(), byte < 0x80);
+        if let Some(value) = decoder.lookup(byte) {
+            assert_eq!(Ok((value, 1)), expected);
+        }
+        assert_eq!(decoder.read_zigzag_varint(&[byte]), expected);
+    }
+    for x in random_i64s(1000) {
+        let bytes = zigzag_encode_all(&[x]);
+        assert_eq!(decoder.read_zigzag_varint(&bytes), Ok((x, bytes.len())));
+    }
+    assert_eq!(
+        (-64..=63)
+            .map(|x| decoder.lookup(zigzag_encode_all(&[x])[0]))
+            .collect::<Vec<_>>(),
+        (-64..=63).map(Some).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn extends_prepopulated_vec() {
+    let mut values = vec![1, 2, 3];
+    decode_varint_extend(&zigzag_encode_all(&[-4, i64::MAX]), &mut values).unwrap();
+    assert_eq!(values, [1, 2, 3, -4, i64::MAX]);
+
+    let mut bytes = zigzag_encode_all(&[5]);
+    bytes.push(0xff);
+    assert_eq!(
+        decode_varint_extend(&bytes, &mut values),
+        Err(VarintError::Truncated)
+    );
+    assert_eq!(values, [1, 2, 3, -4, i64::MAX, 5]);
+}
+
+#[test]
+fn try_encode_iter_stops_at_first_error() {
+    let items: Vec<Result<i16, &str>> = vec![Ok(-1), Ok(i16::MIN), Err("bad"), Ok(5), Err("later")];
+    let mut out = vec![0xaa];
+    assert_eq!(try_encode_iter(items, &mut out), Err("bad"));
+    assert_eq!(out[0], 0xaa);
+    assert_eq!(
+        zigzag_decode_all(&out[1..]),
+        Ok(vec![-1, i64::from(i16::MIN)])
+    );
+
+    let mut out = Vec::new();
+    let ok = (0..10i64).map(Ok::<_, ()>);
+    assert_eq!(try_encode_iter(ok, &mut out), Ok(()));
+    assert_eq!(zigzag_decode_all(&out), Ok((0..10).collect()));
+}
+
+#[cfg(target_endian = "little")]
+#[test]
+fn swar_matches_byte_loop() {
+    use zigzag::varint::read_zigzag_varint_swar;
+
+    let mut values = random_i64s(10_000);
+    for shift in 0..64 {
+        let x = 1i64 << shift;
+        values.extend_from_slice(&[x, x.wrapping_sub(1), x.wrapping_neg(), !x]);
+    }
+    let lens: std::collections::BTreeSet<usize> =
+        values.iter().map(|&x| zigzag_varint_len(x)).collect();
+    assert_eq!(lens, (1..=10).collect());
+
+    let bytes = zigzag_encode_all(&values);
+    for start in 0..bytes.len() {
+        let input = &bytes[start..];
+        assert_eq!(read_zigzag_varint_swar(input), read_zigzag_varint(input));
+        // Every truncation of the input must be handled too.
+        let short = &input[..input.len().min(9)];
+        for end in 0..short.len() {
+            assert_eq!(
+                read_zigzag_varint_swar(&short[..end]),
+                read_zigzag_varint(&short[..end])
+            );
+        }
+    }
+    let overflow = [0xff; 10];
+    assert_eq!(
+        read_zigzag_varint_swar(&overflow),
+        read_zigzag_varint(&overflow)
+    );
+}
+
+#[test]
+fn tenth_byte_may_only_hold_bit_63() {
+    let mut input = [0xff; 10];
+    input[9] = 0x01;
+    assert_eq!(read_zigzag_varint(&input), Ok((i64::MIN, 10)));
+    assert_eq!(zigzag_encode_all(&[i64::MIN]), input);
+
+    for last in 0x02..=0xff {
+        input[9] = last;
+        assert_eq!(read_zigzag_varint(&input), Err(VarintError::Overflow));
+    }
+
+    // A tenth byte of zero is redundant but still fits in 64 bits.
+    let mut padded = [0x80; 10];
+    padded[9] = 0x00;
+    assert_eq!(read_zigzag_varint(&padded), Ok((0, 10)));
+}
+
+#[test]
+fn constant_block_matches_naive_loop() {
+    use zigzag::varint::encode_constant_block;
+
+    for &value in &[0, -1, 150, i64::MIN, i64::MAX] {
+        for &count in &[0, 1, 7, 1000] {
+            let mut out = vec![0xaa];
+            encode_constant_block(value, count, &mut out);
+
+            let mut naive = vec![0xaa];
+            for _ in 0..count {
+                write_zigzag_varint(value, &mut naive);
+            }
+            assert_eq!(out, naive);
+            assert_eq!(zigzag_decode_all(&out[1..]), Ok(vec![value; count]));
+        }
+    }
+}
+
+#[test]
+fn dumps_varint_bytes_as_hex() {
+    use zigzag::varint::dump_zigzag_varint;
+
+    let dump = |x| {
+        let mut s = String::new();
+        dump_zigzag_varint(x, &mut s).unwrap();
+        s
+    };
+    assert_eq!(dump(-1), "01");
+    assert_eq!(dump(0), "00");
+    assert_eq!(dump(i64::MIN), "ff ff ff ff ff ff ff ff ff 01");
+    assert_eq!(dump(i64::MAX), "fe ff ff ff ff ff ff ff ff 01");
+}
+
+#[test]
+fn clamped_read_rejects_values_outside_range() {
+    use zigzag::varint::read_zigzag_varint_clamped;
+
+    let read = |x: i64| {
+        let mut out = Vec::new();
+        write_zigzag_varint(x, &mut out);
+        read_zigzag_varint_clamped(&out, -1000, 1000)
+    };
+    assert_eq!(read(-1000), Ok((-1000, 2)));
+    assert_eq!(read(1000), Ok((1000, 2)));
+    assert_eq!(read(0), Ok((0, 1)));
+    assert_eq!(read(-1001), Err(VarintError::OutOfRange));
+    assert_eq!(read(1001), Err(VarintError::OutOfRange));
+    assert_eq!(
+        read_zigzag_varint_clamped(&[0x80], -1000, 1000),
+        Err(VarintError::Truncated)
+    );
+}
+
+#[test]
+fn varint_bytes_from_signed_integers() {
+    use zigzag::varint::VarintBytes;
+
+    assert_eq!(VarintBytes::from(-1i32).as_ref(), &[0x01]);
+    assert_eq!(VarintBytes::from(150i16).as_ref(), &[0xac, 0x02]);
+    assert_eq!(VarintBytes::from(i8::MIN).as_ref(), &[0xff, 0x01]);
+    assert_eq!(
+        VarintBytes::from(i32::MIN).as_ref(),
+        &[0xff, 0xff, 0xff, 0xff, 0x0f]
+    );
+    assert_eq!(
+        VarintBytes::from(i64::MIN),
+        VarintBytes::from_zigzag(i64::MIN)
+    );
+}
+
+#[test]
+fn callback_encoder_matches_write_zigzag_varint() {
+    use zigzag::varint::encode_zigzag_varint_with;
+
+    for x in random_i64s(1000)
+        .into_iter()
+        .chain([0, -1, 1, i64::MIN, i64::MAX])
+    {
+        let mut expected = Vec::new();
+        write_zigzag_varint(x, &mut expected);
+        let mut bytes = Vec::new();
+        encode_zigzag_varint_with(x, |byte| bytes.push(byte));
+        assert_eq!(bytes, expected, "{}", x);
+    }
+}
+
+#[test]
+fn decodes_fixed_count_into_array() {
+    use zigzag::varint::decode_zigzag_varints_array;
+
+    let input = zigzag_encode_all(&[-3, 1000, i64::MIN, 7]);
+    let (values, len) = decode_zigzag_varints_array::<3>(&input).unwrap();
+    assert_eq!(values, [-3, 1000, i64::MIN]);
+    assert_eq!(len, input.len() - 1);
+
+    assert_eq!(decode_zigzag_varints_array::<0>(&[]), Ok(([], 0)));
+    assert_eq!(
+        decode_zigzag_varints_array::<3>(&input[..4]),
+        Err(VarintError::Truncated)
+    );
+}
+
+#[test]
+fn hash_zigzag_is_canonical() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::Hasher;
+    use zigzag::varint::{hash_zigzag, VarintBytes};
+
+    fn hash_of(x: i64) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        hash_zigzag(x, &mut hasher);
+        hasher.finish()
+    }
+
+    for x in random_i64s(100).into_iter().chain([0, -1, i64::MIN]) {
+        assert_eq!(hash_of(x), hash_of(x));
+
+        let mut direct = DefaultHasher::new();
+        direct.write(&VarintBytes::from_zigzag(x));
+        assert_eq!(hash_of(x), direct.finish());
+    }
+    assert_eq!(hash_of(i64::from(-5i32)), hash_of(-5));
+    assert_ne!(hash_of(1), hash_of(-1));
+}
+
+#[test]
+fn control_flow_round_trips() {
+    use std::ops::ControlFlow;
+    use zigzag::varint::{decode_control_flow, encode_control_flow};
+
+    let values = [
+        ControlFlow::Continue(0),
+        ControlFlow::Break(-1),
+        ControlFlow::Continue(i64::MIN),
+        ControlFlow::Break(i64::MAX),
+    ];
+    let mut out = Vec::new();
+    for &cf in &values {
+        encode_control_flow(cf, &mut out);
+    }
+
+    let mut input = &out[..];
+    for &expected in &values {
+        let (cf, len) = decode_control_flow(input).unwrap();
+        assert_eq!(cf, expected);
+        input = &input[len..];
+    }
+    assert!(input.is_empty());
+
+    assert_eq!(decode_control_flow(&[]), Err(VarintError::Truncated));
+    assert_eq!(decode_control_flow(&[0x01]), Err(VarintError::Truncated));
+    assert_eq!(
+        decode_control_flow(&[0x02, 0x00]),
+        Err(VarintError::OutOfRange)
+    );
+}
+
+#[test]
+fn chunks_split_on_varint_boundaries() {
+    use zigzag::varint::split_zigzag_varint_chunks;
+
+    let values = random_i64s(1000);
+    let input = zigzag_encode_all(&values);
+    for approx_chunks in [0, 1, 2, 3, 7, 64, 1000, 5000] {
+        let chunks = split_zigzag_varint_chunks(&input, approx_chunks);
+        assert!(chunks.len() <= approx_chunks.max(1));
+        assert_eq!(chunks.concat(), input);
+
+        let decoded: Vec<i64> = chunks
+            .iter()
+            .flat_map(|chunk| zigzag_decode_all(chunk).unwrap())
+            .collect();
+        assert_eq!(decoded, values);
+    }
+    assert!(split_zigzag_varint_chunks(&[], 4).is_empty());
+}
+
+#[test]
+fn transcodes_native_endian_i32s() {
+    use zigzag::varint::transcode_raw_i32_to_varint;
+    use zigzag::LengthError;
+
+    let values = [-1i32, i32::MIN, 150];
+    let bytes: Vec<u8> = values.iter().flat_map(|x| x.to_ne_bytes()).collect();
+    assert_eq!(bytes.len(), 12);
+
+    let mut out = Vec::new();
+    transcode_raw_i32_to_varint(&bytes, &mut out).unwrap();
+    let expected: Vec<i64> = values.iter().map(|&x| i64::from(x)).collect();
+    assert_eq!(out, zigzag_encode_all(&expected));
+
+    let mut out = vec![0xaa];
+    assert_eq!(
+        transcode_raw_i32_to_varint(&bytes[..11], &mut out),
+        Err(LengthError {
+            len: 11,
+            value_size: 4
+        })
+    );
+    assert_eq!(out, [0xaa]);
+}
+
+#[test]
+fn decode_context_enforces_max_len() {
+    use zigzag::varint::DecodeContext;
+
+    let values = random_i64s(1000);
+    let input = zigzag_encode_all(&values);
+    let context = DecodeContext::new();
+    let mut offset = 0;
+    while offset < input.len() {
+        let result = context.decode(&input[offset..]);
+        assert_eq!(result, read_zigzag_varint(&input[offset..]));
+        offset += result.unwrap().1;
+    }
+
+    let context = DecodeContext::with_max_len(5);
+    assert_eq!(context.max_len(), 5);
+    let max_i32 = zigzag_encode_all(&[i64::from(i32::MIN)]);
+    assert_eq!(context.decode(&max_i32), Ok((i64::from(i32::MIN), 5)));
+    let too_long = zigzag_encode_all(&[-(1 << 35)]);
+    assert_eq!(context.decode(&too_long), Err(VarintError::Overflow));
+    assert_eq!(context.decode(&too_long[..5]), Err(VarintError::Truncated));
+
+    assert_eq!(DecodeContext::with_max_len(100), DecodeContext::new());
+}
+
+#[test]
+fn reports_offsets_of_varint_boundaries() {
+    use zigzag::varint::decode_varint_with_offsets;
+
+    let values = random_i64s(1000);
+    let input = zigzag_encode_all(&values);
+    let decoded: Vec<(usize, i64)> = decode_varint_with_offsets(&input)
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(decoded.len(), values.len());
+    let mut expected_offset = 0;
+    for (&(offset, value), &expected) in decoded.iter().zip(&values) {
+        assert_eq!(offset, expected_offset);
+        assert_eq!(value, expected);
+        assert_eq!(read_zigzag_varint(&input[offset..]).unwrap().0, value);
+        expected_offset += zigzag_varint_len(expected);
+    }
+    assert!(decoded.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+    let mut iter = decode_varint_with_offsets(&[0x02, 0x80]);
+    assert_eq!(iter.next(), Some(Ok((0, 1))));
+    assert_eq!(iter.next(), Some(Err(VarintError::Truncated)));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn migrates_fixed_width_to_varint() {
+    use std::convert::TryInto;
+    use zigzag::varint::migrate_fixed_to_varint_i32;
+    use zigzag::{ZigZagDecode, ZigZagEncode};
+
+    let values = [-1i32, 0, 150, i32::MIN];
+    let input: Vec<u8> = values
+        .iter()
+        .flat_map(|&x| ZigZagEncode::zigzag_encode(x).to_le_bytes())
+        .collect();
+
+    let mut out = Vec::new();
+    migrate_fixed_to_varint_i32(&input, &mut out).unwrap();
+    assert!(out.len() < input.len());
+
+    let original: Vec<i64> = input
+        .chunks_exact(4)
+        .map(|chunk| {
+            let raw = u32::from_le_bytes(chunk.try_into().unwrap());
+            i64::from(ZigZagDecode::zigzag_decode(raw))
+        })
+        .collect();
+    assert_eq!(zigzag_decode_all(&out), Ok(original));
+    assert_eq!(out, [0x01, 0x00, 0xac, 0x02, 0xff, 0xff, 0xff, 0xff, 0x0f]);
+
+    assert!(migrate_fixed_to_varint_i32(&input[..7], &mut out).is_err());
+}
+
+#[test]
+fn parses_varint_bytes() {
+    use zigzag::varint::VarintBytes;
+
+    let input = zigzag_encode_all(&[i64::MIN, -1, 150]);
+    let (first, rest) = VarintBytes::parse(&input).unwrap();
+    assert_eq!(first.decode(), i64::MIN);
+    assert_eq!(first, VarintBytes::from_zigzag(i64::MIN));
+    let (second, rest) = VarintBytes::parse(rest).unwrap();
+    assert_eq!(second.decode(), -1);
+    let (third, rest) = VarintBytes::parse(rest).unwrap();
+    assert_eq!((third.decode(), third.decode_uvarint()), (150, 300));
+    assert!(rest.is_empty());
+
+    let (padded, _) = VarintBytes::parse(&[0x81, 0x00]).unwrap();
+    assert_eq!(&*padded, &[0x81, 0x00]);
+    assert_eq!(padded.decode(), -1);
+    assert_eq!(VarintBytes::parse(&[0x81]), Err(VarintError::Truncated));
+}
+
+#[test]
+fn peeking_does_not_consume() {
+    use zigzag::varint::{parse_zigzag_varint, peek_len, peek_zigzag_varint};
+
+    let values = random_i64s(100);
+    let encoded = zigzag_encode_all(&values);
+    let mut input = &encoded[..];
+    for &expected in &values {
+        assert_eq!(peek_zigzag_varint(input), Ok(expected));
+        assert_eq!(peek_zigzag_varint(input), Ok(expected));
+        let len = peek_len(input).unwrap();
+        let (value, rest) = parse_zigzag_varint(input).unwrap();
+        assert_eq!(value, expected);
+        assert_eq!(input.len() - rest.len(), len);
+        input = rest;
+    }
+    assert_eq!(peek_len(input), Err(VarintError::Truncated));
+}
+
+#[test]
+fn ringbuf_decode_stops_at_capacity_and_resumes() {
+    use std::collections::VecDeque;
+    use zigzag::varint::decode_into_ringbuf;
+
+    let values = random_i64s(100);
+    let input = zigzag_encode_all(&values);
+    let mut ring = VecDeque::new();
+    let mut decoded = Vec::new();
+    let mut offset = 0;
+    while offset < input.len() {
+        offset += decode_into_ringbuf(&input[offset..], &mut ring, 7).unwrap();
+        assert!(ring.len() == 7 || offset == input.len());
+        // Drain only part of the ring, as a slow consumer would.
+        decoded.extend(ring.drain(..3.min(ring.len())));
+    }
+    decoded.extend(ring.drain(..));
+    assert_eq!(decoded, values);
+
+    let mut full: VecDeque<i64> = (0..3).collect();
+    assert_eq!(decode_into_ringbuf(&input, &mut full, 3), Ok(0));
+    assert_eq!(
+        decode_into_ringbuf(&[0x02, 0x80], &mut full, 5),
+        Err(VarintError::Truncated)
+    );
+    assert_eq!(full, [0, 1, 2, 1]);
+}
+
+// Test vectors from the protobuf encoding guide and the Avro specification.
+#[test]
+fn encode_varint_matches_protobuf_and_avro() {
+    use zigzag::varint::{decode_varint, encode_varint};
+
+    let cases: &[(i64, &[u8])] = &[
+        (0, &[0x00]),
+        (-1, &[0x01]),
+        (1, &[0x02]),
+        (-2, &[0x03]),
+        (-64, &[0x7f]),
+        (64, &[0x80, 0x01]),
+        (i64::from(i32::MAX), &[0xfe, 0xff, 0xff, 0xff, 0x0f]),
+        (i64::from(i32::MIN), &[0xff, 0xff, 0xff, 0xff, 0x0f]),
+        (
+            i64::MIN,
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+        ),
+    ];
+    for &(value, bytes) in cases {
+        let mut out = Vec::new();
+        assert_eq!(encode_varint(value, &mut out), bytes.len());
+        assert_eq!(out, bytes);
+        assert_eq!(decode_varint(bytes), Ok((value, bytes.len())));
+    }
+
+    for x in random_i64s(1000) {
+        let mut out = Vec::new();
+        let len = encode_varint(x, &mut out);
+        assert_eq!(decode_varint(&out), Ok((x, len)));
+    }
+    assert_eq!(decode_varint(&[0x80]), Err(VarintError::Truncated));
+}
+
+#[test]
+fn decode_varint_from_slice_needs_the_whole_input() {
+    use zigzag::varint::decode_varint_from_slice;
+    use zigzag::DecodeError;
+
+    for x in random_i64s(1000) {
+        let mut out = Vec::new();
+        write_zigzag_varint(x, &mut out);
+        assert_eq!(decode_varint_from_slice(&out), Ok(x));
+        assert_eq!(
+            decode_varint_from_slice(&out[..out.len() - 1]),
+            Err(DecodeError::Truncated { needed: 1 })
+        );
+        out.push(0);
+        assert_eq!(
+            decode_varint_from_slice(&out),
+            Err(DecodeError::TrailingGarbage)
+        );
+    }
+    assert_eq!(
+        decode_varint_from_slice(&[0xff; 11]),
+        Err(DecodeError::Overflow)
+    );
+    assert_eq!(
+        DecodeError::from(VarintError::Truncated),
+        DecodeError::Truncated { needed: 1 }
+    );
+    assert_eq!(
+        DecodeError::Truncated { needed: 3 }.to_string(),
+        "input is truncated: 3 more bytes needed"
+    );
+}
+
+#[test]
+fn cursor_resumes_after_errors() {
+    use zigzag::varint::{VarintCursor, ZigZagVarintIter};
+    use zigzag::DecodeError;
+
+    let good = zigzag_encode_all(&[-1, 150, i64::MIN]);
+    let mut input = good.clone();
+    input.extend_from_slice(&[0x80; 10]);
+    input.push(0x00);
+    input.extend_from_slice(&good);
+
+    let mut iter = ZigZagVarintIter::new(&input);
+    assert_eq!(iter.by_ref().take(3).count(), 3);
+    assert_eq!(iter.remaining(), &input[good.len()..]);
+    assert_eq!(iter.next(), Some(Err(VarintError::Overflow)));
+    assert!(iter.remaining().is_empty());
+
+    let mut cursor = VarintCursor::new(&input);
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+    loop {
+        match cursor.next() {
+            Some(Ok(value)) => values.push(value),
+            Some(Err(err)) => {
+                errors.push((cursor.position(), err));
+                assert_eq!(cursor.next(), None);
+                cursor.resync();
+            }
+            None => break,
+        }
+    }
+    assert_eq!(values, [-1, 150, i64::MIN, -1, 150, i64::MIN]);
+    assert_eq!(errors, [(good.len(), DecodeError::Overflow)]);
+
+    cursor.set_position(1);
+    assert_eq!(cursor.next(), Some(Ok(150)));
+
+    let mut cursor = VarintCursor::new(&good[..good.len() - 1]);
+    assert_eq!(cursor.by_ref().take(2).count(), 2);
+    assert_eq!(
+        cursor.next(),
+        Some(Err(DecodeError::Truncated { needed: 1 }))
+    );
+    assert_eq!(cursor.position(), 3);
+    assert_eq!(cursor.remaining().len(), 9);
+}
+
+#[test]
+fn bulk_decoder_matches_one_at_a_time() {
+    use zigzag::varint::decode_varints_into;
+
+    // Mostly one- and two-byte varints, in runs of varying length so that
+    // each fast path starts at every alignment.
+    let mut values = Vec::new();
+    for (i, x) in random_i64s(20_000).into_iter().enumerate() {
+        values.push(match i % 7 {
+            0..=2 => x >> 57,
+            3..=4 => x >> 50,
+            5 => x >> (i % 64),
+            _ => x,
+        });
+    }
+    values.extend(std::iter::repeat_n(5, 100));
+    values.extend(std::iter::repeat_n(-4000, 100));
+
+    let bytes = zigzag_encode_all(&values);
+    let mut out = vec![42];
+    assert_eq!(decode_varints_into(&bytes, &mut out), Ok(values.len()));
+    assert_eq!(out[0], 42);
+    assert_eq!(out[1..], values[..]);
+
+    // Every truncation, and a malformed varint at every position, must leave
+    // behind the values before it, as decoding one at a time does.
+    let mut malformed = bytes[..300].to_vec();
+    malformed.extend_from_slice(&[0xff; 10]);
+    malformed.extend_from_slice(&bytes[300..400]);
+    for start in 0..320 {
+        for input in [&bytes[start..400], &malformed[start..]] {
+            for end in (0..input.len()).rev().take(24) {
+                let mut expected = Vec::new();
+                let result = decode_varint_extend(&input[..end], &mut expected);
+                let mut actual = Vec::new();
+                assert_eq!(
+                    decode_varints_into(&input[..end], &mut actual),
+                    result.map(|()| expected.len()).map_err(Into::into)
+                );
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "alloc")]
+
+use zigzag::varint::zigzag_encode_all;
+use zigzag::varint_be::{read_zigzag_varint_be, transcode_be_to_le, write_zigzag_varint_be};
+use zigzag::VarintError;
+
+#[test]
+fn transcodes_to_standard_le_encoding() {
+    let mut values = vec![0, -1, 1, -64, 64, i64::MIN, i64::MAX];
+    for shift in 0..63 {
+        values.push(1 << shift);
+        values.push(-(1 << shift));
+    }
+    let mut be = Vec::new();
+    for &x in &values {
+        write_zigzag_varint_be(x, &mut be);
+    }
+
+    let mut offset = 0;
+    for &x in &values {
+        let (value, len) = read_zigzag_varint_be(&be[offset..]).unwrap();
+        assert_eq!(value, x);
+        offset += len;
+    }
+    assert_eq!(offset, be.len());
+
+    let mut le = Vec::new();
+    transcode_be_to_le(&be, &mut le).unwrap();
+    assert_eq!(le, zigzag_encode_all(&values));
+}
+
+#[test]
+fn rejects_malformed_be_varints() {
+    let mut out = Vec::new();
+    assert_eq!(
+        transcode_be_to_le(&[0x01, 0x81], &mut out),
+        Err(VarintError::Truncated)
+    );
+    assert_eq!(out, [0x01]);
+
+    let mut overlong = [0x80; 11];
+    overlong[10] = 0x01;
+    assert_eq!(read_zigzag_varint_be(&overlong), Err(VarintError::Overflow));
+
+    let mut too_big = [0xff; 10];
+    too_big[0] = 0x82;
+    too_big[9] = 0x7f;
+    assert_eq!(read_zigzag_varint_be(&too_big), Err(VarintError::Overflow));
+    too_big[0] = 0x81;
+    assert_eq!(read_zigzag_varint_be(&too_big), Ok((i64::MIN, 10)));
+}
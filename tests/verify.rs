@@ -0,0 +1,37 @@
+use zigzag::{
+    find_first_roundtrip_failure, find_first_roundtrip_failure_with, ZigZagDecode, ZigZagEncode,
+};
+
+mod common;
+
+fn values() -> Vec<i64> {
+    let mut values = common::mixed_i64s(10_000);
+    values.extend([0, -1, 1, i64::MIN, i64::MAX]);
+    values
+}
+
+#[test]
+fn scalar_codec_round_trips() {
+    assert_eq!(find_first_roundtrip_failure(&values()), None);
+    assert_eq!(find_first_roundtrip_failure(&[]), None);
+}
+
+#[test]
+fn pinpoints_injected_fault() {
+    // Drops the top bit of the encoding, which only matters for values whose
+    // magnitude needs all 64 bits.
+    fn faulty_encode(x: i64) -> u64 {
+        ZigZagEncode::zigzag_encode(x) & !(1 << 63)
+    }
+
+    let values = values();
+    let (index, value, decoded) =
+        find_first_roundtrip_failure_with(&values, faulty_encode, ZigZagDecode::zigzag_decode)
+            .unwrap();
+    assert!(values[..index]
+        .iter()
+        .all(|&x| ZigZagEncode::zigzag_encode(x) >> 63 == 0));
+    assert_eq!(values[index], value);
+    assert_ne!(value, decoded);
+    assert_eq!(decoded, ZigZagDecode::zigzag_decode(faulty_encode(value)));
+}
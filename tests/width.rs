@@ -0,0 +1,104 @@
+use std::convert::TryFrom;
+
+use zigzag::width::{
+    demote_encoded_u32_to_u16, promote_encoded_u16_to_u32, zigzag_decode_narrowest,
+    zigzag_encode_narrowest, NarrowSigned, NarrowUnsigned,
+};
+use zigzag::{OutOfRange, ZigZagDecode, ZigZagEncode};
+
+#[test]
+fn promotes_boundaries() {
+    assert_eq!(
+        promote_encoded_u16_to_u32(u16::MAX),
+        i32::from(i16::MIN).zigzag_encode()
+    );
+    assert_eq!(
+        promote_encoded_u16_to_u32(u16::MAX - 1),
+        i32::from(i16::MAX).zigzag_encode()
+    );
+    assert_eq!(promote_encoded_u16_to_u32(1), (-1i32).zigzag_encode());
+    assert_eq!(promote_encoded_u16_to_u32(0), 0);
+}
+
+#[test]
+fn promotes_every_value() {
+    for x in i16::MIN..=i16::MAX {
+        let promoted = promote_encoded_u16_to_u32(x.zigzag_encode());
+        assert_eq!(ZigZagDecode::zigzag_decode(promoted), i32::from(x));
+    }
+}
+
+#[test]
+fn demotes_in_range_values() {
+    let min = i32::from(i16::MIN).zigzag_encode();
+    let max = i32::from(i16::MAX).zigzag_encode();
+    assert_eq!(demote_encoded_u32_to_u16(min), Ok(u16::MAX));
+    assert_eq!(demote_encoded_u32_to_u16(max), Ok(u16::MAX - 1));
+    assert_eq!(demote_encoded_u32_to_u16(1), Ok(1));
+}
+
+#[test]
+fn rejects_out_of_range_values() {
+    let below = (i32::from(i16::MIN) - 1).zigzag_encode();
+    let above = (i32::from(i16::MAX) + 1).zigzag_encode();
+    assert_eq!(demote_encoded_u32_to_u16(below), Err(OutOfRange));
+    assert_eq!(demote_encoded_u32_to_u16(above), Err(OutOfRange));
+    assert_eq!(demote_encoded_u32_to_u16(u32::MAX), Err(OutOfRange));
+}
+
+#[test]
+fn narrowest_round_trips_at_boundaries() {
+    for shift in 0..64 {
+        let x = 1i64 << shift;
+        for &value in &[x, x.wrapping_sub(1), x.wrapping_neg(), !x] {
+            let encoded = zigzag_encode_narrowest(value);
+            let decoded = zigzag_decode_narrowest(encoded.get());
+            assert_eq!(decoded.get(), value);
+            assert_eq!(
+                matches!(decoded, NarrowSigned::I8(_)),
+                i8::try_from(value).is_ok()
+            );
+            assert_eq!(
+                matches!(encoded, NarrowUnsigned::U8(_)),
+                matches!(decoded, NarrowSigned::I8(_))
+            );
+        }
+    }
+}
+
+#[test]
+fn narrows_i128_before_encoding() {
+    use zigzag::width::{decode_u32_to_i128, try_encode_i128_as_u32};
+
+    for &x in &[0, -1, 1, i128::from(i32::MIN), i128::from(i32::MAX)] {
+        let encoded = try_encode_i128_as_u32(x).unwrap();
+        assert_eq!(encoded, i32::try_from(x).unwrap().zigzag_encode());
+        assert_eq!(decode_u32_to_i128(encoded), x);
+    }
+    assert_eq!(
+        try_encode_i128_as_u32(i128::from(i32::MAX) + 1),
+        Err(OutOfRange)
+    );
+    assert_eq!(
+        try_encode_i128_as_u32(i128::from(i32::MIN) - 1),
+        Err(OutOfRange)
+    );
+    // Wrapping with `as` would have produced the encoding of zero.
+    assert_eq!(try_encode_i128_as_u32(1 << 32), Err(OutOfRange));
+}
+
+#[test]
+fn narrow_unsigned_orders_by_decoded_value() {
+    assert!(NarrowUnsigned::U8(1) < NarrowUnsigned::U64(10));
+    assert!(NarrowUnsigned::U64(u64::MAX) < NarrowUnsigned::U8(1));
+    assert!(NarrowUnsigned::U64(u64::MAX - 1) > NarrowUnsigned::U32(u32::MAX - 1));
+    assert_eq!(NarrowUnsigned::U16(3), NarrowUnsigned::U64(3));
+
+    let mut values: Vec<i64> = vec![5, -1, i64::MIN, 300, -70_000, i64::MAX, 0];
+    let mut encoded: Vec<NarrowUnsigned> =
+        values.iter().map(|&x| zigzag_encode_narrowest(x)).collect();
+    encoded.sort();
+    values.sort();
+    let decoded: Vec<i64> = encoded.iter().map(|x| x.decode()).collect();
+    assert_eq!(decoded, values);
+}
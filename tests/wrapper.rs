@@ -0,0 +1,49 @@
+#![cfg(feature = "alloc")]
+
+use std::num::{Saturating, Wrapping};
+
+use zigzag::{ZigZagDecode, ZigZagEncode};
+
+#[test]
+fn arrays_encode_element_wise() {
+    let values = [0i32, -1, 1, i32::MIN];
+    let encoded: [u32; 4] = values.zigzag_encode();
+    assert_eq!(encoded, [0, 1, 2, u32::MAX]);
+    let decoded: [i32; 4] = encoded.zigzag_decode();
+    assert_eq!(decoded, values);
+
+    let nested = [[Some(-2i8), None], [Some(i8::MAX), Some(0)]];
+    let encoded: [[Option<u8>; 2]; 2] = nested.zigzag_encode();
+    assert_eq!(encoded, [[Some(3), None], [Some(254), Some(0)]]);
+    assert_eq!(zigzag::decode(encoded), nested);
+}
+
+#[test]
+fn vecs_encode_element_wise() {
+    let values = vec![Some(-3i64), None, Some(i64::MAX)];
+    let encoded: Vec<Option<u64>> = values.clone().zigzag_encode();
+    assert_eq!(encoded, [Some(5), None, Some(u64::MAX - 1)]);
+    let decoded: Vec<Option<i64>> = encoded.zigzag_decode();
+    assert_eq!(decoded, values);
+
+    let empty: Vec<u16> = Vec::<i16>::new().zigzag_encode();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn wrappers_round_trip_without_unwrapping() {
+    let sum = Wrapping(i32::MAX) + Wrapping(1);
+    let encoded = sum.zigzag_encode();
+    assert_eq!(encoded, Wrapping(u32::MAX));
+    assert_eq!(encoded.zigzag_decode(), Wrapping(i32::MIN));
+
+    let deltas = [Wrapping(-1i8), Wrapping(2)];
+    let encoded = deltas.zigzag_encode();
+    assert_eq!(encoded, [Wrapping(1), Wrapping(4)]);
+    assert_eq!(zigzag::decode(encoded), deltas);
+
+    let saturated = [Saturating(i16::MIN) - Saturating(1), Saturating(5)];
+    let encoded = saturated.zigzag_encode();
+    assert_eq!(encoded, [Saturating(u16::MAX), Saturating(10)]);
+    assert_eq!(encoded.zigzag_decode(), saturated);
+}
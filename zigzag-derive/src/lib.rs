@@ -0,0 +1,254 @@
+//! Derive macros for zigzag encoding the fields of a struct.
+//!
+//! This crate is an implementation detail of the `residua-zigzag` crate,
+//! which re-exports its macros with the `derive` feature enabled. Depend on
+//! that crate instead.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, Ident, LitStr, Path};
+
+/// Generates a mirror of a struct in which every field is zigzag encoded,
+/// along with an inherent `zigzag_encode` method converting to it.
+///
+/// The mirror struct is named `Encoded` followed by the name of the struct,
+/// and has the same visibility and field names. Each field of type `T` has
+/// the type `<T as ZigZagEncode>::Output` instead, so signed integers become
+/// unsigned ones, and the field's doc comments are carried over.
+///
+/// The struct must not be generic. The following attributes are accepted:
+///
+/// - `#[zigzag(name = "...")]` on the struct names the mirror struct.
+/// - `#[zigzag(derive(...))]` on the struct derives the given traits for
+///   the mirror struct.
+/// - `#[zigzag(skip)]` on a field copies it into the mirror struct as is,
+///   which allows fields whose types aren't integers.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::ZigZagEncode;
+///
+/// #[derive(ZigZagEncode)]
+/// #[zigzag(derive(Debug, PartialEq))]
+/// struct Packet {
+///     dx: i16,
+///     dy: i32,
+///     #[zigzag(skip)]
+///     flags: u8,
+/// }
+///
+/// let packet = Packet { dx: -1, dy: 2, flags: 0x80 };
+/// assert_eq!(
+///     packet.zigzag_encode(),
+///     EncodedPacket { dx: 1u16, dy: 4u32, flags: 0x80 },
+/// );
+/// ```
+#[proc_macro_derive(ZigZagEncode, attributes(zigzag))]
+pub fn derive_zigzag_encode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_encode(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Generates an inherent `zigzag_decode` method on the mirror struct of
+/// [`ZigZagEncode`](derive@ZigZagEncode), converting it back to the
+/// original struct.
+///
+/// The struct must derive `ZigZagEncode` as well, which generates the
+/// mirror struct itself, and is subject to the same attributes.
+///
+/// # Examples
+///
+/// ```
+/// use zigzag::{ZigZagDecode, ZigZagEncode};
+///
+/// #[derive(ZigZagEncode, ZigZagDecode, Debug, PartialEq)]
+/// struct Delta(i64, i8);
+///
+/// let encoded = Delta(-3, i8::MIN).zigzag_encode();
+/// assert_eq!((encoded.0, encoded.1), (5u64, 255u8));
+/// assert_eq!(encoded.zigzag_decode(), Delta(-3, i8::MIN));
+/// ```
+#[proc_macro_derive(ZigZagDecode, attributes(zigzag))]
+pub fn derive_zigzag_decode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_decode(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The parsed struct and the attributes on it.
+struct Container<'a> {
+    fields: &'a Fields,
+    encoded: Ident,
+    derives: Vec<Path>,
+    skipped: Vec<bool>,
+}
+
+impl<'a> Container<'a> {
+    fn parse(input: &'a DeriveInput) -> syn::Result<Self> {
+        let fields = match &input.data {
+            Data::Struct(data) => &data.fields,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "zigzag derives only support structs",
+                ))
+            }
+        };
+        if !input.generics.params.is_empty() || input.generics.where_clause.is_some() {
+            return Err(Error::new_spanned(
+                &input.generics,
+                "zigzag derives don't support generic structs",
+            ));
+        }
+
+        let mut encoded = format_ident!("Encoded{}", input.ident);
+        let mut derives = Vec::new();
+        for attr in zigzag_attrs(&input.attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    encoded = meta.value()?.parse::<LitStr>()?.parse()?;
+                    Ok(())
+                } else if meta.path.is_ident("derive") {
+                    meta.parse_nested_meta(|derive| {
+                        derives.push(derive.path);
+                        Ok(())
+                    })
+                } else {
+                    Err(meta.error("expected `name` or `derive`"))
+                }
+            })?;
+        }
+
+        let mut skipped = Vec::new();
+        for field in fields {
+            let mut skip = false;
+            for attr in zigzag_attrs(&field.attrs) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("skip") {
+                        skip = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `skip`"))
+                    }
+                })?;
+            }
+            skipped.push(skip);
+        }
+
+        Ok(Container {
+            fields,
+            encoded,
+            derives,
+            skipped,
+        })
+    }
+
+    /// Returns a struct expression building `ty` from the fields of `self`,
+    /// transforming each field which isn't skipped with `transform`.
+    fn convert(&self, ty: &Ident, transform: TokenStream2) -> TokenStream2 {
+        let fields = self
+            .fields
+            .members()
+            .zip(&self.skipped)
+            .map(|(member, &skip)| {
+                if skip {
+                    quote!(#member: self.#member)
+                } else {
+                    quote!(#member: #transform(self.#member))
+                }
+            });
+        quote!(#ty { #(#fields,)* })
+    }
+}
+
+fn zigzag_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("zigzag"))
+}
+
+fn expand_encode(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let container = Container::parse(input)?;
+    let Container {
+        encoded, derives, ..
+    } = &container;
+    let name = &input.ident;
+    let vis = &input.vis;
+
+    let fields = container
+        .fields
+        .iter()
+        .zip(&container.skipped)
+        .map(|(field, &skip)| {
+            let docs = field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("doc"));
+            let vis = &field.vis;
+            let ident = field.ident.iter();
+            let ty = &field.ty;
+            let ty = if skip {
+                quote!(#ty)
+            } else {
+                quote!(<#ty as ::zigzag::ZigZagEncode>::Output)
+            };
+            quote!(#(#docs)* #vis #(#ident:)* #ty)
+        });
+    let body = match container.fields {
+        Fields::Named(_) => quote!({ #(#fields,)* }),
+        Fields::Unnamed(_) => quote!(( #(#fields,)* );),
+        Fields::Unit => quote!(;),
+    };
+
+    let doc = format!("The zigzag-encoded form of [`{}`].", name);
+    let derives = if derives.is_empty() {
+        None
+    } else {
+        Some(quote!(#[derive(#(#derives),*)]))
+    };
+    let convert = container.convert(encoded, quote!(::zigzag::encode));
+    let method_doc = format!(
+        "Zigzag encodes every field of `self`, returning an [`{}`].",
+        encoded
+    );
+
+    Ok(quote! {
+        #[doc = #doc]
+        #derives
+        #vis struct #encoded #body
+
+        impl #name {
+            #[doc = #method_doc]
+            #[inline]
+            #vis fn zigzag_encode(self) -> #encoded {
+                #convert
+            }
+        }
+    })
+}
+
+fn expand_decode(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let container = Container::parse(input)?;
+    let name = &input.ident;
+    let vis = &input.vis;
+    let encoded = &container.encoded;
+
+    let convert = container.convert(name, quote!(::zigzag::decode));
+    let method_doc = format!(
+        "Zigzag decodes every field of `self`, returning a [`{}`].",
+        name
+    );
+
+    Ok(quote! {
+        impl #encoded {
+            #[doc = #method_doc]
+            #[inline]
+            #vis fn zigzag_decode(self) -> #name {
+                #convert
+            }
+        }
+    })
+}